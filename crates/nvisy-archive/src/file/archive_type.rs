@@ -5,6 +5,7 @@
 
 use std::ffi::OsStr;
 use std::fmt;
+use std::path::Path;
 
 /// Supported archive types
 ///
@@ -23,12 +24,109 @@ pub enum ArchiveType {
     TarBz2,
     /// XZ compressed TAR archive
     TarXz,
+    /// Zstandard compressed TAR archive
+    TarZst,
+    /// LZ4 compressed TAR archive
+    TarLz4,
     /// GZIP compression (single file)
     Gz,
     /// BZIP2 compression (single file)
     Bz2,
     /// XZ compression (single file)
     Xz,
+    /// Zstandard compression (single file)
+    Zstd,
+    /// LZ4 compression (single file)
+    Lz4,
+    /// Unix `ar` archive format (static libraries, Debian packages)
+    Ar,
+}
+
+/// Outer container format, independent of any compression filter applied
+/// on top of it
+///
+/// Paired with a [`CompressionFilter`] to classify an archive the way
+/// `libarchive` does: the container governs entry layout (names, sizes,
+/// permissions), while the filter is just a byte-stream transform wrapped
+/// around the container (or, for [`ContainerFormat::Raw`], around a single
+/// file with no container at all).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerFormat {
+    /// TAR container
+    Tar,
+    /// ZIP container (ZIP already includes its own per-entry compression,
+    /// so it's only ever paired with [`CompressionFilter::None`])
+    Zip,
+    /// 7-Zip container
+    SevenZip,
+    /// `cpio` container
+    Cpio,
+    /// Unix `ar` container (static libraries, Debian packages)
+    Ar,
+    /// ISO 9660 optical disc image
+    Iso9660,
+    /// BSD `mtree` directory manifest
+    Mtree,
+    /// RAR container
+    Rar,
+    /// No container — a single file wrapped directly in a compression
+    /// filter (or left uncompressed)
+    Raw,
+}
+
+/// Compression filter applied on top of a [`ContainerFormat`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressionFilter {
+    /// No compression
+    None,
+    /// Gzip compression
+    Gzip,
+    /// Bzip2 compression
+    Bzip2,
+    /// XZ/LZMA2 compression
+    Xz,
+    /// Legacy LZMA compression (the predecessor to XZ)
+    Lzma,
+    /// Zstandard compression
+    Zstd,
+    /// LZ4 compression
+    Lz4,
+    /// Lzip compression
+    Lzip,
+    /// Unix `compress` (`.Z`) compression
+    Compress,
+}
+
+impl fmt::Display for ContainerFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tar => write!(f, "TAR"),
+            Self::Zip => write!(f, "ZIP"),
+            Self::SevenZip => write!(f, "7z"),
+            Self::Cpio => write!(f, "cpio"),
+            Self::Ar => write!(f, "ar"),
+            Self::Iso9660 => write!(f, "ISO 9660"),
+            Self::Mtree => write!(f, "mtree"),
+            Self::Rar => write!(f, "RAR"),
+            Self::Raw => write!(f, "raw"),
+        }
+    }
+}
+
+impl fmt::Display for CompressionFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "no"),
+            Self::Gzip => write!(f, "gzip"),
+            Self::Bzip2 => write!(f, "bzip2"),
+            Self::Xz => write!(f, "xz"),
+            Self::Lzma => write!(f, "lzma"),
+            Self::Zstd => write!(f, "zstd"),
+            Self::Lz4 => write!(f, "lz4"),
+            Self::Lzip => write!(f, "lzip"),
+            Self::Compress => write!(f, "compress"),
+        }
+    }
 }
 
 impl ArchiveType {
@@ -59,9 +157,14 @@ impl ArchiveType {
             "tar.gz" | "tgz" => Some(Self::TarGz),
             "tar.bz2" | "tbz2" | "tb2" => Some(Self::TarBz2),
             "tar.xz" | "txz" => Some(Self::TarXz),
+            "tar.zst" | "tzst" => Some(Self::TarZst),
+            "tar.lz4" | "tlz4" => Some(Self::TarLz4),
             "gz" | "gzip" => Some(Self::Gz),
             "bz2" | "bzip2" => Some(Self::Bz2),
             "xz" => Some(Self::Xz),
+            "zst" | "zstd" => Some(Self::Zstd),
+            "lz4" => Some(Self::Lz4),
+            "ar" => Some(Self::Ar),
             _ => None,
         }
     }
@@ -86,9 +189,14 @@ impl ArchiveType {
             Self::TarGz => &["tar.gz", "tgz"],
             Self::TarBz2 => &["tar.bz2", "tbz2", "tb2"],
             Self::TarXz => &["tar.xz", "txz"],
+            Self::TarZst => &["tar.zst", "tzst"],
+            Self::TarLz4 => &["tar.lz4", "tlz4"],
             Self::Gz => &["gz", "gzip"],
             Self::Bz2 => &["bz2", "bzip2"],
             Self::Xz => &["xz"],
+            Self::Zstd => &["zst", "zstd"],
+            Self::Lz4 => &["lz4"],
+            Self::Ar => &["ar"],
         }
     }
 
@@ -108,16 +216,169 @@ impl ArchiveType {
         self.file_extensions()[0]
     }
 
+    /// Sniff an archive type from a content prefix's magic bytes
+    ///
+    /// A gzip/bzip2/xz/zstd magic number alone can't reveal whether the
+    /// payload is a TAR stream or a single compressed file, so this returns
+    /// the single-file variant (`Gz`, `Bz2`, `Xz`, `Zstd`, `Lz4`) in those
+    /// cases; callers who already know they're looking at a TAR stream
+    /// should use [`crate::handler::tar_handler::TarArchiveHandler::from_data_autodetect`]
+    /// instead, which disambiguates further. Returns `None` when nothing
+    /// matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nvisy_archive::ArchiveType;
+    ///
+    /// assert_eq!(ArchiveType::from_magic(b"PK\x03\x04"), Some(ArchiveType::Zip));
+    /// assert_eq!(ArchiveType::from_magic(&[0x1f, 0x8b]), Some(ArchiveType::Gz));
+    /// assert_eq!(ArchiveType::from_magic(b"not an archive"), None);
+    /// ```
+    pub fn from_magic(data: &[u8]) -> Option<Self> {
+        const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+        const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+        const BZIP2_MAGIC: &[u8] = b"BZh";
+        const XZ_MAGIC: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+        const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+        const LZ4_MAGIC: &[u8] = &[0x04, 0x22, 0x4d, 0x18];
+        const AR_MAGIC: &[u8] = b"!<arch>\n";
+        const USTAR_OFFSET: usize = 257;
+        const USTAR_MAGIC: &[u8] = b"ustar";
+
+        if data.starts_with(ZIP_MAGIC) {
+            Some(Self::Zip)
+        } else if data.starts_with(GZIP_MAGIC) {
+            Some(Self::Gz)
+        } else if data.starts_with(BZIP2_MAGIC) {
+            Some(Self::Bz2)
+        } else if data.starts_with(XZ_MAGIC) {
+            Some(Self::Xz)
+        } else if data.starts_with(ZSTD_MAGIC) {
+            Some(Self::Zstd)
+        } else if data.starts_with(LZ4_MAGIC) {
+            Some(Self::Lz4)
+        } else if data.starts_with(AR_MAGIC) {
+            Some(Self::Ar)
+        } else if data.len() >= USTAR_OFFSET + USTAR_MAGIC.len()
+            && &data[USTAR_OFFSET..USTAR_OFFSET + USTAR_MAGIC.len()] == USTAR_MAGIC
+        {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// Split this archive type into its container format and compression
+    /// filter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nvisy_archive::{ArchiveType, CompressionFilter, ContainerFormat};
+    ///
+    /// assert_eq!(
+    ///     ArchiveType::TarGz.container_and_filter(),
+    ///     (ContainerFormat::Tar, CompressionFilter::Gzip)
+    /// );
+    /// assert_eq!(
+    ///     ArchiveType::Zip.container_and_filter(),
+    ///     (ContainerFormat::Zip, CompressionFilter::None)
+    /// );
+    /// ```
+    pub fn container_and_filter(&self) -> (ContainerFormat, CompressionFilter) {
+        match self {
+            Self::Zip => (ContainerFormat::Zip, CompressionFilter::None),
+            Self::Tar => (ContainerFormat::Tar, CompressionFilter::None),
+            Self::TarGz => (ContainerFormat::Tar, CompressionFilter::Gzip),
+            Self::TarBz2 => (ContainerFormat::Tar, CompressionFilter::Bzip2),
+            Self::TarXz => (ContainerFormat::Tar, CompressionFilter::Xz),
+            Self::TarZst => (ContainerFormat::Tar, CompressionFilter::Zstd),
+            Self::TarLz4 => (ContainerFormat::Tar, CompressionFilter::Lz4),
+            Self::Gz => (ContainerFormat::Raw, CompressionFilter::Gzip),
+            Self::Bz2 => (ContainerFormat::Raw, CompressionFilter::Bzip2),
+            Self::Xz => (ContainerFormat::Raw, CompressionFilter::Xz),
+            Self::Zstd => (ContainerFormat::Raw, CompressionFilter::Zstd),
+            Self::Lz4 => (ContainerFormat::Raw, CompressionFilter::Lz4),
+            Self::Ar => (ContainerFormat::Ar, CompressionFilter::None),
+        }
+    }
+
+    /// Detect an archive's container format and compression filter from an
+    /// optional filename and a content prefix
+    ///
+    /// Magic-byte sniffing ([`Self::from_magic`]) takes priority since it
+    /// reflects the bytes actually present rather than a caller-supplied
+    /// name; the filename is only consulted as a fallback, for formats or
+    /// truncated prefixes that [`Self::from_magic`] can't resolve on its
+    /// own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nvisy_archive::{ArchiveType, CompressionFilter, ContainerFormat};
+    ///
+    /// assert_eq!(
+    ///     ArchiveType::detect(Some("archive.tar.gz"), b"not enough bytes"),
+    ///     Some((ContainerFormat::Tar, CompressionFilter::Gzip))
+    /// );
+    /// assert_eq!(
+    ///     ArchiveType::detect(None, b"PK\x03\x04"),
+    ///     Some((ContainerFormat::Zip, CompressionFilter::None))
+    /// );
+    /// assert_eq!(ArchiveType::detect(Some("notes.txt"), b"plain text"), None);
+    /// ```
+    pub fn detect(filename: Option<&str>, data: &[u8]) -> Option<(ContainerFormat, CompressionFilter)> {
+        let archive_type = Self::from_magic(data).or_else(|| {
+            let filename = filename?;
+            let extension = Self::compound_extension(filename)?;
+            Self::from_file_extension(OsStr::new(&extension))
+        })?;
+
+        Some(archive_type.container_and_filter())
+    }
+
+    /// Extract a filename's extension, preferring a known compound suffix
+    /// (`tar.gz`, `tar.bz2`, ...) over the last-component extension a
+    /// naive [`Path::extension`] call would see
+    fn compound_extension(filename: &str) -> Option<String> {
+        const COMPOUND_SUFFIXES: &[&str] =
+            &["tar.gz", "tar.bz2", "tar.xz", "tar.zst", "tar.lz4"];
+
+        let lower = filename.to_lowercase();
+        for suffix in COMPOUND_SUFFIXES {
+            let dotted = format!(".{suffix}");
+            if lower.ends_with(&dotted) && lower.len() > dotted.len() {
+                return Some((*suffix).to_string());
+            }
+        }
+
+        Path::new(filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+    }
+
     /// Check if this archive type is a compressed TAR variant
     pub fn is_tar_variant(&self) -> bool {
-        matches!(self, Self::Tar | Self::TarGz | Self::TarBz2 | Self::TarXz)
+        matches!(
+            self,
+            Self::Tar | Self::TarGz | Self::TarBz2 | Self::TarXz | Self::TarZst | Self::TarLz4
+        )
     }
 
     /// Check if this archive type supports multiple files
     pub fn supports_multiple_files(&self) -> bool {
         matches!(
             self,
-            Self::Zip | Self::Tar | Self::TarGz | Self::TarBz2 | Self::TarXz
+            Self::Zip
+                | Self::Tar
+                | Self::TarGz
+                | Self::TarBz2
+                | Self::TarXz
+                | Self::TarZst
+                | Self::TarLz4
+                | Self::Ar
         )
     }
 }
@@ -130,9 +391,14 @@ impl fmt::Display for ArchiveType {
             Self::TarGz => write!(f, "TAR.GZ"),
             Self::TarBz2 => write!(f, "TAR.BZ2"),
             Self::TarXz => write!(f, "TAR.XZ"),
+            Self::TarZst => write!(f, "TAR.ZST"),
+            Self::TarLz4 => write!(f, "TAR.LZ4"),
             Self::Gz => write!(f, "GZIP"),
             Self::Bz2 => write!(f, "BZIP2"),
             Self::Xz => write!(f, "XZ"),
+            Self::Zstd => write!(f, "ZSTD"),
+            Self::Lz4 => write!(f, "LZ4"),
+            Self::Ar => write!(f, "AR"),
         }
     }
 }
@@ -167,6 +433,26 @@ mod tests {
             ArchiveType::from_file_extension(OsStr::new("unknown")),
             None
         );
+        assert_eq!(
+            ArchiveType::from_file_extension(OsStr::new("tar.zst")),
+            Some(ArchiveType::TarZst)
+        );
+        assert_eq!(
+            ArchiveType::from_file_extension(OsStr::new("tar.lz4")),
+            Some(ArchiveType::TarLz4)
+        );
+        assert_eq!(
+            ArchiveType::from_file_extension(OsStr::new("zst")),
+            Some(ArchiveType::Zstd)
+        );
+        assert_eq!(
+            ArchiveType::from_file_extension(OsStr::new("lz4")),
+            Some(ArchiveType::Lz4)
+        );
+        assert_eq!(
+            ArchiveType::from_file_extension(OsStr::new("ar")),
+            Some(ArchiveType::Ar)
+        );
     }
 
     #[test]
@@ -186,16 +472,53 @@ mod tests {
     fn test_archive_type_variants() {
         assert!(ArchiveType::Tar.is_tar_variant());
         assert!(ArchiveType::TarGz.is_tar_variant());
+        assert!(ArchiveType::TarZst.is_tar_variant());
+        assert!(ArchiveType::TarLz4.is_tar_variant());
         assert!(!ArchiveType::Zip.is_tar_variant());
         assert!(!ArchiveType::Gz.is_tar_variant());
+        assert!(!ArchiveType::Ar.is_tar_variant());
     }
 
     #[test]
     fn test_archive_type_multiple_files() {
         assert!(ArchiveType::Zip.supports_multiple_files());
         assert!(ArchiveType::Tar.supports_multiple_files());
+        assert!(ArchiveType::Ar.supports_multiple_files());
+        assert!(ArchiveType::TarZst.supports_multiple_files());
+        assert!(ArchiveType::TarLz4.supports_multiple_files());
         assert!(!ArchiveType::Gz.supports_multiple_files());
         assert!(!ArchiveType::Bz2.supports_multiple_files());
+        assert!(!ArchiveType::Zstd.supports_multiple_files());
+        assert!(!ArchiveType::Lz4.supports_multiple_files());
+    }
+
+    #[test]
+    fn test_archive_type_from_magic() {
+        assert_eq!(ArchiveType::from_magic(b"PK\x03\x04"), Some(ArchiveType::Zip));
+        assert_eq!(
+            ArchiveType::from_magic(&[0x1f, 0x8b, 0x08, 0x00]),
+            Some(ArchiveType::Gz)
+        );
+        assert_eq!(ArchiveType::from_magic(b"BZh91AY"), Some(ArchiveType::Bz2));
+        assert_eq!(
+            ArchiveType::from_magic(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]),
+            Some(ArchiveType::Xz)
+        );
+        assert_eq!(
+            ArchiveType::from_magic(&[0x28, 0xb5, 0x2f, 0xfd]),
+            Some(ArchiveType::Zstd)
+        );
+        assert_eq!(
+            ArchiveType::from_magic(&[0x04, 0x22, 0x4d, 0x18]),
+            Some(ArchiveType::Lz4)
+        );
+        assert_eq!(ArchiveType::from_magic(b"!<arch>\n"), Some(ArchiveType::Ar));
+
+        let mut ustar_header = vec![0u8; 257];
+        ustar_header.extend_from_slice(b"ustar");
+        assert_eq!(ArchiveType::from_magic(&ustar_header), Some(ArchiveType::Tar));
+
+        assert_eq!(ArchiveType::from_magic(b"not an archive"), None);
     }
 
     #[test]
@@ -203,4 +526,63 @@ mod tests {
         assert_eq!(ArchiveType::Zip.to_string(), "ZIP");
         assert_eq!(ArchiveType::TarGz.to_string(), "TAR.GZ");
     }
+
+    #[test]
+    fn test_container_and_filter() {
+        assert_eq!(
+            ArchiveType::TarGz.container_and_filter(),
+            (ContainerFormat::Tar, CompressionFilter::Gzip)
+        );
+        assert_eq!(
+            ArchiveType::Zip.container_and_filter(),
+            (ContainerFormat::Zip, CompressionFilter::None)
+        );
+        assert_eq!(
+            ArchiveType::Zstd.container_and_filter(),
+            (ContainerFormat::Raw, CompressionFilter::Zstd)
+        );
+        assert_eq!(
+            ArchiveType::Ar.container_and_filter(),
+            (ContainerFormat::Ar, CompressionFilter::None)
+        );
+    }
+
+    #[test]
+    fn test_detect_prefers_magic_over_filename() {
+        // The filename claims `.zip`, but the magic bytes say gzip - magic wins.
+        assert_eq!(
+            ArchiveType::detect(Some("misnamed.zip"), &[0x1f, 0x8b, 0x08, 0x00]),
+            Some((ContainerFormat::Raw, CompressionFilter::Gzip))
+        );
+    }
+
+    #[test]
+    fn test_detect_falls_back_to_filename() {
+        assert_eq!(
+            ArchiveType::detect(Some("archive.tar.gz"), b"too short"),
+            Some((ContainerFormat::Tar, CompressionFilter::Gzip))
+        );
+        assert_eq!(
+            ArchiveType::detect(Some("backup.tgz"), b""),
+            Some((ContainerFormat::Tar, CompressionFilter::Gzip))
+        );
+    }
+
+    #[test]
+    fn test_detect_none_when_neither_matches() {
+        assert_eq!(ArchiveType::detect(Some("notes.txt"), b"plain text"), None);
+        assert_eq!(ArchiveType::detect(None, b"plain text"), None);
+    }
+
+    #[test]
+    fn test_container_format_display() {
+        assert_eq!(ContainerFormat::Tar.to_string(), "TAR");
+        assert_eq!(ContainerFormat::SevenZip.to_string(), "7z");
+    }
+
+    #[test]
+    fn test_compression_filter_display() {
+        assert_eq!(CompressionFilter::Gzip.to_string(), "gzip");
+        assert_eq!(CompressionFilter::None.to_string(), "no");
+    }
 }
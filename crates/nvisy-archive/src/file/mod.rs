@@ -7,15 +7,249 @@ pub mod archive_type;
 
 use std::ffi::OsStr;
 use std::io::Cursor;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-pub use archive_type::ArchiveType;
+pub use archive_type::{ArchiveType, CompressionFilter, ContainerFormat};
+use glob::Pattern;
 use tempfile::TempDir;
 use tokio::fs;
 
 use crate::handler::ArchiveHandler;
 use crate::{Error, Result};
 
+/// A single include/exclude rule evaluated against an archive entry name
+#[derive(Debug, Clone)]
+struct MatchRule {
+    pattern: Pattern,
+    include: bool,
+}
+
+/// Options controlling selective extraction, resource limits, and error
+/// tolerance
+///
+/// Entries are matched against the configured include/exclude rules in
+/// order; the last matching rule wins, and entries with no match fall
+/// back to `default_extract`. This mirrors how pxar-style extractors
+/// resolve overlapping glob rules.
+///
+/// Entry paths are always checked against Zip Slip (an absolute path or a
+/// `..` component that climbs above the destination root) regardless of
+/// these options; `max_entry_count` and `allow_unsafe_symlinks` add two
+/// more layers of defense, next to the existing `max_total_bytes`,
+/// `max_entry_bytes`, and `max_compression_ratio` decompression-bomb caps.
+pub struct ExtractOptions {
+    rules: Vec<MatchRule>,
+    default_extract: bool,
+    allow_existing_dirs: bool,
+    recurse_depth: usize,
+    max_total_bytes: Option<u64>,
+    max_entry_bytes: Option<u64>,
+    max_compression_ratio: Option<f64>,
+    max_entry_count: Option<u64>,
+    allow_unsafe_symlinks: bool,
+    total_bytes_written: u64,
+    entries_seen: u64,
+    on_error: Option<Box<dyn FnMut(Error) -> Result<()> + Send>>,
+}
+
+impl std::fmt::Debug for ExtractOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtractOptions")
+            .field("rules", &self.rules)
+            .field("recurse_depth", &self.recurse_depth)
+            .field("default_extract", &self.default_extract)
+            .field("allow_existing_dirs", &self.allow_existing_dirs)
+            .field("max_total_bytes", &self.max_total_bytes)
+            .field("max_entry_bytes", &self.max_entry_bytes)
+            .field("max_compression_ratio", &self.max_compression_ratio)
+            .field("max_entry_count", &self.max_entry_count)
+            .field("allow_unsafe_symlinks", &self.allow_unsafe_symlinks)
+            .field("on_error", &self.on_error.is_some())
+            .finish()
+    }
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            default_extract: true,
+            allow_existing_dirs: true,
+            recurse_depth: 0,
+            max_total_bytes: None,
+            max_entry_bytes: None,
+            max_compression_ratio: None,
+            max_entry_count: None,
+            allow_unsafe_symlinks: false,
+            total_bytes_written: 0,
+            entries_seen: 0,
+            on_error: None,
+        }
+    }
+}
+
+impl ExtractOptions {
+    /// Create a new set of options that extracts every entry by default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an include glob, evaluated in the order rules are added
+    pub fn with_include(mut self, pattern: impl AsRef<str>) -> Result<Self> {
+        let pattern = Pattern::new(pattern.as_ref())
+            .map_err(|e| Error::invalid_archive(format!("Invalid include pattern: {}", e)))?;
+        self.rules.push(MatchRule {
+            pattern,
+            include: true,
+        });
+        Ok(self)
+    }
+
+    /// Add an exclude glob, evaluated in the order rules are added
+    pub fn with_exclude(mut self, pattern: impl AsRef<str>) -> Result<Self> {
+        let pattern = Pattern::new(pattern.as_ref())
+            .map_err(|e| Error::invalid_archive(format!("Invalid exclude pattern: {}", e)))?;
+        self.rules.push(MatchRule {
+            pattern,
+            include: false,
+        });
+        Ok(self)
+    }
+
+    /// Set whether entries with no matching rule are extracted (default `true`)
+    pub fn with_default_extract(mut self, default_extract: bool) -> Self {
+        self.default_extract = default_extract;
+        self
+    }
+
+    /// Set whether pre-existing output directories are tolerated (default `true`)
+    pub fn with_allow_existing_dirs(mut self, allow_existing_dirs: bool) -> Self {
+        self.allow_existing_dirs = allow_existing_dirs;
+        self
+    }
+
+    /// Set how many levels of nested archives are unpacked in place
+    ///
+    /// A depth of `0` (the default) disables recursion: a `.zip` found
+    /// inside a `.tar.gz` is left as-is. Each level extracts a nested
+    /// archive into a sibling `<name>.extracted/` directory.
+    pub fn with_recurse_depth(mut self, recurse_depth: usize) -> Self {
+        self.recurse_depth = recurse_depth;
+        self
+    }
+
+    /// Cap the aggregate number of bytes written across all extracted entries
+    ///
+    /// Checked incrementally as each entry is decompressed, so an archive
+    /// that would blow past the cap is stopped mid-write rather than after
+    /// being fully expanded.
+    pub fn with_max_total_bytes(mut self, max_total_bytes: u64) -> Self {
+        self.max_total_bytes = Some(max_total_bytes);
+        self
+    }
+
+    /// Cap the number of bytes a single entry may expand to once decompressed
+    pub fn with_max_entry_bytes(mut self, max_entry_bytes: u64) -> Self {
+        self.max_entry_bytes = Some(max_entry_bytes);
+        self
+    }
+
+    /// Cap the ratio of decompressed to compressed bytes for a single entry
+    ///
+    /// Guards against decompression bombs: a small compressed entry that
+    /// expands far beyond this ratio aborts extraction with
+    /// [`Error::SizeLimitExceeded`] before the full entry is buffered.
+    pub fn with_max_compression_ratio(mut self, max_compression_ratio: f64) -> Self {
+        self.max_compression_ratio = Some(max_compression_ratio);
+        self
+    }
+
+    /// Cap the number of entries an archive may contain
+    ///
+    /// Checked as each entry header is read, before any of its content is
+    /// decompressed, so an archive packing millions of tiny entries is
+    /// rejected before it can exhaust inodes or memory.
+    pub fn with_max_entry_count(mut self, max_entry_count: u64) -> Self {
+        self.max_entry_count = Some(max_entry_count);
+        self
+    }
+
+    /// Allow symlink and hardlink entries whose target resolves outside the
+    /// extraction directory (default `false`)
+    ///
+    /// Disabled by default: a link target that climbs above the destination
+    /// root is a classic archive-extraction escape, so entries like this are
+    /// rejected with [`Error::InvalidArchive`] unless explicitly allowed.
+    pub fn with_allow_unsafe_symlinks(mut self, allow_unsafe_symlinks: bool) -> Self {
+        self.allow_unsafe_symlinks = allow_unsafe_symlinks;
+        self
+    }
+
+    /// Install a handler invoked with each per-entry error
+    ///
+    /// Return `Ok(())` from the handler to skip the offending entry and
+    /// keep extracting, or return `Err` to abort the whole archive.
+    pub fn with_on_error<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(Error) -> Result<()> + Send + 'static,
+    {
+        self.on_error = Some(Box::new(handler));
+        self
+    }
+
+    /// Decide whether an entry should be extracted based on the configured rules
+    fn should_extract(&self, entry_name: &str) -> bool {
+        let mut extract = self.default_extract;
+        for rule in &self.rules {
+            if rule.pattern.matches(entry_name) {
+                extract = rule.include;
+            }
+        }
+        extract
+    }
+
+    /// Route a per-entry error through the configured handler, if any
+    fn handle_error(&mut self, error: Error) -> Result<()> {
+        match &mut self.on_error {
+            Some(handler) => handler(error),
+            None => Err(error),
+        }
+    }
+
+    /// Record newly-written bytes against the aggregate budget
+    fn track_total_bytes(&mut self, additional: u64) -> Result<()> {
+        self.total_bytes_written += additional;
+        if let Some(max_total_bytes) = self.max_total_bytes {
+            if self.total_bytes_written > max_total_bytes {
+                return Err(Error::size_limit_exceeded(format!(
+                    "Aggregate extracted size exceeded max_total_bytes ({} bytes)",
+                    max_total_bytes
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a newly-seen archive entry against `max_entry_count`
+    ///
+    /// Called once per entry header, regardless of whether the entry is
+    /// ultimately extracted, so `max_entry_count` bounds the archive's
+    /// total entry count rather than just the entries that pass the
+    /// include/exclude rules.
+    fn track_entry_count(&mut self) -> Result<()> {
+        self.entries_seen += 1;
+        if let Some(max_entry_count) = self.max_entry_count {
+            if self.entries_seen > max_entry_count {
+                return Err(Error::resource_limit(format!(
+                    "Archive entry count exceeded max_entry_count ({} entries)",
+                    max_entry_count
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Represents an archive file that can be loaded from various sources
 ///
 /// This struct encapsulates an archive and provides methods for
@@ -37,6 +271,47 @@ enum ArchiveSource {
     Memory(Vec<u8>),
     /// Archive loaded from an iterator
     Iterator(Vec<u8>),
+    /// Archive loaded from a remote URL, downloaded on demand
+    Url(String),
+}
+
+/// A single entry to write when packing an archive with [`ArchiveFile::pack`]
+#[derive(Debug, Clone)]
+pub enum PackEntry {
+    /// Copy a file from disk under `archive_path`; TAR keeps its permissions
+    /// and modification time, ZIP keeps its Unix permissions but not its
+    /// modification time
+    File {
+        /// Path the entry will have inside the archive
+        archive_path: String,
+        /// Path of the source file on disk
+        source_path: PathBuf,
+    },
+    /// Embed raw bytes under `archive_path`
+    Memory {
+        /// Path the entry will have inside the archive
+        archive_path: String,
+        /// Entry contents
+        data: Vec<u8>,
+    },
+}
+
+impl PackEntry {
+    /// Create an entry that copies a file from disk
+    pub fn file(archive_path: impl Into<String>, source_path: impl Into<PathBuf>) -> Self {
+        Self::File {
+            archive_path: archive_path.into(),
+            source_path: source_path.into(),
+        }
+    }
+
+    /// Create an entry from in-memory bytes
+    pub fn memory(archive_path: impl Into<String>, data: Vec<u8>) -> Self {
+        Self::Memory {
+            archive_path: archive_path.into(),
+            data,
+        }
+    }
 }
 
 impl ArchiveFile {
@@ -58,26 +333,8 @@ impl ArchiveFile {
         let extension = path
             .extension()
             .ok_or_else(|| Error::invalid_archive("No file extension found"))?;
-
-        // Handle compound extensions like .tar.gz
-        let full_name = path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("");
-
-        let archive_type = if full_name.contains(".tar.") {
-            // Try to match compound extensions first
-            if let Some(pos) = full_name.find(".tar.") {
-                let compound_ext = &full_name[pos + 1..]; // Skip the dot
-                ArchiveType::from_file_extension(OsStr::new(compound_ext))
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-        .or_else(|| ArchiveType::from_file_extension(extension))
-        .ok_or_else(|| Error::unsupported_format(extension.to_string_lossy().to_string()))?;
+        let archive_type = detect_archive_type(path)
+            .ok_or_else(|| Error::unsupported_format(extension.to_string_lossy().to_string()))?;
 
         Ok(Self {
             archive_type,
@@ -122,6 +379,81 @@ impl ArchiveFile {
         }
     }
 
+    /// Create a new archive file backed by a remote URL
+    ///
+    /// The archive type is detected from the URL's path extension, the
+    /// same way [`ArchiveFile::from_path`] reads it from a filesystem
+    /// path. Nothing is downloaded until [`ArchiveFile::unpack`] or
+    /// [`ArchiveFile::size`] is called. If the URL has no recognizable
+    /// extension (e.g. it ends in an opaque redirect slug), use
+    /// [`ArchiveFile::from_url_detect`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nvisy_archive::ArchiveFile;
+    ///
+    /// let archive = ArchiveFile::from_url("https://example.com/archive.tar.gz")?;
+    /// # Ok::<(), nvisy_archive::Error>(())
+    /// ```
+    pub fn from_url(url: impl Into<String>) -> Result<Self> {
+        let url = url.into();
+        let url_path = url.split(['?', '#']).next().unwrap_or(&url);
+        let archive_type = detect_archive_type(Path::new(url_path))
+            .ok_or_else(|| Error::unsupported_format("No file extension found in URL"))?;
+
+        Ok(Self {
+            archive_type,
+            source: ArchiveSource::Url(url),
+        })
+    }
+
+    /// Create a new archive file backed by a remote URL, sniffing the
+    /// archive type when the URL's path has no recognizable extension
+    ///
+    /// Downloads the response body (aborting once it exceeds `max_bytes`,
+    /// reusing the same incremental size enforcement as extraction's
+    /// decompression-bomb guard) and detects the type from the
+    /// `Content-Type` header, falling back to a magic-byte sniff of the
+    /// downloaded bytes. The archive is constructed from the downloaded
+    /// bytes directly, so extraction does not re-fetch the URL.
+    ///
+    /// Requires the `http` feature.
+    #[cfg(feature = "http")]
+    pub async fn from_url_detect(url: impl AsRef<str>, max_bytes: u64) -> Result<Self> {
+        let url = url.as_ref();
+        let response = reqwest::get(url)
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| Error::other(format!("Failed to fetch {}: {}", url, e)))?;
+
+        let content_type_archive_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(archive_type_from_content_type);
+
+        let data = read_body_with_limit(response, max_bytes, url).await?;
+
+        let url_path = url.split(['?', '#']).next().unwrap_or(url);
+        let archive_type = detect_archive_type(Path::new(url_path))
+            .or(content_type_archive_type)
+            .or_else(|| sniff_archive_type(&data))
+            .ok_or_else(|| {
+                Error::unsupported_format("Could not detect archive type from URL or content")
+            })?;
+
+        Ok(Self {
+            archive_type,
+            source: ArchiveSource::Memory(data),
+        })
+    }
+
+    #[cfg(not(feature = "http"))]
+    pub async fn from_url_detect(_url: impl AsRef<str>, _max_bytes: u64) -> Result<Self> {
+        Err(Error::unsupported_format("HTTP support not enabled"))
+    }
+
     /// Create an archive with explicit type (useful for ambiguous extensions)
     pub fn with_archive_type(mut self, archive_type: ArchiveType) -> Self {
         self.archive_type = archive_type;
@@ -137,7 +469,7 @@ impl ArchiveFile {
     pub async fn exists(&self) -> bool {
         match &self.source {
             ArchiveSource::Path(path) => fs::try_exists(path).await.unwrap_or(false),
-            ArchiveSource::Memory(_) | ArchiveSource::Iterator(_) => true,
+            ArchiveSource::Memory(_) | ArchiveSource::Iterator(_) | ArchiveSource::Url(_) => true,
         }
     }
 
@@ -157,6 +489,7 @@ impl ArchiveFile {
                 Ok(metadata.len())
             }
             ArchiveSource::Memory(data) | ArchiveSource::Iterator(data) => Ok(data.len() as u64),
+            ArchiveSource::Url(url) => remote_content_length(url).await,
         }
     }
 
@@ -191,16 +524,72 @@ impl ArchiveFile {
     /// # }
     /// ```
     pub async fn unpack(self) -> Result<ArchiveHandler> {
+        self.unpack_with(ExtractOptions::default()).await
+    }
+
+    /// Extract the archive to a temporary directory with selective extraction
+    ///
+    /// Unlike [`ArchiveFile::unpack`], this accepts [`ExtractOptions`] so
+    /// callers can restrict extraction to entries matching include/exclude
+    /// globs and tolerate per-entry failures via `on_error` instead of
+    /// aborting the whole archive.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nvisy_archive::ArchiveFile;
+    /// use nvisy_archive::file::ExtractOptions;
+    ///
+    /// # async fn example() -> nvisy_archive::Result<()> {
+    /// let archive = ArchiveFile::from_path("archive.zip")?;
+    /// let options = ExtractOptions::new().with_include("*.json")?;
+    /// let handler = archive.unpack_with(options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn unpack_with(self, mut options: ExtractOptions) -> Result<ArchiveHandler> {
         // Create temporary directory
         let temp_dir = TempDir::new()
             .map_err(|e| Error::other(format!("Failed to create temporary directory: {}", e)))?;
+        let recurse_depth = options.recurse_depth;
+
+        // Plain, gzip-, bzip2- and xz-compressed TAR archives loaded from a
+        // file are extracted by streaming directly off disk, so the archive
+        // and its decompressed contents are never fully materialized in
+        // memory. Every other type loaded from a file is read into memory first.
+        let files = match (&self.source, self.archive_type) {
+            (
+                ArchiveSource::Path(path),
+                ArchiveType::Tar | ArchiveType::TarGz | ArchiveType::TarBz2 | ArchiveType::TarXz,
+            ) => {
+                extract_tar_streaming(
+                    path.clone(),
+                    self.archive_type,
+                    temp_dir.path().to_path_buf(),
+                    options,
+                )
+                .await?
+            }
+            (ArchiveSource::Url(url), _) => {
+                let max_bytes = options.max_total_bytes.unwrap_or(DEFAULT_MAX_DOWNLOAD_BYTES);
+                let data = download_with_limit(url, max_bytes).await?;
+                let cursor = Cursor::new(data);
+                self.extract_archive(cursor, temp_dir.path(), &mut options)
+                    .await?
+            }
+            _ => {
+                let data = self.get_data().await?;
+                let cursor = Cursor::new(data);
+                self.extract_archive(cursor, temp_dir.path(), &mut options)
+                    .await?
+            }
+        };
 
-        // Get archive data as bytes
-        let data = self.get_data().await?;
-        let cursor = Cursor::new(data);
-
-        // Extract based on archive type
-        let files = self.extract_archive(cursor, temp_dir.path()).await?;
+        let files = if recurse_depth > 0 {
+            extract_nested_archives(files, recurse_depth).await?
+        } else {
+            files
+        };
 
         Ok(ArchiveHandler::new(
             self.archive_type,
@@ -215,30 +604,74 @@ impl ArchiveFile {
         match &self.source {
             ArchiveSource::Path(path) => fs::read(path).await.map_err(Into::into),
             ArchiveSource::Memory(data) | ArchiveSource::Iterator(data) => Ok(data.clone()),
+            ArchiveSource::Url(url) => download_with_limit(url, DEFAULT_MAX_DOWNLOAD_BYTES).await,
         }
     }
 
+    /// Pack a set of entries into a new archive, returned as an in-memory buffer
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nvisy_archive::ArchiveType;
+    /// use nvisy_archive::file::{ArchiveFile, PackEntry};
+    ///
+    /// # async fn example() -> nvisy_archive::Result<()> {
+    /// let entries = vec![PackEntry::memory("hello.txt", b"Hello, World!".to_vec())];
+    /// let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pack(archive_type: ArchiveType, entries: Vec<PackEntry>) -> Result<Vec<u8>> {
+        let buffer = Self::pack_to_writer(archive_type, entries, Cursor::new(Vec::new())).await?;
+        Ok(buffer.into_inner())
+    }
+
+    /// Pack a set of entries into a new archive, writing to the given writer
+    ///
+    /// The writer must support seeking because the ZIP format writes its
+    /// central directory after all entries have been streamed.
+    pub async fn pack_to_writer<W>(archive_type: ArchiveType, entries: Vec<PackEntry>, writer: W) -> Result<W>
+    where
+        W: std::io::Write + std::io::Seek + Send + 'static,
+    {
+        tokio::task::spawn_blocking(move || pack_sync(archive_type, entries, writer))
+            .await
+            .map_err(|e| Error::other(format!("Packing task panicked: {}", e)))?
+    }
+
     /// Extract archive contents to the specified directory
     async fn extract_archive(
         &self,
         data: Cursor<Vec<u8>>,
         target_dir: &Path,
+        options: &mut ExtractOptions,
     ) -> Result<Vec<PathBuf>> {
         match self.archive_type {
-            ArchiveType::Zip => self.extract_zip(data, target_dir).await,
-            ArchiveType::Tar => self.extract_tar(data, target_dir).await,
-            ArchiveType::TarGz => self.extract_tar_gz(data, target_dir).await,
-            ArchiveType::TarBz2 => self.extract_tar_bz2(data, target_dir).await,
-            ArchiveType::TarXz => self.extract_tar_xz(data, target_dir).await,
-            ArchiveType::Gz => self.extract_gz(data, target_dir).await,
-            ArchiveType::Bz2 => self.extract_bz2(data, target_dir).await,
-            ArchiveType::Xz => self.extract_xz(data, target_dir).await,
+            ArchiveType::Zip => self.extract_zip(data, target_dir, options).await,
+            ArchiveType::Tar => self.extract_tar(data, target_dir, options).await,
+            ArchiveType::TarGz => self.extract_tar_gz(data, target_dir, options).await,
+            ArchiveType::TarBz2 => self.extract_tar_bz2(data, target_dir, options).await,
+            ArchiveType::TarXz => self.extract_tar_xz(data, target_dir, options).await,
+            ArchiveType::TarZst => self.extract_tar_zst(data, target_dir, options).await,
+            ArchiveType::TarLz4 => self.extract_tar_lz4(data, target_dir, options).await,
+            ArchiveType::Gz => self.extract_gz(data, target_dir, options).await,
+            ArchiveType::Bz2 => self.extract_bz2(data, target_dir, options).await,
+            ArchiveType::Xz => self.extract_xz(data, target_dir, options).await,
+            ArchiveType::Zstd => self.extract_zstd(data, target_dir, options).await,
+            ArchiveType::Lz4 => self.extract_lz4(data, target_dir, options).await,
+            ArchiveType::Ar => self.extract_ar(data, target_dir, options).await,
         }
     }
 
     /// Extract ZIP archive
     #[cfg(feature = "zip")]
-    async fn extract_zip(&self, data: Cursor<Vec<u8>>, target_dir: &Path) -> Result<Vec<PathBuf>> {
+    async fn extract_zip(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
         use tokio::io::AsyncWriteExt;
         use zip::ZipArchive;
 
@@ -247,23 +680,69 @@ impl ArchiveFile {
 
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)?;
-            let file_path = target_dir.join(file.name());
+            let name = file.name().to_string();
 
-            // Create parent directories if they don't exist
-            if let Some(parent) = file_path.parent() {
-                fs::create_dir_all(parent).await?;
+            options.track_entry_count()?;
+            if !options.should_extract(&name) {
+                continue;
             }
+            let allow_existing_dirs = options.allow_existing_dirs;
+            let allow_unsafe_symlinks = options.allow_unsafe_symlinks;
+
+            #[cfg(unix)]
+            let is_symlink = file
+                .unix_mode()
+                .map(|mode| mode & 0o170000 == 0o120000)
+                .unwrap_or(false);
+            #[cfg(not(unix))]
+            let is_symlink = false;
+
+            let result: Result<Option<PathBuf>> = async {
+                let file_path = sanitize_entry_path(target_dir, &name)?;
+
+                // Create parent directories if they don't exist
+                if let Some(parent) = file_path.parent() {
+                    fs::create_dir_all(parent).await?;
+                }
+
+                if file.is_dir() {
+                    if !allow_existing_dirs && fs::try_exists(&file_path).await.unwrap_or(false) {
+                        return Err(Error::invalid_archive(format!(
+                            "Directory already exists: {}",
+                            file_path.display()
+                        )));
+                    }
+                    fs::create_dir_all(&file_path).await?;
+                    Ok(None)
+                } else if is_symlink {
+                    let compressed_size = file.compressed_size();
+                    let content =
+                        read_entry_with_limits(&mut file, compressed_size, options, "read file from ZIP")?;
+                    let link_target = PathBuf::from(String::from_utf8_lossy(&content).into_owned());
+                    validate_symlink_target(target_dir, &file_path, &link_target, allow_unsafe_symlinks)?;
+
+                    #[cfg(unix)]
+                    {
+                        tokio::fs::symlink(&link_target, &file_path).await?;
+                    }
+                    Ok(Some(file_path))
+                } else {
+                    let compressed_size = file.compressed_size();
+                    let content =
+                        read_entry_with_limits(&mut file, compressed_size, options, "read file from ZIP")?;
+                    options.track_total_bytes(content.len() as u64)?;
+
+                    let mut output_file = fs::File::create(&file_path).await?;
+                    output_file.write_all(&content).await?;
+                    Ok(Some(file_path))
+                }
+            }
+            .await;
 
-            if file.is_dir() {
-                fs::create_dir_all(&file_path).await?;
-            } else {
-                let mut content = Vec::new();
-                std::io::Read::read_to_end(&mut file, &mut content)
-                    .map_err(|e| Error::other(format!("Failed to read file from ZIP: {}", e)))?;
-
-                let mut output_file = fs::File::create(&file_path).await?;
-                output_file.write_all(&content).await?;
-                files.push(file_path);
+            match result {
+                Ok(Some(file_path)) => files.push(file_path),
+                Ok(None) => {}
+                Err(error) => options.handle_error(error)?,
             }
         }
 
@@ -275,13 +754,19 @@ impl ArchiveFile {
         &self,
         _data: Cursor<Vec<u8>>,
         _target_dir: &Path,
+        _options: &mut ExtractOptions,
     ) -> Result<Vec<PathBuf>> {
         Err(Error::unsupported_format("ZIP support not enabled"))
     }
 
     /// Extract TAR archive
     #[cfg(feature = "tar")]
-    async fn extract_tar(&self, data: Cursor<Vec<u8>>, target_dir: &Path) -> Result<Vec<PathBuf>> {
+    async fn extract_tar(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
         use tar::Archive;
         use tokio::io::AsyncWriteExt;
 
@@ -290,24 +775,72 @@ impl ArchiveFile {
 
         for entry in archive.entries()? {
             let mut entry = entry?;
-            let path = entry.path()?;
-            let file_path = target_dir.join(&path);
+            let path = entry.path()?.to_string_lossy().into_owned();
 
-            // Create parent directories if they don't exist
-            if let Some(parent) = file_path.parent() {
-                fs::create_dir_all(parent).await?;
+            options.track_entry_count()?;
+            if !options.should_extract(&path) {
+                continue;
             }
 
-            if entry.header().entry_type().is_dir() {
-                fs::create_dir_all(&file_path).await?;
-            } else {
-                let mut content = Vec::new();
-                std::io::Read::read_to_end(&mut entry, &mut content)
-                    .map_err(|e| Error::other(format!("Failed to read file from TAR: {}", e)))?;
+            let entry_type = entry.header().entry_type();
+            let is_dir = entry_type.is_dir();
+            let is_link = entry_type == tar::EntryType::Symlink || entry_type == tar::EntryType::Link;
+            let allow_existing_dirs = options.allow_existing_dirs;
+            let allow_unsafe_symlinks = options.allow_unsafe_symlinks;
+            let link_name = if is_link { entry.link_name()?.map(|l| l.into_owned()) } else { None };
+            let result: Result<Option<PathBuf>> = async {
+                let file_path = sanitize_entry_path(target_dir, &path)?;
+
+                // Create parent directories if they don't exist
+                if let Some(parent) = file_path.parent() {
+                    fs::create_dir_all(parent).await?;
+                }
+
+                if is_dir {
+                    if !allow_existing_dirs && fs::try_exists(&file_path).await.unwrap_or(false) {
+                        return Err(Error::invalid_archive(format!(
+                            "Directory already exists: {}",
+                            file_path.display()
+                        )));
+                    }
+                    fs::create_dir_all(&file_path).await?;
+                    Ok(None)
+                } else if is_link {
+                    let Some(link_target) = link_name else {
+                        return Ok(None);
+                    };
+                    validate_symlink_target(
+                        target_dir,
+                        &file_path,
+                        &link_target,
+                        allow_unsafe_symlinks,
+                    )?;
+
+                    #[cfg(unix)]
+                    let created = {
+                        tokio::fs::symlink(&link_target, &file_path).await?;
+                        true
+                    };
+                    #[cfg(not(unix))]
+                    let created = false;
+
+                    Ok(created.then_some(file_path))
+                } else {
+                    let content =
+                        read_entry_with_limits(&mut entry, 0, options, "read file from TAR")?;
+                    options.track_total_bytes(content.len() as u64)?;
+
+                    let mut output_file = fs::File::create(&file_path).await?;
+                    output_file.write_all(&content).await?;
+                    Ok(Some(file_path))
+                }
+            }
+            .await;
 
-                let mut output_file = fs::File::create(&file_path).await?;
-                output_file.write_all(&content).await?;
-                files.push(file_path);
+            match result {
+                Ok(Some(file_path)) => files.push(file_path),
+                Ok(None) => {}
+                Err(error) => options.handle_error(error)?,
             }
         }
 
@@ -319,6 +852,7 @@ impl ArchiveFile {
         &self,
         _data: Cursor<Vec<u8>>,
         _target_dir: &Path,
+        _options: &mut ExtractOptions,
     ) -> Result<Vec<PathBuf>> {
         Err(Error::unsupported_format("TAR support not enabled"))
     }
@@ -328,16 +862,15 @@ impl ArchiveFile {
         &self,
         data: Cursor<Vec<u8>>,
         target_dir: &Path,
+        options: &mut ExtractOptions,
     ) -> Result<Vec<PathBuf>> {
         use flate2::read::GzDecoder;
-        let decoder = GzDecoder::new(data);
-        let cursor = Cursor::new({
-            let mut buf = Vec::new();
-            std::io::Read::read_to_end(&mut { decoder }, &mut buf)
-                .map_err(|e| Error::other(format!("Failed to decompress GZIP: {}", e)))?;
-            buf
-        });
-        self.extract_tar(cursor, target_dir).await
+        let compressed_size = data.get_ref().len() as u64;
+        let mut decoder = GzDecoder::new(data);
+        let decompressed =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress GZIP")?;
+        self.extract_tar(Cursor::new(decompressed), target_dir, options)
+            .await
     }
 
     /// Extract BZIP2-compressed TAR archive
@@ -345,16 +878,15 @@ impl ArchiveFile {
         &self,
         data: Cursor<Vec<u8>>,
         target_dir: &Path,
+        options: &mut ExtractOptions,
     ) -> Result<Vec<PathBuf>> {
         use bzip2::read::BzDecoder;
-        let decoder = BzDecoder::new(data);
-        let cursor = Cursor::new({
-            let mut buf = Vec::new();
-            std::io::Read::read_to_end(&mut { decoder }, &mut buf)
-                .map_err(|e| Error::other(format!("Failed to decompress BZIP2: {}", e)))?;
-            buf
-        });
-        self.extract_tar(cursor, target_dir).await
+        let compressed_size = data.get_ref().len() as u64;
+        let mut decoder = BzDecoder::new(data);
+        let decompressed =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress BZIP2")?;
+        self.extract_tar(Cursor::new(decompressed), target_dir, options)
+            .await
     }
 
     /// Extract XZ-compressed TAR archive
@@ -362,25 +894,90 @@ impl ArchiveFile {
         &self,
         data: Cursor<Vec<u8>>,
         target_dir: &Path,
+        options: &mut ExtractOptions,
     ) -> Result<Vec<PathBuf>> {
         use xz2::read::XzDecoder;
+        let compressed_size = data.get_ref().len() as u64;
         let mut decoder = XzDecoder::new(data);
-        let mut decompressed_data = Vec::new();
-        std::io::Read::read_to_end(&mut decoder, &mut decompressed_data)
-            .map_err(|e| Error::other(format!("Failed to decompress XZ: {}", e)))?;
-        let cursor = Cursor::new(decompressed_data);
-        self.extract_tar(cursor, target_dir).await
+        let decompressed =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress XZ")?;
+        self.extract_tar(Cursor::new(decompressed), target_dir, options)
+            .await
+    }
+
+    /// Extract Zstandard-compressed TAR archive
+    #[cfg(feature = "zstd")]
+    async fn extract_tar_zst(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        let compressed_size = data.get_ref().len() as u64;
+        let mut decoder = zstd::stream::read::Decoder::new(data)
+            .map_err(|e| Error::other(format!("Failed to initialize Zstandard decoder: {}", e)))?;
+        let decompressed = read_entry_with_limits(
+            &mut decoder,
+            compressed_size,
+            options,
+            "decompress Zstandard",
+        )?;
+        self.extract_tar(Cursor::new(decompressed), target_dir, options)
+            .await
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    async fn extract_tar_zst(
+        &self,
+        _data: Cursor<Vec<u8>>,
+        _target_dir: &Path,
+        _options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        Err(Error::unsupported_format("Zstandard support not enabled"))
+    }
+
+    /// Extract LZ4-compressed TAR archive
+    #[cfg(feature = "lz4")]
+    async fn extract_tar_lz4(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        let compressed_size = data.get_ref().len() as u64;
+        let mut decoder = lz4::Decoder::new(data)
+            .map_err(|e| Error::other(format!("Failed to initialize LZ4 decoder: {}", e)))?;
+        let decompressed =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress LZ4")?;
+        self.extract_tar(Cursor::new(decompressed), target_dir, options)
+            .await
+    }
+
+    #[cfg(not(feature = "lz4"))]
+    async fn extract_tar_lz4(
+        &self,
+        _data: Cursor<Vec<u8>>,
+        _target_dir: &Path,
+        _options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        Err(Error::unsupported_format("LZ4 support not enabled"))
     }
 
     /// Extract single GZIP file
-    async fn extract_gz(&self, data: Cursor<Vec<u8>>, target_dir: &Path) -> Result<Vec<PathBuf>> {
+    async fn extract_gz(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
         use flate2::read::GzDecoder;
         use tokio::io::AsyncWriteExt;
 
+        let compressed_size = data.get_ref().len() as u64;
         let mut decoder = GzDecoder::new(data);
-        let mut content = Vec::new();
-        std::io::Read::read_to_end(&mut decoder, &mut content)
-            .map_err(|e| Error::other(format!("Failed to decompress GZIP: {}", e)))?;
+        let content =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress GZIP")?;
+        options.track_total_bytes(content.len() as u64)?;
 
         // For single files, we need to determine the output filename
         let output_path = if let Some(path) = self.path() {
@@ -400,14 +997,20 @@ impl ArchiveFile {
     }
 
     /// Extract single BZIP2 file
-    async fn extract_bz2(&self, data: Cursor<Vec<u8>>, target_dir: &Path) -> Result<Vec<PathBuf>> {
+    async fn extract_bz2(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
         use bzip2::read::BzDecoder;
         use tokio::io::AsyncWriteExt;
 
+        let compressed_size = data.get_ref().len() as u64;
         let mut decoder = BzDecoder::new(data);
-        let mut content = Vec::new();
-        std::io::Read::read_to_end(&mut decoder, &mut content)
-            .map_err(|e| Error::other(format!("Failed to decompress BZIP2: {}", e)))?;
+        let content =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress BZIP2")?;
+        options.track_total_bytes(content.len() as u64)?;
 
         let output_path = if let Some(path) = self.path() {
             let stem = path
@@ -426,14 +1029,20 @@ impl ArchiveFile {
     }
 
     /// Extract single XZ file
-    async fn extract_xz(&self, data: Cursor<Vec<u8>>, target_dir: &Path) -> Result<Vec<PathBuf>> {
+    async fn extract_xz(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
         use tokio::io::AsyncWriteExt;
         use xz2::read::XzDecoder;
 
+        let compressed_size = data.get_ref().len() as u64;
         let mut decoder = XzDecoder::new(data);
-        let mut content = Vec::new();
-        std::io::Read::read_to_end(&mut decoder, &mut content)
-            .map_err(|e| Error::other(format!("Failed to decompress XZ: {}", e)))?;
+        let content =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress XZ")?;
+        options.track_total_bytes(content.len() as u64)?;
 
         let output_path = if let Some(path) = self.path() {
             let stem = path
@@ -450,40 +1059,818 @@ impl ArchiveFile {
 
         Ok(vec![output_path])
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Extract single Zstandard-compressed file
+    #[cfg(feature = "zstd")]
+    async fn extract_zstd(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        use tokio::io::AsyncWriteExt;
 
-    #[test]
-    fn test_archive_file_from_memory() {
-        let data = vec![0x50, 0x4B, 0x03, 0x04]; // ZIP signature
-        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
-        assert_eq!(archive.archive_type(), ArchiveType::Zip);
-        assert!(archive.path().is_none());
-    }
+        let compressed_size = data.get_ref().len() as u64;
+        let mut decoder = zstd::stream::read::Decoder::new(data)
+            .map_err(|e| Error::other(format!("Failed to initialize Zstandard decoder: {}", e)))?;
+        let content = read_entry_with_limits(
+            &mut decoder,
+            compressed_size,
+            options,
+            "decompress Zstandard",
+        )?;
+        options.track_total_bytes(content.len() as u64)?;
 
-    #[test]
-    fn test_archive_file_from_iterator() {
-        let data = [0x50, 0x4B, 0x03, 0x04]; // ZIP signature
-        let archive = ArchiveFile::from_iterator(ArchiveType::Zip, data.into_iter());
-        assert_eq!(archive.archive_type(), ArchiveType::Zip);
-    }
+        let output_path = if let Some(path) = self.path() {
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("extracted");
+            target_dir.join(stem)
+        } else {
+            target_dir.join("extracted")
+        };
 
-    #[test]
-    fn test_archive_file_from_path() -> Result<()> {
-        let archive = ArchiveFile::from_path("test.zip")?;
-        assert_eq!(archive.archive_type(), ArchiveType::Zip);
-        assert!(archive.path().is_some());
-        Ok(())
-    }
+        let mut output_file = fs::File::create(&output_path).await?;
+        output_file.write_all(&content).await?;
 
-    #[test]
-    fn test_compound_extension() -> Result<()> {
-        let archive = ArchiveFile::from_path("test.tar.gz")?;
-        assert_eq!(archive.archive_type(), ArchiveType::TarGz);
-        Ok(())
+        Ok(vec![output_path])
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    async fn extract_zstd(
+        &self,
+        _data: Cursor<Vec<u8>>,
+        _target_dir: &Path,
+        _options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        Err(Error::unsupported_format("Zstandard support not enabled"))
+    }
+
+    /// Extract single LZ4-compressed file
+    #[cfg(feature = "lz4")]
+    async fn extract_lz4(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        use tokio::io::AsyncWriteExt;
+
+        let compressed_size = data.get_ref().len() as u64;
+        let mut decoder = lz4::Decoder::new(data)
+            .map_err(|e| Error::other(format!("Failed to initialize LZ4 decoder: {}", e)))?;
+        let content =
+            read_entry_with_limits(&mut decoder, compressed_size, options, "decompress LZ4")?;
+        options.track_total_bytes(content.len() as u64)?;
+
+        let output_path = if let Some(path) = self.path() {
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("extracted");
+            target_dir.join(stem)
+        } else {
+            target_dir.join("extracted")
+        };
+
+        let mut output_file = fs::File::create(&output_path).await?;
+        output_file.write_all(&content).await?;
+
+        Ok(vec![output_path])
+    }
+
+    #[cfg(not(feature = "lz4"))]
+    async fn extract_lz4(
+        &self,
+        _data: Cursor<Vec<u8>>,
+        _target_dir: &Path,
+        _options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        Err(Error::unsupported_format("LZ4 support not enabled"))
+    }
+
+    /// Extract Unix `ar` archive (static libraries, Debian packages)
+    #[cfg(feature = "ar")]
+    async fn extract_ar(
+        &self,
+        data: Cursor<Vec<u8>>,
+        target_dir: &Path,
+        options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut archive = ar::Archive::new(data);
+        let mut files = Vec::new();
+
+        while let Some(entry) = archive.next_entry() {
+            let mut entry =
+                entry.map_err(|e| Error::other(format!("Failed to read AR entry: {}", e)))?;
+            let name = String::from_utf8_lossy(entry.header().identifier()).into_owned();
+
+            options.track_entry_count()?;
+            if !options.should_extract(&name) {
+                continue;
+            }
+
+            let result: Result<PathBuf> = async {
+                let file_path = sanitize_entry_path(target_dir, &name)?;
+
+                if let Some(parent) = file_path.parent() {
+                    fs::create_dir_all(parent).await?;
+                }
+
+                let content = read_entry_with_limits(&mut entry, 0, options, "read file from AR")?;
+                options.track_total_bytes(content.len() as u64)?;
+
+                let mut output_file = fs::File::create(&file_path).await?;
+                output_file.write_all(&content).await?;
+                Ok(file_path)
+            }
+            .await;
+
+            match result {
+                Ok(file_path) => files.push(file_path),
+                Err(error) => options.handle_error(error)?,
+            }
+        }
+
+        Ok(files)
+    }
+
+    #[cfg(not(feature = "ar"))]
+    async fn extract_ar(
+        &self,
+        _data: Cursor<Vec<u8>>,
+        _target_dir: &Path,
+        _options: &mut ExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        Err(Error::unsupported_format("AR support not enabled"))
+    }
+}
+
+/// Determine the archive type of a path from its extension, handling
+/// compound extensions like `.tar.gz` the same way as [`ArchiveFile::from_path`]
+fn detect_archive_type(path: &Path) -> Option<ArchiveType> {
+    let extension = path.extension()?;
+
+    // Handle compound extensions like .tar.gz
+    let full_name = path.file_name().and_then(|name| name.to_str())?;
+
+    if full_name.contains(".tar.") {
+        if let Some(pos) = full_name.find(".tar.") {
+            let compound_ext = &full_name[pos + 1..]; // Skip the dot
+            if let Some(archive_type) = ArchiveType::from_file_extension(OsStr::new(compound_ext))
+            {
+                return Some(archive_type);
+            }
+        }
+    }
+
+    ArchiveType::from_file_extension(extension)
+}
+
+/// Default cap on how many bytes [`ArchiveFile::from_url`] and friends will
+/// download when the caller hasn't set `ExtractOptions::max_total_bytes`
+const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Map a `Content-Type` header value to an [`ArchiveType`], ignoring any
+/// trailing parameters such as `; charset=...`
+#[cfg(feature = "http")]
+fn archive_type_from_content_type(content_type: &str) -> Option<ArchiveType> {
+    match content_type.split(';').next()?.trim() {
+        "application/zip" | "application/x-zip-compressed" => Some(ArchiveType::Zip),
+        "application/x-tar" => Some(ArchiveType::Tar),
+        "application/gzip" | "application/x-gzip" => Some(ArchiveType::Gz),
+        "application/x-bzip2" => Some(ArchiveType::Bz2),
+        "application/x-xz" => Some(ArchiveType::Xz),
+        "application/zstd" => Some(ArchiveType::Zstd),
+        _ => None,
+    }
+}
+
+/// Best-effort magic-byte sniff for the archive formats this crate
+/// supports, used as a last resort when a URL has no recognizable
+/// extension and the response has no usable `Content-Type`
+///
+/// This is intentionally narrow (just the formats in [`ArchiveType`]);
+/// general-purpose content sniffing belongs in `nvisy-core`. Note that
+/// gzip's magic bytes can't distinguish a plain `.gz` from a `.tar.gz`,
+/// so a gzip-compressed tarball sniffed this way is reported as `Gz`.
+#[cfg(feature = "http")]
+fn sniff_archive_type(data: &[u8]) -> Option<ArchiveType> {
+    if data.starts_with(b"PK\x03\x04") {
+        Some(ArchiveType::Zip)
+    } else if data.starts_with(&[0x1F, 0x8B]) {
+        Some(ArchiveType::Gz)
+    } else if data.starts_with(b"BZh") {
+        Some(ArchiveType::Bz2)
+    } else if data.starts_with(&[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00]) {
+        Some(ArchiveType::Xz)
+    } else if data.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        Some(ArchiveType::Zstd)
+    } else if data.starts_with(b"!<arch>\n") {
+        Some(ArchiveType::Ar)
+    } else {
+        None
+    }
+}
+
+/// Download `url`'s response body into memory, aborting once more than
+/// `max_bytes` have been received
+///
+/// The body is read off the network in chunks, so an oversized download
+/// is caught as soon as the cap is crossed rather than after the whole
+/// thing has been buffered.
+#[cfg(feature = "http")]
+async fn download_with_limit(url: &str, max_bytes: u64) -> Result<Vec<u8>> {
+    let response = reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| Error::other(format!("Failed to fetch {}: {}", url, e)))?;
+
+    read_body_with_limit(response, max_bytes, url).await
+}
+
+#[cfg(not(feature = "http"))]
+async fn download_with_limit(_url: &str, _max_bytes: u64) -> Result<Vec<u8>> {
+    Err(Error::unsupported_format("HTTP support not enabled"))
+}
+
+/// Drain `response`'s body in chunks, aborting once more than `max_bytes`
+/// have been received so an oversized download is caught mid-stream
+/// rather than after being fully buffered
+#[cfg(feature = "http")]
+async fn read_body_with_limit(response: reqwest::Response, max_bytes: u64, url: &str) -> Result<Vec<u8>> {
+    use futures_util::StreamExt;
+
+    let mut stream = response.bytes_stream();
+    let mut data = Vec::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| {
+            Error::other(format!("Failed to read response body from {}: {}", url, e))
+        })?;
+        data.extend_from_slice(&chunk);
+        if data.len() as u64 > max_bytes {
+            return Err(Error::size_limit_exceeded(format!(
+                "Download from {} exceeded max download size ({} bytes)",
+                url, max_bytes
+            )));
+        }
+    }
+
+    Ok(data)
+}
+
+/// Query the `Content-Length` of a remote archive without downloading it
+#[cfg(feature = "http")]
+async fn remote_content_length(url: &str) -> Result<u64> {
+    let response = reqwest::Client::new()
+        .head(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| Error::other(format!("Failed to query {}: {}", url, e)))?;
+
+    response
+        .content_length()
+        .ok_or_else(|| Error::other(format!("Response from {} had no Content-Length header", url)))
+}
+
+#[cfg(not(feature = "http"))]
+async fn remote_content_length(_url: &str) -> Result<u64> {
+    Err(Error::unsupported_format("HTTP support not enabled"))
+}
+
+/// Safety net on top of `recurse_depth`: stop descending into nested
+/// archives once this many bytes have been pulled from nested archives,
+/// regardless of remaining depth, to bound runaway expansion.
+const MAX_NESTED_EXTRACTION_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Unpack any nested archives found among `files` in place, up to `max_depth`
+/// levels deep, returning the flattened list of leaf (non-archive) files
+///
+/// Each nested archive is extracted into a sibling `<name>.extracted/`
+/// directory. A visited set (by canonical path) prevents an archive from
+/// being expanded twice, and a cumulative byte counter bounds how much
+/// nested content is pulled in overall.
+async fn extract_nested_archives(files: Vec<PathBuf>, max_depth: usize) -> Result<Vec<PathBuf>> {
+    let mut visited = std::collections::HashSet::new();
+    let mut total_extracted_bytes: u64 = 0;
+    let mut result = Vec::new();
+    let mut queue: Vec<(PathBuf, usize)> = files.into_iter().map(|f| (f, max_depth)).collect();
+
+    while let Some((path, remaining_depth)) = queue.pop() {
+        let Some(archive_type) = (remaining_depth > 0)
+            .then(|| detect_archive_type(&path))
+            .flatten()
+        else {
+            result.push(path);
+            continue;
+        };
+
+        let canonical = fs::canonicalize(&path).await.unwrap_or_else(|_| path.clone());
+        if !visited.insert(canonical) || total_extracted_bytes >= MAX_NESTED_EXTRACTION_BYTES {
+            result.push(path);
+            continue;
+        }
+
+        let nested = ArchiveFile::from_path(&path)?.with_archive_type(archive_type);
+        let data = nested.get_data().await?;
+        total_extracted_bytes += data.len() as u64;
+
+        let sibling_dir = {
+            let mut dir_name = path.file_name().unwrap_or_default().to_os_string();
+            dir_name.push(".extracted");
+            path.with_file_name(dir_name)
+        };
+        fs::create_dir_all(&sibling_dir).await?;
+
+        let mut nested_options = ExtractOptions::new();
+        let cursor = Cursor::new(data);
+        let nested_files = nested
+            .extract_archive(cursor, &sibling_dir, &mut nested_options)
+            .await?;
+
+        queue.extend(nested_files.into_iter().map(|f| (f, remaining_depth - 1)));
+    }
+
+    Ok(result)
+}
+
+/// Resolve an archive entry name against `target_dir`, rejecting any entry
+/// that would escape it via an absolute path or a `..` component (Zip Slip).
+pub(crate) fn sanitize_entry_path(target_dir: &Path, entry_name: &str) -> Result<PathBuf> {
+    let entry_path = Path::new(entry_name);
+
+    if entry_path.is_absolute() {
+        return Err(Error::unsafe_entry(entry_name));
+    }
+
+    let mut resolved = target_dir.to_path_buf();
+    for component in entry_path.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::unsafe_entry(entry_name));
+            }
+        }
+    }
+
+    if !resolved.starts_with(target_dir) {
+        return Err(Error::unsafe_entry(entry_name));
+    }
+
+    Ok(resolved)
+}
+
+/// Reject a symlink or hardlink entry whose target resolves outside
+/// `target_dir`, unless `allow_unsafe_symlinks` is set
+///
+/// `link_path` is the entry's own (already-sanitized) destination and
+/// `link_target` is the raw target recorded in the archive, which may be
+/// relative to `link_path`'s parent or (if rejected up front) absolute.
+fn validate_symlink_target(
+    target_dir: &Path,
+    link_path: &Path,
+    link_target: &Path,
+    allow_unsafe_symlinks: bool,
+) -> Result<()> {
+    if allow_unsafe_symlinks {
+        return Ok(());
+    }
+
+    if link_target.is_absolute() {
+        return Err(Error::invalid_archive(format!(
+            "Symlink target escapes extraction directory: {}",
+            link_target.display()
+        )));
+    }
+
+    let link_parent = link_path.parent().unwrap_or(target_dir);
+    let mut resolved = link_parent.to_path_buf();
+    for component in link_target.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return Err(Error::invalid_archive(format!(
+                        "Symlink target escapes extraction directory: {}",
+                        link_target.display()
+                    )));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::invalid_archive(format!(
+                    "Symlink target escapes extraction directory: {}",
+                    link_target.display()
+                )));
+            }
+        }
+    }
+
+    if !resolved.starts_with(target_dir) {
+        return Err(Error::invalid_archive(format!(
+            "Symlink target escapes extraction directory: {}",
+            link_target.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Read the entirety of `reader` in bounded chunks, enforcing
+/// `max_entry_bytes` and `max_compression_ratio` incrementally
+///
+/// Limits are checked after every chunk rather than after the full entry
+/// has been buffered, so a small compressed entry that expands without
+/// bound is caught partway through instead of exhausting memory first.
+/// `compressed_size` is the known compressed size of this entry; pass `0`
+/// to skip the ratio check for formats with no meaningful compressed size
+/// (e.g. an entry inside an already-decompressed TAR stream).
+fn read_entry_with_limits(
+    reader: &mut impl std::io::Read,
+    compressed_size: u64,
+    options: &ExtractOptions,
+    context: &str,
+) -> Result<Vec<u8>> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+    let mut buf = [0u8; CHUNK_SIZE];
+    let mut content = Vec::new();
+
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| Error::other(format!("Failed to {}: {}", context, e)))?;
+        if n == 0 {
+            break;
+        }
+        content.extend_from_slice(&buf[..n]);
+
+        if let Some(max_entry_bytes) = options.max_entry_bytes {
+            if content.len() as u64 > max_entry_bytes {
+                return Err(Error::size_limit_exceeded(format!(
+                    "Entry expanded past max_entry_bytes ({} bytes)",
+                    max_entry_bytes
+                )));
+            }
+        }
+
+        if let Some(max_ratio) = options.max_compression_ratio {
+            if compressed_size > 0 {
+                let ratio = content.len() as f64 / compressed_size as f64;
+                if ratio > max_ratio {
+                    return Err(Error::size_limit_exceeded(format!(
+                        "Compression ratio {:.1}x exceeds max_compression_ratio {:.1}x",
+                        ratio, max_ratio
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(content)
+}
+
+/// Stream `reader` into `writer` in bounded chunks, enforcing
+/// `max_entry_bytes` and `max_total_bytes` incrementally
+///
+/// Used by the true streaming TAR extraction path, where entries are
+/// written straight to disk and never buffered in memory.
+fn copy_entry_with_limits(
+    reader: &mut impl std::io::Read,
+    writer: &mut impl std::io::Write,
+    options: &mut ExtractOptions,
+) -> Result<u64> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+    let mut buf = [0u8; CHUNK_SIZE];
+    let mut entry_bytes: u64 = 0;
+
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| Error::other(format!("Failed to stream file from TAR: {}", e)))?;
+        if n == 0 {
+            break;
+        }
+        entry_bytes += n as u64;
+
+        if let Some(max_entry_bytes) = options.max_entry_bytes {
+            if entry_bytes > max_entry_bytes {
+                return Err(Error::size_limit_exceeded(format!(
+                    "Entry expanded past max_entry_bytes ({} bytes)",
+                    max_entry_bytes
+                )));
+            }
+        }
+
+        options.track_total_bytes(n as u64)?;
+        writer
+            .write_all(&buf[..n])
+            .map_err(|e| Error::other(format!("Failed to stream file from TAR: {}", e)))?;
+    }
+
+    Ok(entry_bytes)
+}
+
+/// Extract a TAR (optionally GZIP/BZIP2/XZ compressed) archive straight off
+/// disk without buffering the archive or its decompressed contents in memory
+///
+/// The decoder is chained directly onto the open file and entries are
+/// streamed to disk as they are read, so peak memory stays bounded by a
+/// single entry's buffer regardless of archive size.
+async fn extract_tar_streaming(
+    path: PathBuf,
+    archive_type: ArchiveType,
+    target_dir: PathBuf,
+    mut options: ExtractOptions,
+) -> Result<Vec<PathBuf>> {
+    tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&path)?;
+        let reader: Box<dyn std::io::Read> = match archive_type {
+            ArchiveType::Tar => Box::new(file),
+            ArchiveType::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+            ArchiveType::TarBz2 => Box::new(bzip2::read::BzDecoder::new(file)),
+            ArchiveType::TarXz => Box::new(xz2::read::XzDecoder::new(file)),
+            _ => unreachable!("extract_tar_streaming only handles tar variants"),
+        };
+
+        let mut archive = tar::Archive::new(reader);
+        let mut files = Vec::new();
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.to_string_lossy().into_owned();
+
+            options.track_entry_count()?;
+            if !options.should_extract(&entry_path) {
+                continue;
+            }
+
+            let entry_type = entry.header().entry_type();
+            let is_dir = entry_type.is_dir();
+            let is_link = entry_type == tar::EntryType::Symlink || entry_type == tar::EntryType::Link;
+            let link_name = if is_link {
+                entry.link_name()?.map(|l| l.into_owned())
+            } else {
+                None
+            };
+            let allow_unsafe_symlinks = options.allow_unsafe_symlinks;
+            let result: Result<Option<PathBuf>> = (|| {
+                let file_path = sanitize_entry_path(&target_dir, &entry_path)?;
+
+                if let Some(parent) = file_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+
+                if is_dir {
+                    if !options.allow_existing_dirs && file_path.exists() {
+                        return Err(Error::invalid_archive(format!(
+                            "Directory already exists: {}",
+                            file_path.display()
+                        )));
+                    }
+                    std::fs::create_dir_all(&file_path)?;
+                    Ok(None)
+                } else if is_link {
+                    let Some(link_target) = link_name else {
+                        return Ok(None);
+                    };
+                    validate_symlink_target(&target_dir, &file_path, &link_target, allow_unsafe_symlinks)?;
+
+                    #[cfg(unix)]
+                    let created = {
+                        std::os::unix::fs::symlink(&link_target, &file_path)?;
+                        true
+                    };
+                    #[cfg(not(unix))]
+                    let created = false;
+
+                    Ok(created.then_some(file_path))
+                } else {
+                    let mut output_file = std::fs::File::create(&file_path)?;
+                    copy_entry_with_limits(&mut entry, &mut output_file, &mut options)?;
+                    Ok(Some(file_path))
+                }
+            })();
+
+            match result {
+                Ok(Some(file_path)) => files.push(file_path),
+                Ok(None) => {}
+                Err(error) => options.handle_error(error)?,
+            }
+        }
+
+        Ok(files)
+    })
+    .await
+    .map_err(|e| Error::other(format!("Extraction task panicked: {}", e)))?
+}
+
+/// Write `entries` into a new archive of `archive_type`, returning the writer
+fn pack_sync<W>(archive_type: ArchiveType, entries: Vec<PackEntry>, writer: W) -> Result<W>
+where
+    W: std::io::Write + std::io::Seek,
+{
+    if archive_type == ArchiveType::Zip {
+        pack_zip(entries, writer)
+    } else if archive_type.is_tar_variant() {
+        pack_tar(archive_type, entries, writer)
+    } else {
+        Err(Error::unsupported_format(format!(
+            "Cannot pack entries into a {} archive",
+            archive_type
+        )))
+    }
+}
+
+#[cfg(feature = "zip")]
+fn pack_zip<W: std::io::Write + std::io::Seek>(entries: Vec<PackEntry>, writer: W) -> Result<W> {
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    let mut zip_writer = ZipWriter::new(writer);
+
+    for entry in entries {
+        match entry {
+            PackEntry::File {
+                archive_path,
+                source_path,
+            } => {
+                let content = std::fs::read(&source_path)?;
+                let mut options =
+                    SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let mode = std::fs::metadata(&source_path)?.permissions().mode();
+                    options = options.unix_permissions(mode);
+                }
+
+                zip_writer.start_file(&archive_path, options)?;
+                std::io::Write::write_all(&mut zip_writer, &content)?;
+            }
+            PackEntry::Memory { archive_path, data } => {
+                let options =
+                    SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+                zip_writer.start_file(&archive_path, options)?;
+                std::io::Write::write_all(&mut zip_writer, &data)?;
+            }
+        }
+    }
+
+    Ok(zip_writer.finish()?)
+}
+
+#[cfg(not(feature = "zip"))]
+fn pack_zip<W: std::io::Write + std::io::Seek>(_entries: Vec<PackEntry>, _writer: W) -> Result<W> {
+    Err(Error::unsupported_format("ZIP support not enabled"))
+}
+
+#[cfg(feature = "tar")]
+fn pack_tar<W: std::io::Write + std::io::Seek>(
+    archive_type: ArchiveType,
+    entries: Vec<PackEntry>,
+    mut writer: W,
+) -> Result<W> {
+    let mut builder = tar::Builder::new(Vec::new());
+
+    for entry in entries {
+        match entry {
+            PackEntry::File {
+                archive_path,
+                source_path,
+            } => {
+                builder.append_path_with_name(&source_path, &archive_path)?;
+            }
+            PackEntry::Memory { archive_path, data } => {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(data.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, &archive_path, data.as_slice())?;
+            }
+        }
+    }
+
+    let uncompressed = builder.into_inner()?;
+    let compressed = compress_tar_bytes(archive_type, uncompressed)?;
+    std::io::Write::write_all(&mut writer, &compressed)?;
+    Ok(writer)
+}
+
+#[cfg(not(feature = "tar"))]
+fn pack_tar<W: std::io::Write + std::io::Seek>(
+    _archive_type: ArchiveType,
+    _entries: Vec<PackEntry>,
+    _writer: W,
+) -> Result<W> {
+    Err(Error::unsupported_format("TAR support not enabled"))
+}
+
+/// Apply the compression implied by `archive_type` to an uncompressed TAR byte stream
+#[cfg(feature = "tar")]
+fn compress_tar_bytes(archive_type: ArchiveType, uncompressed: Vec<u8>) -> Result<Vec<u8>> {
+    use std::io::Write;
+
+    if archive_type == ArchiveType::Tar {
+        return Ok(uncompressed);
+    }
+
+    let compressed = match archive_type {
+        ArchiveType::TarGz => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&uncompressed)?;
+            encoder
+                .finish()
+                .map_err(|e| Error::other(format!("Failed to compress GZIP: {}", e)))?
+        }
+        ArchiveType::TarBz2 => {
+            let mut encoder =
+                bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+            encoder.write_all(&uncompressed)?;
+            encoder
+                .finish()
+                .map_err(|e| Error::other(format!("Failed to compress BZIP2: {}", e)))?
+        }
+        ArchiveType::TarXz => {
+            let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+            encoder.write_all(&uncompressed)?;
+            encoder
+                .finish()
+                .map_err(|e| Error::other(format!("Failed to compress XZ: {}", e)))?
+        }
+        #[cfg(feature = "zstd")]
+        ArchiveType::TarZst => {
+            let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 0)
+                .map_err(|e| Error::other(format!("Failed to initialize Zstandard encoder: {}", e)))?;
+            encoder.write_all(&uncompressed)?;
+            encoder
+                .finish()
+                .map_err(|e| Error::other(format!("Failed to compress Zstandard: {}", e)))?
+        }
+        #[cfg(not(feature = "zstd"))]
+        ArchiveType::TarZst => {
+            return Err(Error::unsupported_format("Zstandard support not enabled"));
+        }
+        #[cfg(feature = "lz4")]
+        ArchiveType::TarLz4 => {
+            let mut encoder = lz4::EncoderBuilder::new()
+                .build(Vec::new())
+                .map_err(|e| Error::other(format!("Failed to initialize LZ4 encoder: {}", e)))?;
+            encoder.write_all(&uncompressed)?;
+            let (compressed, result) = encoder.finish();
+            result.map_err(|e| Error::other(format!("Failed to compress LZ4: {}", e)))?;
+            compressed
+        }
+        #[cfg(not(feature = "lz4"))]
+        ArchiveType::TarLz4 => {
+            return Err(Error::unsupported_format("LZ4 support not enabled"));
+        }
+        ArchiveType::Tar => unreachable!("handled above"),
+        _ => unreachable!("compress_tar_bytes only handles tar variants"),
+    };
+
+    Ok(compressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_file_from_memory() {
+        let data = vec![0x50, 0x4B, 0x03, 0x04]; // ZIP signature
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        assert_eq!(archive.archive_type(), ArchiveType::Zip);
+        assert!(archive.path().is_none());
+    }
+
+    #[test]
+    fn test_archive_file_from_iterator() {
+        let data = [0x50, 0x4B, 0x03, 0x04]; // ZIP signature
+        let archive = ArchiveFile::from_iterator(ArchiveType::Zip, data.into_iter());
+        assert_eq!(archive.archive_type(), ArchiveType::Zip);
+    }
+
+    #[test]
+    fn test_archive_file_from_path() -> Result<()> {
+        let archive = ArchiveFile::from_path("test.zip")?;
+        assert_eq!(archive.archive_type(), ArchiveType::Zip);
+        assert!(archive.path().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compound_extension() -> Result<()> {
+        let archive = ArchiveFile::from_path("test.tar.gz")?;
+        assert_eq!(archive.archive_type(), ArchiveType::TarGz);
+        Ok(())
     }
 
     #[test]
@@ -492,10 +1879,401 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_archive_file_from_url() -> Result<()> {
+        let archive = ArchiveFile::from_url("https://example.com/archive.tar.gz")?;
+        assert_eq!(archive.archive_type(), ArchiveType::TarGz);
+        assert!(archive.path().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_archive_file_from_url_ignores_query_string() -> Result<()> {
+        let archive = ArchiveFile::from_url("https://example.com/archive.zip?sig=abc123")?;
+        assert_eq!(archive.archive_type(), ArchiveType::Zip);
+        Ok(())
+    }
+
+    #[test]
+    fn test_archive_file_from_url_rejects_missing_extension() {
+        let result = ArchiveFile::from_url("https://example.com/download");
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_memory_size() {
         let data = vec![1, 2, 3, 4, 5];
         let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
         assert_eq!(archive.size().await.unwrap(), 5);
     }
+
+    #[test]
+    fn test_sanitize_entry_path_rejects_parent_dir() {
+        let target_dir = Path::new("/tmp/extract-root");
+        let result = sanitize_entry_path(target_dir, "../../etc/passwd");
+        assert!(matches!(result, Err(Error::UnsafeEntry { .. })));
+    }
+
+    #[test]
+    fn test_sanitize_entry_path_rejects_absolute_path() {
+        let target_dir = Path::new("/tmp/extract-root");
+        let result = sanitize_entry_path(target_dir, "/etc/passwd");
+        assert!(matches!(result, Err(Error::UnsafeEntry { .. })));
+    }
+
+    #[test]
+    fn test_sanitize_entry_path_allows_nested_file() {
+        let target_dir = Path::new("/tmp/extract-root");
+        let result = sanitize_entry_path(target_dir, "subdir/file.txt").unwrap();
+        assert_eq!(result, target_dir.join("subdir").join("file.txt"));
+    }
+
+    #[test]
+    fn test_extract_options_default_extracts_everything() {
+        let options = ExtractOptions::new();
+        assert!(options.should_extract("anything.txt"));
+    }
+
+    #[test]
+    fn test_extract_options_include_only() -> Result<()> {
+        let options = ExtractOptions::new()
+            .with_default_extract(false)
+            .with_include("*.json")?;
+        assert!(options.should_extract("data.json"));
+        assert!(!options.should_extract("data.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_options_last_match_wins() -> Result<()> {
+        let options = ExtractOptions::new()
+            .with_include("*.txt")?
+            .with_exclude("secret.txt")?;
+        assert!(options.should_extract("notes.txt"));
+        assert!(!options.should_extract("secret.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_options_on_error_can_swallow_errors() {
+        let mut options =
+            ExtractOptions::new().with_on_error(|_error| Ok(()));
+        assert!(options.handle_error(Error::other("boom")).is_ok());
+    }
+
+    #[test]
+    fn test_extract_options_without_handler_propagates_error() {
+        let mut options = ExtractOptions::new();
+        assert!(options.handle_error(Error::other("boom")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_extraction_handles_many_entries() -> Result<()> {
+        let source_dir = TempDir::new().unwrap();
+        let archive_path = source_dir.path().join("bundle.tar.gz");
+
+        // Build a synthetic tar.gz on disk; streaming extraction never reads
+        // this into a single in-memory buffer.
+        let archive_file = std::fs::File::create(&archive_path).unwrap();
+        let encoder = flate2::write::GzEncoder::new(archive_file, flate2::Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        for i in 0..200 {
+            let name = format!("entry-{i}.txt");
+            let data = format!("payload {i}").into_bytes();
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, &name, data.as_slice())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let archive = ArchiveFile::from_path(&archive_path)?;
+        let handler = archive.unpack().await?;
+        assert_eq!(handler.file_count(), 200);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pack_zip_round_trips_through_unpack() -> Result<()> {
+        let entries = vec![
+            PackEntry::memory("hello.txt", b"Hello, World!".to_vec()),
+            PackEntry::memory("nested/data.json", b"{}".to_vec()),
+        ];
+
+        let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        let handler = archive.unpack().await?;
+        assert_eq!(handler.file_count(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pack_tar_gz_round_trips_through_unpack() -> Result<()> {
+        let entries = vec![PackEntry::memory("hello.txt", b"Hello, World!".to_vec())];
+
+        let data = ArchiveFile::pack(ArchiveType::TarGz, entries).await?;
+        let archive = ArchiveFile::from_memory(ArchiveType::TarGz, data);
+        let handler = archive.unpack().await?;
+        assert_eq!(handler.file_count(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pack_rejects_unsupported_archive_type() {
+        let entries = vec![PackEntry::memory("hello.txt", b"hi".to_vec())];
+        let result = ArchiveFile::pack(ArchiveType::Gz, entries).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unpack_with_recurse_depth_expands_nested_zip() -> Result<()> {
+        let inner_data =
+            ArchiveFile::pack(ArchiveType::Zip, vec![PackEntry::memory("inner.txt", b"hi".to_vec())])
+                .await?;
+        let outer_data = ArchiveFile::pack(
+            ArchiveType::Zip,
+            vec![PackEntry::memory("nested.zip", inner_data)],
+        )
+        .await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, outer_data);
+        let options = ExtractOptions::new().with_recurse_depth(1);
+        let handler = archive.unpack_with(options).await?;
+
+        assert!(handler
+            .file_paths()
+            .iter()
+            .any(|p| p.file_name().and_then(|n| n.to_str()) == Some("inner.txt")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unpack_without_recurse_depth_leaves_nested_archive_packed() -> Result<()> {
+        let inner_data =
+            ArchiveFile::pack(ArchiveType::Zip, vec![PackEntry::memory("inner.txt", b"hi".to_vec())])
+                .await?;
+        let outer_data = ArchiveFile::pack(
+            ArchiveType::Zip,
+            vec![PackEntry::memory("nested.zip", inner_data)],
+        )
+        .await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, outer_data);
+        let handler = archive.unpack().await?;
+
+        assert!(handler
+            .file_paths()
+            .iter()
+            .any(|p| p.file_name().and_then(|n| n.to_str()) == Some("nested.zip")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_entry_bytes_rejects_oversized_entry() -> Result<()> {
+        let entries = vec![PackEntry::memory("big.txt", vec![b'a'; 1024])];
+        let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        let options = ExtractOptions::new().with_max_entry_bytes(100);
+        let result = archive.unpack_with(options).await;
+
+        assert!(matches!(result, Err(Error::SizeLimitExceeded { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_total_bytes_rejects_once_aggregate_exceeded() -> Result<()> {
+        let entries = vec![
+            PackEntry::memory("a.txt", vec![b'a'; 100]),
+            PackEntry::memory("b.txt", vec![b'b'; 100]),
+        ];
+        let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        let options = ExtractOptions::new().with_max_total_bytes(150);
+        let result = archive.unpack_with(options).await;
+
+        assert!(matches!(result, Err(Error::SizeLimitExceeded { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_compression_ratio_rejects_decompression_bomb() -> Result<()> {
+        // A highly compressible payload that expands far beyond its
+        // compressed size once inflated.
+        let entries = vec![PackEntry::memory("bomb.txt", vec![0u8; 1_000_000])];
+        let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        let options = ExtractOptions::new().with_max_compression_ratio(10.0);
+        let result = archive.unpack_with(options).await;
+
+        assert!(matches!(result, Err(Error::SizeLimitExceeded { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_size_limits_allow_extraction_within_bounds() -> Result<()> {
+        let entries = vec![PackEntry::memory("small.txt", b"hello".to_vec())];
+        let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        let options = ExtractOptions::new()
+            .with_max_entry_bytes(1024)
+            .with_max_total_bytes(1024)
+            .with_max_compression_ratio(1000.0);
+        let handler = archive.unpack_with(options).await?;
+
+        assert_eq!(handler.file_count(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_entry_count_rejects_archive_with_too_many_entries() -> Result<()> {
+        let entries = vec![
+            PackEntry::memory("a.txt", b"a".to_vec()),
+            PackEntry::memory("b.txt", b"b".to_vec()),
+            PackEntry::memory("c.txt", b"c".to_vec()),
+        ];
+        let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        let options = ExtractOptions::new().with_max_entry_count(2);
+        let result = archive.unpack_with(options).await;
+
+        assert!(matches!(result, Err(Error::ResourceLimit { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_entry_count_allows_archive_within_bounds() -> Result<()> {
+        let entries = vec![
+            PackEntry::memory("a.txt", b"a".to_vec()),
+            PackEntry::memory("b.txt", b"b".to_vec()),
+        ];
+        let data = ArchiveFile::pack(ArchiveType::Zip, entries).await?;
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Zip, data);
+        let options = ExtractOptions::new().with_max_entry_count(2);
+        let handler = archive.unpack_with(options).await?;
+
+        assert_eq!(handler.file_count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_symlink_target_rejects_absolute_target() {
+        let target_dir = Path::new("/tmp/extract-root");
+        let link_path = target_dir.join("link.txt");
+        let result =
+            validate_symlink_target(target_dir, &link_path, Path::new("/etc/passwd"), false);
+        assert!(matches!(result, Err(Error::InvalidArchive { .. })));
+    }
+
+    #[test]
+    fn test_validate_symlink_target_rejects_parent_dir_escape() {
+        let target_dir = Path::new("/tmp/extract-root");
+        let link_path = target_dir.join("link.txt");
+        let result = validate_symlink_target(
+            target_dir,
+            &link_path,
+            Path::new("../../etc/passwd"),
+            false,
+        );
+        assert!(matches!(result, Err(Error::InvalidArchive { .. })));
+    }
+
+    #[test]
+    fn test_validate_symlink_target_allows_nested_target() {
+        let target_dir = Path::new("/tmp/extract-root");
+        let link_path = target_dir.join("subdir/link.txt");
+        let result = validate_symlink_target(
+            target_dir,
+            &link_path,
+            Path::new("../sibling.txt"),
+            false,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_symlink_target_allow_unsafe_bypasses_check() {
+        let target_dir = Path::new("/tmp/extract-root");
+        let link_path = target_dir.join("link.txt");
+        let result =
+            validate_symlink_target(target_dir, &link_path, Path::new("/etc/passwd"), true);
+        assert!(result.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unpack_rejects_tar_symlink_escaping_destination() -> Result<()> {
+        let mut data = Vec::new();
+        {
+            let mut builder = tar::Builder::new(&mut data);
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_link(&mut header, "link.txt", "../../etc/passwd")
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Tar, data);
+        let result = archive.unpack().await;
+
+        assert!(matches!(result, Err(Error::InvalidArchive { .. })));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unpack_materializes_safe_tar_symlink() -> Result<()> {
+        let mut data = Vec::new();
+        {
+            let mut builder = tar::Builder::new(&mut data);
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_size(5);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "real.txt", b"hello".as_slice())
+                .unwrap();
+
+            let mut link_header = tar::Header::new_gnu();
+            link_header.set_entry_type(tar::EntryType::Symlink);
+            link_header.set_size(0);
+            link_header.set_mode(0o644);
+            link_header.set_cksum();
+            builder
+                .append_link(&mut link_header, "link.txt", "real.txt")
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let archive = ArchiveFile::from_memory(ArchiveType::Tar, data);
+        let handler = archive.unpack().await?;
+
+        let link_path = handler
+            .file_paths()
+            .iter()
+            .find(|p| p.file_name().and_then(|n| n.to_str()) == Some("link.txt"))
+            .expect("symlink entry should be extracted")
+            .clone();
+        assert_eq!(std::fs::read_link(&link_path).unwrap(), Path::new("real.txt"));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,722 @@
+//! Format-neutral facade over the individual archive handlers
+//!
+//! This module provides [`AnyArchiveHandler`], which sniffs an archive's
+//! magic bytes to pick the right backend (ZIP or TAR, including TAR's
+//! compressed variants) and exposes the subset of their APIs that makes
+//! sense across formats, without callers needing to know up front which
+//! concrete handler they need.
+
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use super::tar_handler::{TarArchiveHandler, TarEntryInfo};
+use super::zip_handler::{ZipArchiveHandler, ZipEntryInfo};
+use crate::{ArchiveType, Error, Result};
+
+/// Magic bytes for a ZIP local file header
+const ZIP_MAGIC: &[u8] = &[0x50, 0x4b, 0x03, 0x04];
+
+/// Decode a single-file compressed payload fully into memory
+///
+/// These formats have no internal structure of their own, so the whole
+/// decoded stream becomes one synthetic entry's content.
+fn decode_single_file(data: Vec<u8>, archive_type: ArchiveType) -> Result<Vec<u8>> {
+    let cursor = Cursor::new(data);
+    let mut decoded = Vec::new();
+
+    match archive_type {
+        ArchiveType::Gz => {
+            use flate2::read::GzDecoder;
+            GzDecoder::new(cursor).read_to_end(&mut decoded)?;
+        }
+        ArchiveType::Bz2 => {
+            use bzip2::read::BzDecoder;
+            BzDecoder::new(cursor).read_to_end(&mut decoded)?;
+        }
+        ArchiveType::Xz => {
+            use xz2::read::XzDecoder;
+            XzDecoder::new(cursor).read_to_end(&mut decoded)?;
+        }
+        #[cfg(feature = "zstd")]
+        ArchiveType::Zstd => {
+            zstd::stream::read::Decoder::new(cursor)
+                .map_err(|e| {
+                    Error::other(format!("Failed to initialize Zstandard decoder: {}", e))
+                })?
+                .read_to_end(&mut decoded)?;
+        }
+        #[cfg(not(feature = "zstd"))]
+        ArchiveType::Zstd => {
+            return Err(Error::unsupported_format("Zstandard support not enabled"));
+        }
+        #[cfg(feature = "lz4")]
+        ArchiveType::Lz4 => {
+            lz4::Decoder::new(cursor)
+                .map_err(|e| Error::other(format!("Failed to initialize LZ4 decoder: {}", e)))?
+                .read_to_end(&mut decoded)?;
+        }
+        #[cfg(not(feature = "lz4"))]
+        ArchiveType::Lz4 => {
+            return Err(Error::unsupported_format("LZ4 support not enabled"));
+        }
+        other => {
+            return Err(Error::unsupported_format(format!(
+                "Not a single-file compression format: {}",
+                other
+            )));
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Format-neutral information about an archive entry
+///
+/// A normalized view over [`ZipEntryInfo`] and [`TarEntryInfo`], carrying
+/// only the fields that make sense for every supported format. Use the
+/// concrete handler directly (via [`AnyArchiveHandler::as_zip`] /
+/// [`AnyArchiveHandler::as_tar`]) when format-specific metadata is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryInfo {
+    /// Path of the entry within the archive
+    pub name: String,
+    /// Uncompressed size in bytes
+    pub size: u64,
+    /// Whether this entry is a directory
+    pub is_dir: bool,
+}
+
+impl From<ZipEntryInfo> for EntryInfo {
+    fn from(entry: ZipEntryInfo) -> Self {
+        Self {
+            name: entry.name,
+            size: entry.size,
+            is_dir: entry.is_dir,
+        }
+    }
+}
+
+impl From<TarEntryInfo> for EntryInfo {
+    fn from(entry: TarEntryInfo) -> Self {
+        Self {
+            is_dir: entry.entry_type.is_dir(),
+            name: entry.path.to_string_lossy().into_owned(),
+            size: entry.size,
+        }
+    }
+}
+
+/// A single archive entry with its content loaded into memory
+///
+/// Produced by [`AnyArchiveHandler::read_entries`]. Unlike [`EntryInfo`],
+/// this carries the entry's decoded `content`, which maps directly onto
+/// `nvisy_core::Content::binary`/`Content::text` for callers that want to
+/// wrap archive contents as first-class referenceable data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Path of the entry within the archive (empty for the synthetic entry
+    /// produced by a single-file compressor, which carries no name)
+    pub path: String,
+    /// Size of the decoded content in bytes
+    pub size: u64,
+    /// Whether this entry is a directory
+    pub is_dir: bool,
+    /// The entry's decoded content
+    pub content: Vec<u8>,
+}
+
+/// An entry discovered while recursively descending into nested archives
+///
+/// Produced by [`AnyArchiveHandler::read_entries_recursive`]. The
+/// `source_id`/`mapping_id` fields deliberately mirror the shape of
+/// `nvisy_core::io::DataReference` (this crate has no dependency on
+/// `nvisy-core` — general-purpose content tracking lives there, archive
+/// decoding lives here) so a caller that depends on both crates can build a
+/// `DataReference` straight from a `NestedEntry` without any translation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedEntry {
+    /// Identifier shared by every entry produced from the same top-level
+    /// [`Self::read_entries_recursive`] call, mirroring the `source_id` a
+    /// caller would assign the outermost archive's `DataReference`
+    pub source_id: String,
+    /// Bang-delimited path recording how this entry was reached, e.g.
+    /// `zip!inner.zip!file.txt`: the outermost archive type's primary
+    /// extension, then each entry path along the way
+    pub mapping_id: String,
+    /// Nesting depth at which this entry was found (0 = top-level archive)
+    pub depth: usize,
+    /// The entry itself
+    pub entry: Entry,
+}
+
+/// Limits applied while recursively descending into nested archives
+///
+/// Guards [`AnyArchiveHandler::read_entries_recursive`] against
+/// zip-bomb-style inputs, where a small archive decompresses into an
+/// arbitrarily large or deeply nested tree.
+#[derive(Debug, Clone, Copy)]
+pub struct DescentLimits {
+    /// Maximum nesting depth to recurse into; a nested archive found deeper
+    /// than this fails the descent with [`Error::ResourceLimit`] (with 0,
+    /// any nested archive does)
+    pub max_depth: usize,
+    /// Maximum total decompressed bytes across every entry visited,
+    /// including entries that are themselves descended into
+    pub max_total_bytes: u64,
+}
+
+impl Default for DescentLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 8,
+            max_total_bytes: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// Identify `entry`'s archive type, if it looks like a nested archive
+///
+/// Tries magic-byte sniffing first, since it can't be spoofed by a
+/// misleading filename, falling back to the entry's extension for formats
+/// [`ArchiveType::from_magic`] can't distinguish from plain data.
+fn detect_nested_archive_type(entry: &Entry) -> Option<ArchiveType> {
+    ArchiveType::from_magic(&entry.content)
+        .or_else(|| Path::new(&entry.path).extension().and_then(ArchiveType::from_file_extension))
+}
+
+/// Archive handler that dispatches to the right backend based on the
+/// archive's detected format
+///
+/// Construct with [`Self::from_memory`], which sniffs the data's magic
+/// bytes rather than trusting a file extension. The TAR variant is backed
+/// by [`TarArchiveHandler::from_data_autodetect`], so it also transparently
+/// handles gzip/bzip2/xz/zstd-compressed TAR streams. Single-file
+/// compressors (plain gzip/bzip2/xz/zstd/lz4, as opposed to a compressed
+/// TAR stream) are ambiguous from magic bytes alone and must be constructed
+/// explicitly with [`Self::from_compressed`].
+pub enum AnyArchiveHandler {
+    /// A ZIP archive
+    Zip(ZipArchiveHandler<Cursor<Vec<u8>>>),
+    /// A TAR archive, optionally wrapped in a compression codec
+    Tar(TarArchiveHandler<Box<dyn Read>>),
+    /// A single-file compressed payload, decoded eagerly since these
+    /// formats have no directory structure of their own
+    Compressed {
+        /// The fully decoded payload
+        data: Vec<u8>,
+        /// Which single-file compression format this was
+        archive_type: ArchiveType,
+    },
+}
+
+impl AnyArchiveHandler {
+    /// Detect the archive format from `data`'s magic bytes and construct
+    /// the matching handler
+    pub fn from_memory(data: Vec<u8>) -> Result<Self> {
+        if data.starts_with(ZIP_MAGIC) {
+            return Ok(Self::Zip(ZipArchiveHandler::from_memory(data)?));
+        }
+
+        let (handler, _archive_type) = TarArchiveHandler::from_data_autodetect(data)?;
+        Ok(Self::Tar(handler))
+    }
+
+    /// Construct a handler for a single-file compressed payload
+    ///
+    /// Unlike [`Self::from_memory`], this must be told the archive type
+    /// explicitly: gzip/bzip2/xz/zstd/lz4 magic bytes alone can't
+    /// distinguish a plain compressed file from a compressed TAR stream
+    /// (see [`ArchiveType::from_magic`]). A compressed TAR stream goes to
+    /// [`Self::from_memory`] instead, which detects its compression itself.
+    pub fn from_compressed(data: Vec<u8>, archive_type: ArchiveType) -> Result<Self> {
+        let data = decode_single_file(data, archive_type)?;
+        Ok(Self::Compressed { data, archive_type })
+    }
+
+    /// Construct a handler for `data`, given an already-known `archive_type`
+    ///
+    /// Unlike [`Self::from_memory`], which only ever sniffs magic bytes
+    /// (and so can't tell a plain compressed file from a compressed TAR
+    /// stream), this trusts the caller's `archive_type` and dispatches
+    /// directly. Used by [`Self::read_entries_recursive`] once a nested
+    /// entry's format has been identified via [`ArchiveType::from_magic`] or
+    /// its file extension.
+    fn from_memory_with_type(data: Vec<u8>, archive_type: ArchiveType) -> Result<Self> {
+        match archive_type {
+            ArchiveType::Zip => Ok(Self::Zip(ZipArchiveHandler::from_memory(data)?)),
+            other if other.is_tar_variant() => {
+                let handler = TarArchiveHandler::from_compressed_data(data, other)?;
+                Ok(Self::Tar(handler))
+            }
+            other => Self::from_compressed(data, other),
+        }
+    }
+
+    /// Get the detected archive type
+    pub fn archive_type(&self) -> ArchiveType {
+        match self {
+            Self::Zip(handler) => handler.archive_type(),
+            Self::Tar(handler) => handler.archive_type(),
+            Self::Compressed { archive_type, .. } => *archive_type,
+        }
+    }
+
+    /// Borrow the underlying [`ZipArchiveHandler`], if this is a ZIP archive
+    pub fn as_zip(&mut self) -> Option<&mut ZipArchiveHandler<Cursor<Vec<u8>>>> {
+        match self {
+            Self::Zip(handler) => Some(handler),
+            Self::Tar(_) | Self::Compressed { .. } => None,
+        }
+    }
+
+    /// Borrow the underlying [`TarArchiveHandler`], if this is a TAR archive
+    pub fn as_tar(&mut self) -> Option<&mut TarArchiveHandler<Box<dyn Read>>> {
+        match self {
+            Self::Zip(_) | Self::Compressed { .. } => None,
+            Self::Tar(handler) => Some(handler),
+        }
+    }
+
+    /// List all entries without extracting
+    pub fn list_entries(&mut self) -> Result<Vec<EntryInfo>> {
+        match self {
+            Self::Zip(handler) => Ok(handler
+                .list_entries()?
+                .into_iter()
+                .map(EntryInfo::from)
+                .collect()),
+            Self::Tar(handler) => Ok(handler
+                .list_entries()?
+                .into_iter()
+                .map(EntryInfo::from)
+                .collect()),
+            Self::Compressed { data, .. } => Ok(vec![EntryInfo {
+                name: String::new(),
+                size: data.len() as u64,
+                is_dir: false,
+            }]),
+        }
+    }
+
+    /// Read a single entry's content into memory
+    ///
+    /// For a TAR archive, this walks entries sequentially from wherever the
+    /// underlying stream currently is, since TAR offers no random access;
+    /// calling it (or [`Self::list_entries`] / [`Self::extract_to`]) more
+    /// than once on the same TAR handler will not see entries already
+    /// consumed by a prior call. For a single-file compressed payload, the
+    /// synthetic entry has no name, so only `""` resolves.
+    pub fn read_file(&mut self, name: &str) -> Result<Vec<u8>> {
+        match self {
+            Self::Zip(handler) => handler.read_file(name),
+            Self::Tar(handler) => {
+                for entry in handler.entries()? {
+                    let mut entry = entry?;
+                    if entry.path()?.to_string_lossy() == name {
+                        let mut content = Vec::new();
+                        Read::read_to_end(&mut entry, &mut content)?;
+                        return Ok(content);
+                    }
+                }
+                Err(Error::entry_not_found(name))
+            }
+            Self::Compressed { data, .. } => {
+                if name.is_empty() {
+                    Ok(data.clone())
+                } else {
+                    Err(Error::entry_not_found(name))
+                }
+            }
+        }
+    }
+
+    /// Read every entry's content into memory alongside its metadata
+    ///
+    /// Dispatches to the appropriate decoder per format: ZIP reads entries
+    /// by name via the underlying archive; TAR (including its compressed
+    /// variants) walks sequentially; a single-file compressed payload
+    /// yields exactly one synthetic [`Entry`] with an empty path. Directory
+    /// entries are included with empty content.
+    pub fn read_entries(&mut self) -> Result<Vec<Entry>> {
+        match self {
+            Self::Zip(handler) => {
+                let infos = handler.list_entries()?;
+                infos
+                    .into_iter()
+                    .map(|info| {
+                        let content = if info.is_dir {
+                            Vec::new()
+                        } else {
+                            handler.read_file(&info.name)?
+                        };
+                        Ok(Entry {
+                            path: info.name,
+                            size: info.size,
+                            is_dir: info.is_dir,
+                            content,
+                        })
+                    })
+                    .collect()
+            }
+            Self::Tar(handler) => {
+                let mut entries = Vec::new();
+                for entry in handler.entries()? {
+                    let mut entry = entry?;
+                    let is_dir = entry.header().entry_type().is_dir();
+                    let path = entry.path()?.to_string_lossy().into_owned();
+                    let size = entry.header().size()?;
+                    let mut content = Vec::new();
+                    if !is_dir {
+                        Read::read_to_end(&mut entry, &mut content)?;
+                    }
+                    entries.push(Entry {
+                        path,
+                        size,
+                        is_dir,
+                        content,
+                    });
+                }
+                Ok(entries)
+            }
+            Self::Compressed { data, .. } => Ok(vec![Entry {
+                path: String::new(),
+                size: data.len() as u64,
+                is_dir: false,
+                content: data.clone(),
+            }]),
+        }
+    }
+
+    /// Recursively read every entry, descending into nested archives
+    ///
+    /// When an entry is itself a recognized archive format (detected via
+    /// [`ArchiveType::from_magic`], falling back to its name's extension),
+    /// the descent transparently recurses into it instead of yielding it
+    /// as-is, up to `limits.max_depth`. Each produced [`NestedEntry`]
+    /// carries `source_id` (passed through unchanged from the caller, so it
+    /// can match the top-level archive's own `DataReference`) and a
+    /// `mapping_id` such as `zip!inner.zip!file.txt`, letting
+    /// callers reconstruct provenance for deeply nested content.
+    ///
+    /// A nested entry that merely looks like an archive (matching
+    /// signature or extension) but fails to parse as one is yielded
+    /// as-is rather than aborting the whole descent.
+    ///
+    /// Returns [`Error::size_limit_exceeded`] if `limits.max_total_bytes`
+    /// would be exceeded, or [`Error::resource_limit`] if recursing further
+    /// would exceed `limits.max_depth` — guarding against zip-bomb-style
+    /// nested archives.
+    pub fn read_entries_recursive(
+        &mut self,
+        source_id: impl Into<String>,
+        limits: &DescentLimits,
+    ) -> Result<Vec<NestedEntry>> {
+        let source_id = source_id.into();
+        let root_name = self.archive_type().primary_extension().to_string();
+        let mut total_bytes = 0u64;
+        self.descend(&source_id, &root_name, 0, limits, &mut total_bytes)
+    }
+
+    /// Recursion step backing [`Self::read_entries_recursive`]
+    fn descend(
+        &mut self,
+        source_id: &str,
+        prefix: &str,
+        depth: usize,
+        limits: &DescentLimits,
+        total_bytes: &mut u64,
+    ) -> Result<Vec<NestedEntry>> {
+        let mut results = Vec::new();
+
+        for entry in self.read_entries()? {
+            *total_bytes += entry.content.len() as u64;
+            if *total_bytes > limits.max_total_bytes {
+                return Err(Error::size_limit_exceeded(format!(
+                    "Recursive archive descent exceeded {} decompressed bytes",
+                    limits.max_total_bytes
+                )));
+            }
+
+            let mapping_id = if entry.path.is_empty() {
+                prefix.to_string()
+            } else {
+                format!("{}!{}", prefix, entry.path)
+            };
+
+            if !entry.is_dir {
+                if let Some(nested_type) = detect_nested_archive_type(&entry) {
+                    if depth + 1 > limits.max_depth {
+                        return Err(Error::resource_limit(format!(
+                            "Archive nesting at {} exceeded max depth {}",
+                            mapping_id, limits.max_depth
+                        )));
+                    }
+                    if let Ok(mut nested_handler) =
+                        Self::from_memory_with_type(entry.content.clone(), nested_type)
+                    {
+                        let bytes_before = *total_bytes;
+                        match nested_handler.descend(source_id, &mapping_id, depth + 1, limits, total_bytes) {
+                            Ok(nested) => {
+                                results.extend(nested);
+                                continue;
+                            }
+                            Err(e @ (Error::SizeLimitExceeded { .. } | Error::ResourceLimit { .. })) => {
+                                return Err(e);
+                            }
+                            // Not parseable after all: yield the entry as-is below
+                            Err(_) => *total_bytes = bytes_before,
+                        }
+                    }
+                }
+            }
+
+            results.push(NestedEntry {
+                source_id: source_id.to_string(),
+                mapping_id,
+                depth,
+                entry,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Extract all entries to the specified directory
+    ///
+    /// For a single-file compressed payload, the decoded content is
+    /// written to a single file named `content` inside `target_dir`, since
+    /// no original filename survives compression.
+    pub async fn extract_to(&mut self, target_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        match self {
+            Self::Zip(handler) => handler.extract_to(target_dir).await,
+            Self::Tar(handler) => handler.extract_to(target_dir).await,
+            Self::Compressed { data, .. } => {
+                let target_dir = target_dir.as_ref();
+                tokio::fs::create_dir_all(target_dir).await?;
+                let output_path = target_dir.join("content");
+                tokio::fs::write(&output_path, &data).await?;
+                Ok(vec![output_path])
+            }
+        }
+    }
+
+    /// Check whether a file exists in the archive
+    ///
+    /// Returns a `Result` rather than a plain `bool` (unlike
+    /// [`ZipArchiveHandler::contains_file`]) since checking a TAR archive
+    /// requires walking and can fail partway through.
+    pub fn contains_file(&mut self, name: &str) -> Result<bool> {
+        match self {
+            Self::Zip(handler) => Ok(handler.contains_file(name)),
+            Self::Tar(handler) => Ok(handler
+                .list_entries()?
+                .iter()
+                .any(|entry| entry.path.to_string_lossy() == name)),
+            Self::Compressed { .. } => Ok(name.is_empty()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::handler::zip_handler::ZipArchiveBuilder;
+
+    #[test]
+    fn test_from_memory_detects_zip() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("file.txt", b"hello")
+            .unwrap();
+        let cursor = builder.finish().unwrap();
+
+        let mut handler = AnyArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        assert_eq!(handler.archive_type(), ArchiveType::Zip);
+        assert!(handler.as_zip().is_some());
+
+        let entries = handler.list_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "file.txt");
+
+        assert_eq!(handler.read_file("file.txt").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_from_memory_detects_tar() {
+        let mut builder = crate::handler::tar_handler::TarArchiveBuilder::new(
+            Cursor::new(Vec::new()),
+            ArchiveType::Tar,
+        )
+        .unwrap();
+        builder
+            .append_data("file.txt", 5, std::io::Cursor::new(b"hello".to_vec()))
+            .unwrap();
+        let cursor = builder.finish().unwrap();
+
+        let mut handler = AnyArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        assert_eq!(handler.archive_type(), ArchiveType::Tar);
+        assert!(handler.as_tar().is_some());
+
+        let entries = handler.list_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "file.txt");
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_dispatches_to_zip_backend() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("file.txt", b"content")
+            .unwrap();
+        let cursor = builder.finish().unwrap();
+
+        let mut handler = AnyArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let extracted = handler.extract_to(temp_dir.path()).await.unwrap();
+        assert_eq!(extracted, vec![temp_dir.path().join("file.txt")]);
+    }
+
+    #[test]
+    fn test_read_entries_zip() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("file.txt", b"hello")
+            .unwrap();
+        let cursor = builder.finish().unwrap();
+
+        let mut handler = AnyArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let entries = handler.read_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "file.txt");
+        assert_eq!(entries[0].content, b"hello");
+        assert!(!entries[0].is_dir);
+    }
+
+    #[test]
+    fn test_read_entries_tar() {
+        let mut builder = crate::handler::tar_handler::TarArchiveBuilder::new(
+            Cursor::new(Vec::new()),
+            ArchiveType::Tar,
+        )
+        .unwrap();
+        builder
+            .append_data("file.txt", 5, std::io::Cursor::new(b"hello".to_vec()))
+            .unwrap();
+        let cursor = builder.finish().unwrap();
+
+        let mut handler = AnyArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let entries = handler.read_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "file.txt");
+        assert_eq!(entries[0].content, b"hello");
+    }
+
+    #[test]
+    fn test_from_compressed_gzip_single_entry() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"plain compressed content").unwrap();
+        let gz_data = encoder.finish().unwrap();
+
+        let mut handler = AnyArchiveHandler::from_compressed(gz_data, ArchiveType::Gz).unwrap();
+        assert_eq!(handler.archive_type(), ArchiveType::Gz);
+        assert!(handler.as_zip().is_none());
+        assert!(handler.as_tar().is_none());
+
+        let entries = handler.read_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].content, b"plain compressed content");
+        assert!(handler.contains_file("").unwrap());
+        assert!(!handler.contains_file("file.txt").unwrap());
+        assert_eq!(handler.read_file("").unwrap(), b"plain compressed content");
+    }
+
+    fn build_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        for (name, content) in files {
+            builder.add_file_from_memory(name, content).unwrap();
+        }
+        builder.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_read_entries_recursive_descends_into_nested_zip() {
+        let inner_zip = build_zip(&[("file.txt", b"hello")]);
+        let outer_zip = build_zip(&[("inner.zip", &inner_zip)]);
+
+        let mut handler = AnyArchiveHandler::from_memory(outer_zip).unwrap();
+        let entries = handler
+            .read_entries_recursive("source-1", &DescentLimits::default())
+            .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].source_id, "source-1");
+        assert_eq!(entries[0].mapping_id, "zip!inner.zip!file.txt");
+        assert_eq!(entries[0].depth, 1);
+        assert_eq!(entries[0].entry.content, b"hello");
+    }
+
+    #[test]
+    fn test_read_entries_recursive_respects_max_depth() {
+        let inner_zip = build_zip(&[("file.txt", b"hello")]);
+        let outer_zip = build_zip(&[("inner.zip", &inner_zip)]);
+
+        let mut handler = AnyArchiveHandler::from_memory(outer_zip).unwrap();
+        let limits = DescentLimits {
+            max_depth: 0,
+            ..DescentLimits::default()
+        };
+        let error = handler.read_entries_recursive("source-1", &limits).unwrap_err();
+        assert!(matches!(error, Error::ResourceLimit { .. }));
+    }
+
+    #[test]
+    fn test_read_entries_recursive_respects_max_total_bytes() {
+        let outer_zip = build_zip(&[("file.txt", b"hello world")]);
+
+        let mut handler = AnyArchiveHandler::from_memory(outer_zip).unwrap();
+        let limits = DescentLimits {
+            max_total_bytes: 4,
+            ..DescentLimits::default()
+        };
+        let error = handler.read_entries_recursive("source-1", &limits).unwrap_err();
+        assert!(matches!(error, Error::SizeLimitExceeded { .. }));
+    }
+
+    #[test]
+    fn test_read_entries_recursive_flat_archive_has_no_bang_in_mapping_id() {
+        let zip = build_zip(&[("file.txt", b"hello")]);
+
+        let mut handler = AnyArchiveHandler::from_memory(zip).unwrap();
+        let entries = handler
+            .read_entries_recursive("source-1", &DescentLimits::default())
+            .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].mapping_id, "zip!file.txt");
+        assert_eq!(entries[0].depth, 0);
+    }
+
+    #[tokio::test]
+    async fn test_from_compressed_extract_to_writes_single_file() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"data").unwrap();
+        let gz_data = encoder.finish().unwrap();
+
+        let mut handler = AnyArchiveHandler::from_compressed(gz_data, ArchiveType::Gz).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let extracted = handler.extract_to(temp_dir.path()).await.unwrap();
+        assert_eq!(extracted, vec![temp_dir.path().join("content")]);
+        assert_eq!(
+            tokio::fs::read(temp_dir.path().join("content")).await.unwrap(),
+            b"data"
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Async streaming TAR archive handler implementation
+//!
+//! This module mirrors [`TarArchiveHandler`](crate::handler::TarArchiveHandler),
+//! but reads from an [`AsyncRead`] source and copies each entry's body straight
+//! to its output file in bounded chunks via `tokio::io::copy`, so extracting a
+//! large member keeps peak memory constant instead of buffering it in full first.
+
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWriteExt, ReadBuf};
+use tokio_stream::{Stream, StreamExt};
+use tokio_tar::{Archive, EntryType};
+
+use crate::{ArchiveType, Error, Result};
+
+/// Specialized handler for streaming TAR archive extraction over an [`AsyncRead`]
+///
+/// Unlike [`TarArchiveHandler`](crate::handler::TarArchiveHandler), which reads
+/// each regular-file entry fully into memory before writing it out, this handler
+/// copies entry bodies directly to their output files, keeping peak memory
+/// constant regardless of entry size. Compressed TAR variants aren't handled
+/// here directly: wrap `R` in an async decompressor before constructing this
+/// handler, then pass [`ArchiveType::Tar`] since the stream has already been
+/// decompressed.
+pub struct AsyncTarArchiveHandler<R: AsyncRead + Unpin> {
+    archive: Archive<R>,
+    archive_type: ArchiveType,
+}
+
+impl<R: AsyncRead + Unpin + Send + 'static> AsyncTarArchiveHandler<R> {
+    /// Create a new async TAR handler from an `AsyncRead` source
+    pub fn new(reader: R, archive_type: ArchiveType) -> Result<Self> {
+        if !archive_type.is_tar_variant() {
+            return Err(Error::unsupported_format(format!(
+                "Expected TAR variant, got: {}",
+                archive_type
+            )));
+        }
+
+        Ok(Self {
+            archive: Archive::new(reader),
+            archive_type,
+        })
+    }
+
+    /// Get the archive type
+    pub fn archive_type(&self) -> ArchiveType {
+        self.archive_type
+    }
+
+    /// Lazily stream this archive's entries, one at a time, without
+    /// buffering any entry's body
+    ///
+    /// This is the streaming core [`Self::extract_to`] is built on: each
+    /// yielded [`StreamEntry`] exposes its metadata immediately but only
+    /// reads its body (via [`AsyncRead`]) on demand, so a caller can
+    /// inspect, filter, or re-emit entries from a multi-gigabyte archive
+    /// without ever materializing the whole thing, on disk or in memory.
+    ///
+    /// Entries must be read in archive order: a TAR stream has no index
+    /// to seek back into, so once an entry's body has been advanced past
+    /// (or skipped by polling the next entry), it can't be revisited.
+    /// A malformed header is reported as [`Error::Corrupted`]; any other
+    /// I/O failure reading from `R` is reported as [`Error::Io`].
+    pub fn entries(&mut self) -> Result<impl Stream<Item = Result<StreamEntry<'_, R>>> + '_> {
+        let entries = self
+            .archive
+            .entries()
+            .map_err(|e| Error::corrupted(format!("Invalid TAR stream: {e}")))?;
+        Ok(entries.map(|entry| {
+            let entry = entry.map_err(|e| match e.kind() {
+                // Header parse failures surface as `Other`/`InvalidData`;
+                // any other kind came from reading `R` itself
+                std::io::ErrorKind::Other | std::io::ErrorKind::InvalidData => {
+                    Error::corrupted(format!("Invalid TAR entry: {e}"))
+                }
+                _ => Error::Io(e),
+            })?;
+            let path = entry
+                .path()
+                .map_err(|e| Error::corrupted(format!("Invalid entry path: {e}")))?
+                .into_owned();
+            let size = entry
+                .header()
+                .size()
+                .map_err(|e| Error::corrupted(format!("Invalid entry size: {e}")))?;
+            let entry_type = entry.header().entry_type();
+            Ok(StreamEntry {
+                path,
+                size,
+                entry_type,
+                inner: entry,
+            })
+        }))
+    }
+
+    /// Extract all entries to the specified directory
+    ///
+    /// Each regular file's body is streamed straight from the archive reader to
+    /// its output file via `tokio::io::copy`, never buffering the whole entry.
+    /// This is a convenience wrapper over [`Self::entries`] for callers that
+    /// want everything materialized on disk rather than inspecting entries
+    /// as they stream past.
+    ///
+    /// Entry paths and symlink targets are sanitized against Zip Slip the
+    /// same way as [`TarArchiveHandler::extract_to`](crate::handler::TarArchiveHandler::extract_to):
+    /// absolute paths and `..` components are rejected outright, and the
+    /// resolved path is checked, after canonicalization, to still fall
+    /// under `target_dir`. Entries that fail either check are reported as
+    /// [`Error::UnsafeEntry`]/[`Error::UnsafePath`] instead of being written.
+    pub async fn extract_to(mut self, target_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        let target_dir = target_dir.as_ref();
+        tokio::fs::create_dir_all(target_dir).await?;
+        let target_dir_canonical = tokio::fs::canonicalize(target_dir).await?;
+
+        let mut extracted_files = Vec::new();
+        let mut entries = self.archive.entries()?;
+
+        while let Some(entry) = entries.next().await {
+            let mut entry = entry?;
+            let path = entry.path()?.to_path_buf();
+            let target_path = sanitize_entry_path(target_dir, &target_dir_canonical, &path).await?;
+
+            match entry.header().entry_type() {
+                EntryType::Regular => {
+                    let mut output_file = tokio::fs::File::create(&target_path).await?;
+                    tokio::io::copy(&mut entry, &mut output_file).await?;
+                    extracted_files.push(target_path);
+                }
+                EntryType::Directory => {
+                    tokio::fs::create_dir_all(&target_path).await?;
+                }
+                EntryType::Symlink => {
+                    if let Ok(Some(link_target)) = entry.link_name() {
+                        resolve_symlink_target(target_dir, &target_path, &link_target)?;
+                        #[cfg(unix)]
+                        {
+                            tokio::fs::symlink(&link_target, &target_path).await?;
+                        }
+                        #[cfg(windows)]
+                        {
+                            if target_path.is_dir() {
+                                tokio::fs::symlink_dir(&link_target, &target_path).await?;
+                            } else {
+                                tokio::fs::symlink_file(&link_target, &target_path).await?;
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    // Other entry types (hard links, devices, etc.) are skipped.
+                }
+            }
+        }
+
+        Ok(extracted_files)
+    }
+}
+
+/// Resolve a TAR entry's path against `target_dir`, guarding against Zip
+/// Slip
+///
+/// Mirrors the non-streaming `TarArchiveHandler`'s identical sanitizer in
+/// `tar_handler.rs`.
+async fn sanitize_entry_path(
+    target_dir: &Path,
+    target_dir_canonical: &Path,
+    entry_path: &Path,
+) -> Result<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in entry_path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::unsafe_entry(entry_path.to_string_lossy()));
+            }
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(Error::unsafe_entry(entry_path.to_string_lossy()));
+    }
+
+    let resolved = target_dir.join(&relative);
+
+    if let Some(parent) = resolved.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+        let canonical_parent = tokio::fs::canonicalize(parent).await?;
+        let canonical_resolved = match resolved.file_name() {
+            Some(file_name) => canonical_parent.join(file_name),
+            None => canonical_parent,
+        };
+
+        if !canonical_resolved.starts_with(target_dir_canonical) {
+            return Err(Error::unsafe_path(
+                entry_path.to_string_lossy(),
+                canonical_resolved,
+            ));
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Verify that a symlink entry's target, once resolved relative to its own
+/// location under `target_dir`, still falls inside `target_dir`
+///
+/// `target` isn't required to exist yet, so this walks its components
+/// lexically against the symlink's position rather than canonicalizing.
+/// Absolute targets are rejected outright since they point outside
+/// `target_dir` by construction.
+fn resolve_symlink_target(target_dir: &Path, link_path: &Path, target: &Path) -> Result<()> {
+    if target.is_absolute() {
+        return Err(Error::unsafe_path(
+            target.to_string_lossy(),
+            target_dir.join(target),
+        ));
+    }
+
+    let link_parent = link_path.parent().unwrap_or(target_dir);
+    let mut components: Vec<&std::ffi::OsStr> = link_parent
+        .strip_prefix(target_dir)
+        .unwrap_or(link_parent)
+        .iter()
+        .collect();
+
+    for component in target.components() {
+        match component {
+            Component::Normal(part) => components.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if components.pop().is_none() {
+                    return Err(Error::unsafe_path(
+                        target.to_string_lossy(),
+                        target_dir.join(target),
+                    ));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::unsafe_path(
+                    target.to_string_lossy(),
+                    target_dir.join(target),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A single TAR entry being streamed directly from the archive reader
+///
+/// Carries metadata (path, size, entry type) read up front from the TAR
+/// header, while implementing [`AsyncRead`] by delegating straight to the
+/// underlying [`tokio_tar::Entry`] so the body is only read as the caller
+/// polls for it. Produced by [`AsyncTarArchiveHandler::entries`].
+pub struct StreamEntry<'a, R: AsyncRead + Unpin> {
+    path: PathBuf,
+    size: u64,
+    entry_type: EntryType,
+    inner: tokio_tar::Entry<'a, R>,
+}
+
+impl<'a, R: AsyncRead + Unpin> StreamEntry<'a, R> {
+    /// Path of the entry within the archive
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Uncompressed size in bytes, as recorded in the TAR header
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The entry's TAR entry type (regular file, directory, symlink, ...)
+    pub fn entry_type(&self) -> EntryType {
+        self.entry_type
+    }
+
+    /// Whether this entry is a directory
+    pub fn is_dir(&self) -> bool {
+        self.entry_type.is_dir()
+    }
+}
+
+impl<'a, R: AsyncRead + Unpin> AsyncRead for StreamEntry<'a, R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner).poll_read(cx, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_async_handler_creation() {
+        let data: Vec<u8> = Vec::new();
+        let handler = AsyncTarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar);
+        assert!(handler.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_async_handler_invalid_type() {
+        let data: Vec<u8> = Vec::new();
+        let handler = AsyncTarArchiveHandler::new(Cursor::new(data), ArchiveType::Zip);
+        assert!(handler.is_err());
+    }
+
+    fn build_tar(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = crate::handler::tar_handler::TarArchiveBuilder::new(
+            Cursor::new(Vec::new()),
+            ArchiveType::Tar,
+        )
+        .unwrap();
+        for (name, content) in files {
+            builder
+                .append_data(name, content.len() as u64, Cursor::new(content.to_vec()))
+                .unwrap();
+        }
+        builder.finish().unwrap().into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_entries_streams_metadata_and_body_lazily() {
+        use tokio::io::AsyncReadExt;
+
+        let tar_data = build_tar(&[("file.txt", b"hello streaming world")]);
+        let mut handler = AsyncTarArchiveHandler::new(Cursor::new(tar_data), ArchiveType::Tar).unwrap();
+
+        let mut entries = handler.entries().unwrap();
+        let mut entry = entries.next().await.unwrap().unwrap();
+        assert_eq!(entry.path(), Path::new("file.txt"));
+        assert_eq!(entry.size(), 21);
+        assert_eq!(entry.entry_type(), EntryType::Regular);
+        assert!(!entry.is_dir());
+
+        let mut body = Vec::new();
+        entry.read_to_end(&mut body).await.unwrap();
+        assert_eq!(body, b"hello streaming world");
+
+        assert!(entries.next().await.is_none());
+    }
+
+    fn build_tar_with_symlink(name: &str, target: &str) -> Vec<u8> {
+        let mut builder = crate::handler::tar_handler::TarArchiveBuilder::new(
+            Cursor::new(Vec::new()),
+            ArchiveType::Tar,
+        )
+        .unwrap();
+        builder.append_long_path(name, target).unwrap();
+        builder.finish().unwrap().into_inner()
+    }
+
+    /// Build a single-entry TAR whose header name is written verbatim, since
+    /// `tar::Builder::append_data` refuses names with `..` components
+    fn tar_with_raw_entry_name(name: &[u8], content: &[u8]) -> Vec<u8> {
+        let mut header = tar::Header::new_gnu();
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append(&header, content).unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_rejects_parent_dir_traversal() {
+        let tar_data = tar_with_raw_entry_name(b"../../etc/passwd", b"evil");
+        let handler = AsyncTarArchiveHandler::new(Cursor::new(tar_data), ArchiveType::Tar).unwrap();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let result = handler.extract_to(temp_dir.path()).await;
+
+        assert!(matches!(result, Err(Error::UnsafeEntry { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_archive_handler_open_async_streams_entries() {
+        use tokio::io::AsyncReadExt;
+
+        let tar_data = build_tar(&[("file.txt", b"hello from open_async")]);
+        let mut handler =
+            crate::handler::ArchiveHandler::open_async(Cursor::new(tar_data), ArchiveType::Tar)
+                .unwrap();
+
+        let mut entries = handler.entries().unwrap();
+        let mut entry = entries.next().await.unwrap().unwrap();
+        assert_eq!(entry.path(), Path::new("file.txt"));
+
+        let mut body = Vec::new();
+        entry.read_to_end(&mut body).await.unwrap();
+        assert_eq!(body, b"hello from open_async");
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_rejects_escaping_symlink_target() {
+        let tar_data = build_tar_with_symlink("link.txt", "../../../etc/passwd");
+        let handler = AsyncTarArchiveHandler::new(Cursor::new(tar_data), ArchiveType::Tar).unwrap();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let result = handler.extract_to(temp_dir.path()).await;
+
+        assert!(matches!(result, Err(Error::UnsafePath { .. })));
+        assert!(!temp_dir.path().join("link.txt").exists());
+    }
+}
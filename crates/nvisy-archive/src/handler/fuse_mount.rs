@@ -0,0 +1,277 @@
+//! Read-only FUSE mount of an archive's entries
+//!
+//! Gated behind the `fuse` feature. Builds an in-memory inode tree from
+//! [`AnyArchiveHandler::list_entries`] and serves `lookup`/`getattr`/
+//! `readdir`/`read` against it, reading each file's body from the archive
+//! on demand (via [`AnyArchiveHandler::read_file`]) rather than from a
+//! pre-extracted temp directory. This mirrors the pxar FUSE accessor
+//! pattern, letting large archives be browsed through the normal
+//! filesystem without first paying to unpack them.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::time::Duration;
+
+use fuser::{
+    FileAttr, FileType, Filesystem, MountOption, ReplyAttr, ReplyData, ReplyDirectory,
+    ReplyEntry, Request,
+};
+
+use super::any_handler::AnyArchiveHandler;
+use crate::{Error, Result};
+
+/// How long the kernel may cache attribute and entry lookups before
+/// re-asking the filesystem
+const ATTR_TTL: Duration = Duration::from_secs(1);
+
+/// Inode of the mount's root directory
+const ROOT_INODE: u64 = 1;
+
+/// One node (file or directory) in the mounted archive's inode tree
+struct Node {
+    name: String,
+    is_dir: bool,
+    size: u64,
+    /// Archive-relative path used to fetch this entry's content on demand;
+    /// unused for directories
+    archive_path: String,
+    parent: u64,
+    children: Vec<u64>,
+}
+
+/// Read-only [`Filesystem`] implementation backed by an archive's entries
+struct ArchiveFuse {
+    handler: AnyArchiveHandler,
+    nodes: HashMap<u64, Node>,
+}
+
+impl ArchiveFuse {
+    /// Build the inode tree from the handler's entry listing
+    fn new(mut handler: AnyArchiveHandler) -> Result<Self> {
+        let entries = handler.list_entries()?;
+
+        let mut nodes = HashMap::new();
+        nodes.insert(
+            ROOT_INODE,
+            Node {
+                name: String::new(),
+                is_dir: true,
+                size: 0,
+                archive_path: String::new(),
+                parent: ROOT_INODE,
+                children: Vec::new(),
+            },
+        );
+
+        let mut next_inode = ROOT_INODE + 1;
+        let mut path_to_inode: HashMap<String, u64> = HashMap::new();
+
+        for entry in &entries {
+            let components: Vec<&str> = entry.name.split('/').filter(|c| !c.is_empty()).collect();
+            let mut parent_path = String::new();
+            let mut parent_inode = ROOT_INODE;
+
+            for (index, component) in components.iter().enumerate() {
+                let is_last = index == components.len() - 1;
+                let full_path = if parent_path.is_empty() {
+                    component.to_string()
+                } else {
+                    format!("{parent_path}/{component}")
+                };
+
+                let inode = if let Some(inode) = path_to_inode.get(&full_path) {
+                    *inode
+                } else {
+                    let inode = next_inode;
+                    next_inode += 1;
+
+                    nodes.insert(
+                        inode,
+                        Node {
+                            name: component.to_string(),
+                            is_dir: !is_last || entry.is_dir,
+                            size: if is_last { entry.size } else { 0 },
+                            archive_path: if is_last {
+                                full_path.clone()
+                            } else {
+                                String::new()
+                            },
+                            parent: parent_inode,
+                            children: Vec::new(),
+                        },
+                    );
+                    nodes
+                        .get_mut(&parent_inode)
+                        .expect("parent node was created before its children")
+                        .children
+                        .push(inode);
+                    path_to_inode.insert(full_path.clone(), inode);
+
+                    inode
+                };
+
+                parent_path = full_path;
+                parent_inode = inode;
+            }
+        }
+
+        Ok(Self { handler, nodes })
+    }
+
+    /// Build the kernel-facing [`FileAttr`] for a node
+    fn attr(inode: u64, node: &Node) -> FileAttr {
+        let now = std::time::SystemTime::now();
+
+        FileAttr {
+            ino: inode,
+            size: node.size,
+            blocks: node.size.div_ceil(512),
+            atime: now,
+            mtime: now,
+            ctime: now,
+            crtime: now,
+            kind: if node.is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            perm: if node.is_dir { 0o555 } else { 0o444 },
+            nlink: 1,
+            uid: 0,
+            gid: 0,
+            rdev: 0,
+            blksize: 512,
+            flags: 0,
+        }
+    }
+
+    /// Find the child of `parent` named `name`, if any
+    fn lookup_child(&self, parent: u64, name: &str) -> Option<u64> {
+        let parent_node = self.nodes.get(&parent)?;
+        parent_node
+            .children
+            .iter()
+            .copied()
+            .find(|child| self.nodes.get(child).is_some_and(|node| node.name == name))
+    }
+}
+
+impl Filesystem for ArchiveFuse {
+    fn lookup(&mut self, _req: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let Some(name) = name.to_str() else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+
+        match self.lookup_child(parent, name) {
+            Some(inode) => {
+                let attr = Self::attr(inode, &self.nodes[&inode]);
+                reply.entry(&ATTR_TTL, &attr, 0);
+            }
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request<'_>, inode: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.nodes.get(&inode) {
+            Some(node) => reply.attr(&ATTR_TTL, &Self::attr(inode, node)),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn readdir(
+        &mut self,
+        _req: &Request<'_>,
+        inode: u64,
+        _fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let Some(node) = self.nodes.get(&inode) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        if !node.is_dir {
+            reply.error(libc::ENOTDIR);
+            return;
+        }
+
+        let mut standard_entries = vec![(inode, FileType::Directory, ".".to_string())];
+        standard_entries.push((node.parent, FileType::Directory, "..".to_string()));
+        for &child in &node.children {
+            if let Some(child_node) = self.nodes.get(&child) {
+                let kind = if child_node.is_dir {
+                    FileType::Directory
+                } else {
+                    FileType::RegularFile
+                };
+                standard_entries.push((child, kind, child_node.name.clone()));
+            }
+        }
+
+        for (index, (ino, kind, name)) in standard_entries.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(ino, (index + 1) as i64, kind, name) {
+                break;
+            }
+        }
+
+        reply.ok();
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request<'_>,
+        inode: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        let Some(node) = self.nodes.get(&inode) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        if node.is_dir {
+            reply.error(libc::EISDIR);
+            return;
+        }
+
+        // Each read decodes the whole entry and slices out the requested
+        // range; there's no partial-decode path for archive formats, so
+        // repeated small reads of the same large file re-decode it each time.
+        match self.handler.read_file(&node.archive_path) {
+            Ok(content) => {
+                let start = (offset as usize).min(content.len());
+                let end = (start + size as usize).min(content.len());
+                reply.data(&content[start..end]);
+            }
+            Err(_) => reply.error(libc::EIO),
+        }
+    }
+}
+
+/// Guard returned by [`mount`], unmounting the archive's FUSE filesystem on drop
+pub struct ArchiveMountGuard {
+    _session: fuser::BackgroundSession,
+}
+
+/// Mount `handler`'s entries as a read-only filesystem at `mountpoint`
+///
+/// Returns a guard that unmounts the filesystem when dropped (or when the
+/// caller explicitly drops/forgets it).
+pub fn mount(handler: AnyArchiveHandler, mountpoint: impl AsRef<Path>) -> Result<ArchiveMountGuard> {
+    let filesystem = ArchiveFuse::new(handler)?;
+
+    let options = [
+        MountOption::RO,
+        MountOption::FSName("nvisy-archive".to_string()),
+    ];
+
+    let session = fuser::spawn_mount2(filesystem, mountpoint.as_ref(), &options)
+        .map_err(|e| Error::other(format!("Failed to mount archive: {}", e)))?;
+
+    Ok(ArchiveMountGuard { _session: session })
+}
@@ -4,18 +4,42 @@
 //! temporary directories containing extracted archive contents and
 //! repacking them back into archives.
 
+pub mod any_handler;
+#[cfg(feature = "async")]
+pub mod async_tar_handler;
+#[cfg(feature = "fuse")]
+pub mod fuse_mount;
 pub mod tar_handler;
 pub mod zip_handler;
 
 use std::fs;
+#[cfg(feature = "jobs")]
+use std::future::Future;
 use std::path::{Path, PathBuf};
+#[cfg(feature = "jobs")]
+use std::sync::{Arc, Mutex};
 
 // Re-exports for convenience
-pub use tar_handler::{TarArchiveBuilder, TarArchiveHandler, TarEntryInfo};
+pub use any_handler::{AnyArchiveHandler, DescentLimits, Entry, EntryInfo, NestedEntry};
+#[cfg(feature = "async")]
+pub use async_tar_handler::{AsyncTarArchiveHandler, StreamEntry};
+#[cfg(feature = "fuse")]
+pub use fuse_mount::ArchiveMountGuard;
+#[cfg(feature = "jobs")]
+use nvisy_error::Component;
+#[cfg(feature = "jobs")]
+use nvisy_error::status::{ComponentStatus, HealthStatus};
+pub use tar_handler::{
+    OverwritePolicy, TarArchiveBuilder, TarArchiveHandler, TarEntryInfo, TarExtractOptions,
+};
 use tempfile::TempDir;
-pub use zip_handler::{ZipArchiveBuilder, ZipArchiveHandler, ZipEntryInfo};
+pub use zip_handler::{
+    ZipArchiveBuilder, ZipArchiveHandler, ZipEntryInfo, ZipExtractOptions, ZipStreamHandler,
+};
 
 use crate::{ArchiveType, Error, Result};
+#[cfg(feature = "jobs")]
+use crate::job::{CancellationToken, Job, JobReporter};
 
 /// Handler for unpacked archive contents
 ///
@@ -32,6 +56,13 @@ pub struct ArchiveHandler {
     temp_dir: TempDir,
     /// Files found in the archive
     files: Vec<PathBuf>,
+    /// Status of the most recently run job against this handler, if any
+    ///
+    /// Shared with the [`JobReporter`] of any in-flight job (e.g. one started
+    /// by [`Self::pack_dedup_job`]) so [`Component::current_status`] reflects
+    /// its progress while it runs.
+    #[cfg(feature = "jobs")]
+    job_status: Arc<Mutex<ComponentStatus>>,
 }
 
 impl ArchiveHandler {
@@ -49,6 +80,8 @@ impl ArchiveHandler {
             original_path,
             temp_dir,
             files,
+            #[cfg(feature = "jobs")]
+            job_status: Arc::new(Mutex::new(ComponentStatus::new(HealthStatus::Online))),
         }
     }
 
@@ -150,7 +183,12 @@ impl ArchiveHandler {
                     return Err(Error::unsupported_format("ZIP support not enabled"));
                 }
             }
-            ArchiveType::Tar | ArchiveType::TarGz | ArchiveType::TarBz2 | ArchiveType::TarXz => {
+            ArchiveType::Tar
+            | ArchiveType::TarGz
+            | ArchiveType::TarBz2
+            | ArchiveType::TarXz
+            | ArchiveType::TarZst
+            | ArchiveType::TarLz4 => {
                 #[cfg(feature = "tar")]
                 {
                     let tar_handler = tar_handler::TarArchiveBuilder::for_directory(archive_type);
@@ -226,13 +264,130 @@ impl ArchiveHandler {
         tokio::fs::read(&target_path).await.map_err(Into::into)
     }
 
+    /// Repack the temporary directory into a TAR archive, storing
+    /// whole-file duplicates once as hard links instead of writing their
+    /// bytes twice
+    ///
+    /// See [`crate::dedup::pack_directory_dedup`] for how duplicates are
+    /// detected. Only works when this handler's archive type is the
+    /// uncompressed [`ArchiveType::Tar`]; any other archive type returns an
+    /// error, and [`Self::pack`] covers those targets.
+    #[cfg(feature = "dedup")]
+    pub async fn pack_dedup(&self, target_path: impl AsRef<Path>) -> Result<crate::dedup::DedupStats> {
+        let target_path = target_path.as_ref();
+
+        if let Some(parent) = target_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        crate::dedup::pack_directory_dedup(self.temp_path(), target_path, self.archive_type)
+    }
+
+    /// Like [`Self::pack_dedup`], but run as a cancellable background job that
+    /// reports progress as it goes
+    ///
+    /// The returned [`Job`] carries a `files_done`/`bytes_done`/`current_entry`
+    /// [`JobEvent`](crate::job::JobEvent) for every packed entry; cancelling
+    /// `cancel` causes the job to delete its partial output and finish with
+    /// an error at the next checkpoint. While the job runs (and after it
+    /// finishes), [`Self::current_status`](Component::current_status) and
+    /// [`Self::cached_status`](Component::cached_status) reflect its latest
+    /// reported status.
+    #[cfg(all(feature = "jobs", feature = "dedup"))]
+    pub fn pack_dedup_job(
+        &self,
+        target_path: impl AsRef<Path>,
+        cancel: CancellationToken,
+    ) -> Job<crate::dedup::DedupStats> {
+        let source_dir = self.temp_path().to_path_buf();
+        let target_path = target_path.as_ref().to_path_buf();
+        let archive_type = self.archive_type;
+        let status = self.job_status.clone();
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let task = tokio::task::spawn_blocking(move || {
+            if let Some(parent) = target_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            let mut reporter = JobReporter::new(tx, cancel, status);
+            crate::dedup::pack_directory_dedup_with_job(
+                &source_dir,
+                &target_path,
+                archive_type,
+                &mut reporter,
+            )
+        });
+
+        Job::new(rx, task)
+    }
+
+    /// Mount this archive's entries as a read-only FUSE filesystem at `mountpoint`
+    ///
+    /// Entries are served directly from the original archive bytes at
+    /// [`Self::original_path`] rather than from the unpacked temp directory,
+    /// so mounting never requires extraction first. Returns a guard that
+    /// unmounts the filesystem when dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this handler has no `original_path`, the archive
+    /// can't be re-read, or the mount itself fails.
+    #[cfg(feature = "fuse")]
+    pub fn mount(&self, mountpoint: impl AsRef<Path>) -> Result<fuse_mount::ArchiveMountGuard> {
+        let original_path = self
+            .original_path
+            .as_ref()
+            .ok_or_else(|| Error::other("Cannot mount an archive with no original file path"))?;
+
+        let data = std::fs::read(original_path)?;
+        let handler = any_handler::AnyArchiveHandler::from_memory(data)?;
+
+        fuse_mount::mount(handler, mountpoint)
+    }
+
+    /// Open a TAR-variant archive for async streaming entry iteration
+    ///
+    /// Unlike [`ArchiveFile::unpack`](crate::file::ArchiveFile::unpack),
+    /// which buffers or writes the whole archive before returning, this
+    /// reads `reader` lazily: call [`AsyncTarArchiveHandler::entries`] on
+    /// the returned handler to get a [`Stream`](tokio_stream::Stream) of
+    /// [`StreamEntry`] values, each exposing its path, size, and header
+    /// metadata up front with the decompressed body read on demand via
+    /// `AsyncRead`. `reader` must already be decompressed (wrap it in an
+    /// async decompressor first for `.tar.gz`/`.tar.zst`/etc.) and
+    /// `archive_type` must be a TAR variant.
+    ///
+    /// Entries must be consumed in the order the archive stores them;
+    /// streaming formats like TAR have no index to seek back into, so
+    /// there is no way to reread or skip ahead to an earlier entry once
+    /// its body has been passed over.
+    ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub fn open_async<R>(reader: R, archive_type: ArchiveType) -> Result<AsyncTarArchiveHandler<R>>
+    where
+        R: tokio::io::AsyncRead + Unpin + Send + 'static,
+    {
+        AsyncTarArchiveHandler::new(reader, archive_type)
+    }
+
     /// Write content to a file in the archive
+    ///
+    /// `relative_path` is resolved against [`Self::temp_path`] the same way
+    /// an extracted archive entry name is: absolute paths and `..`
+    /// components are rejected outright, so a caller can't be tricked into
+    /// writing outside the temp directory by passing through an
+    /// attacker-controlled path.
     pub async fn write_file(
         &mut self,
         relative_path: impl AsRef<Path>,
         content: &[u8],
     ) -> Result<()> {
-        let target_path = self.temp_path().join(relative_path.as_ref());
+        let relative_path = relative_path.as_ref().to_string_lossy();
+        let target_path =
+            crate::file::sanitize_entry_path(self.temp_path(), relative_path.as_ref())?;
 
         // Create parent directories if they don't exist
         if let Some(parent) = target_path.parent() {
@@ -251,6 +406,26 @@ impl ArchiveHandler {
     }
 }
 
+/// Reports the status of the most recently run job against this handler
+///
+/// Both methods read the same cheaply-shared snapshot updated by running
+/// jobs (e.g. [`ArchiveHandler::pack_dedup_job`]): there's no separate
+/// expensive probe for `current_status` to perform, so it and `cached_status`
+/// return the same value. A handler with no job run against it yet reports
+/// [`HealthStatus::Online`], matching an idle component with nothing to report.
+#[cfg(feature = "jobs")]
+impl Component for ArchiveHandler {
+    fn current_status(&self) -> impl Future<Output = ComponentStatus> {
+        let status = self.job_status.clone();
+        async move { status.lock().expect("job status mutex poisoned").clone() }
+    }
+
+    fn cached_status(&self) -> impl Future<Output = Option<ComponentStatus>> {
+        let status = self.job_status.clone();
+        async move { Some(status.lock().expect("job status mutex poisoned").clone()) }
+    }
+}
+
 /// Iterator implementation for ArchiveHandler
 ///
 /// Iterates over all file paths in the extracted archive.
@@ -347,4 +522,77 @@ mod tests {
         let read_content = handler.read_file("test.txt").await.unwrap();
         assert_eq!(read_content, content);
     }
+
+    #[tokio::test]
+    async fn test_write_file_rejects_parent_dir_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut handler = ArchiveHandler::new(ArchiveType::Zip, None, temp_dir, vec![]);
+
+        let result = handler.write_file("../../etc/passwd", b"evil").await;
+
+        assert!(matches!(result, Err(Error::UnsafeEntry { .. })));
+    }
+
+    #[cfg(all(feature = "jobs", feature = "dedup"))]
+    #[tokio::test]
+    async fn test_pack_dedup_job_reports_progress_and_completes() {
+        use crate::job::{CancellationToken, JobEvent};
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), b"hello").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), b"world!").unwrap();
+        let files = vec![
+            temp_dir.path().join("a.txt"),
+            temp_dir.path().join("b.txt"),
+        ];
+        let handler = ArchiveHandler::new(ArchiveType::Tar, None, temp_dir, files);
+
+        let target = tempfile::NamedTempFile::new().unwrap();
+        let mut job = handler.pack_dedup_job(target.path(), CancellationToken::new());
+
+        let mut saw_progress = false;
+        let mut saw_completed = false;
+        while let Some(event) = job.events.recv().await {
+            match event {
+                JobEvent::Progress(_) => saw_progress = true,
+                JobEvent::Completed(_) => saw_completed = true,
+                other => panic!("unexpected event: {other:?}"),
+            }
+        }
+
+        let stats = job.join().await.unwrap();
+        assert_eq!(stats.total_bytes, 11);
+        assert!(saw_progress);
+        assert!(saw_completed);
+
+        let status = handler.current_status().await;
+        assert_eq!(status.health_status, nvisy_error::HealthStatus::Online);
+    }
+
+    #[cfg(all(feature = "jobs", feature = "dedup"))]
+    #[tokio::test]
+    async fn test_pack_dedup_job_cancellation_removes_partial_output() {
+        use crate::job::{CancellationToken, JobEvent};
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), b"hello").unwrap();
+        let files = vec![temp_dir.path().join("a.txt")];
+        let handler = ArchiveHandler::new(ArchiveType::Tar, None, temp_dir, files);
+
+        let target = tempfile::NamedTempFile::new().unwrap();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let mut job = handler.pack_dedup_job(target.path(), cancel);
+
+        let mut saw_cancelled = false;
+        while let Some(event) = job.events.recv().await {
+            if matches!(event, JobEvent::Cancelled(_)) {
+                saw_cancelled = true;
+            }
+        }
+
+        assert!(job.join().await.is_err());
+        assert!(saw_cancelled);
+        assert!(!target.path().exists());
+    }
 }
@@ -3,15 +3,22 @@
 //! This module provides specialized handling for TAR archives using the tar crate,
 //! including support for compressed TAR formats (tar.gz, tar.bz2, tar.xz).
 
-use std::io::{Cursor, Read, Write};
-use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
 
 use tar::{Archive, Builder, EntryType};
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 
 use crate::{ArchiveType, Error, Result};
 
+/// Minimum run length of zero bytes that gets turned into a hole rather than
+/// written out, when sparse preservation is enabled. Chosen to match the
+/// common filesystem block size, below which seeking instead of writing
+/// wouldn't actually save any disk blocks.
+const SPARSE_BLOCK_SIZE: u64 = 4096;
+
 /// Buffered writer for XZ compression using liblzma-rs
 ///
 /// This writer buffers all data and compresses it when dropped or explicitly finished.
@@ -57,6 +64,140 @@ impl<W: Write> Drop for XzBufferedWriter<W> {
     }
 }
 
+/// Builder for PAX extended header records
+///
+/// The ustar format's `name`/`linkname` fields are only 99 bytes, so long
+/// paths and symlink targets (and any extended attributes) have to be carried
+/// in a PAX extended header entry instead. Records use the canonical
+/// `"<len> <key>=<value>\n"` format, where `len` is the decimal length of the
+/// whole record *including itself* — since growing `len` by a digit can push
+/// the record past the next power of ten, the length has to be solved for
+/// iteratively rather than computed directly.
+#[derive(Debug, Default, Clone)]
+pub struct PaxBuilder {
+    records: BTreeMap<String, String>,
+}
+
+impl PaxBuilder {
+    /// Create an empty set of PAX records
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or overwrite a record
+    pub fn with_record(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.records.insert(key.into(), value.into());
+        self
+    }
+
+    /// Whether any records have been added
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Render all records into a PAX extended header body
+    pub fn into_bytes(self) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (key, value) in self.records {
+            body.extend_from_slice(&Self::encode_record(&key, &value));
+        }
+        body
+    }
+
+    /// Encode a single `"<len> <key>=<value>\n"` record
+    fn encode_record(key: &str, value: &str) -> Vec<u8> {
+        let rest_len = 3 + key.len() + value.len();
+
+        let mut len_len = 1;
+        let mut max_len = 10;
+        while rest_len + len_len >= max_len {
+            len_len += 1;
+            max_len *= 10;
+        }
+
+        let total = rest_len + len_len;
+        format!("{total} {key}={value}\n").into_bytes()
+    }
+}
+
+/// Policy consulted by [`TarArchiveHandler::extract_with`] before writing an
+/// entry whose resolved destination already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Never overwrite; leave the existing file in place and skip the entry
+    Never,
+    /// Overwrite only if the entry's mtime is newer than the existing file's
+    IfNewer,
+    /// Always overwrite
+    Always,
+}
+
+/// Options controlling how [`TarArchiveHandler::extract_with`] restores
+/// per-entry Unix metadata and handles pre-existing destination files
+///
+/// Mirrors the `set_preserve_*`/`set_unpack_xattrs` setters already exposed
+/// directly on [`TarArchiveHandler`], bundled into a single value so they can
+/// be threaded through one call instead of several, plus ownership
+/// restoration and an explicit [`OverwritePolicy`] that those setters don't
+/// cover.
+#[derive(Debug, Clone, Copy)]
+pub struct TarExtractOptions {
+    /// Restore each entry's mode bits onto the extracted file
+    pub preserve_permissions: bool,
+    /// Restore each entry's uid/gid onto the extracted file
+    pub preserve_ownerships: bool,
+    /// Restore each entry's modification time onto the extracted file
+    pub preserve_mtime: bool,
+    /// Restore extended attributes captured in PAX `SCHILY.xattr.*` records
+    pub unpack_xattrs: bool,
+    /// Policy applied when the resolved destination path already exists
+    pub overwrite: OverwritePolicy,
+}
+
+impl Default for TarExtractOptions {
+    fn default() -> Self {
+        Self {
+            preserve_permissions: false,
+            preserve_ownerships: false,
+            preserve_mtime: false,
+            unpack_xattrs: false,
+            overwrite: OverwritePolicy::Always,
+        }
+    }
+}
+
+impl TarExtractOptions {
+    /// Set whether each entry's mode bits are restored
+    pub fn with_preserve_permissions(mut self, preserve: bool) -> Self {
+        self.preserve_permissions = preserve;
+        self
+    }
+
+    /// Set whether each entry's uid/gid is restored
+    pub fn with_preserve_ownerships(mut self, preserve: bool) -> Self {
+        self.preserve_ownerships = preserve;
+        self
+    }
+
+    /// Set whether each entry's modification time is restored
+    pub fn with_preserve_mtime(mut self, preserve: bool) -> Self {
+        self.preserve_mtime = preserve;
+        self
+    }
+
+    /// Set whether extended attributes are restored
+    pub fn with_unpack_xattrs(mut self, unpack: bool) -> Self {
+        self.unpack_xattrs = unpack;
+        self
+    }
+
+    /// Set the policy applied when a destination file already exists
+    pub fn with_overwrite(mut self, overwrite: OverwritePolicy) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+}
+
 /// Specialized handler for TAR archive operations
 ///
 /// This handler provides efficient TAR-specific operations using the tar crate,
@@ -66,6 +207,13 @@ pub struct TarArchiveHandler<R: Read> {
     archive: Archive<R>,
     /// Archive type (for compression handling)
     archive_type: ArchiveType,
+    /// Whether to recreate sparse files on extraction instead of
+    /// materializing their zero runs
+    preserve_sparse: bool,
+    /// Whether to restore each entry's mode bits onto the extracted file
+    preserve_permissions: bool,
+    /// Whether to restore each entry's modification time onto the extracted file
+    preserve_mtime: bool,
 }
 
 impl<R: Read> TarArchiveHandler<R> {
@@ -81,22 +229,43 @@ impl<R: Read> TarArchiveHandler<R> {
         Ok(Self {
             archive: Archive::new(reader),
             archive_type,
+            preserve_sparse: false,
+            preserve_permissions: false,
+            preserve_mtime: false,
         })
     }
 
+    /// Set whether to recreate sparse files on extraction
+    ///
+    /// When enabled, runs of at least [`SPARSE_BLOCK_SIZE`] zero bytes in a
+    /// regular or GNU sparse entry are turned into holes (via `seek` past
+    /// them) instead of being written out, so extracting a sparse file
+    /// doesn't fully materialize it on disk.
+    pub fn set_preserve_sparse(&mut self, preserve: bool) {
+        self.preserve_sparse = preserve;
+    }
+
     /// Get the archive type
     pub fn archive_type(&self) -> ArchiveType {
         self.archive_type
     }
 
     /// Set whether to preserve permissions when extracting
+    ///
+    /// When enabled, [`Self::extract_to`] and [`Self::extract_matching`]
+    /// restore each regular entry's mode bits onto the extracted file.
     pub fn set_preserve_permissions(&mut self, preserve: bool) {
         self.archive.set_preserve_permissions(preserve);
+        self.preserve_permissions = preserve;
     }
 
     /// Set whether to preserve modification times when extracting
+    ///
+    /// When enabled, [`Self::extract_to`] and [`Self::extract_matching`]
+    /// restore each regular entry's mtime onto the extracted file.
     pub fn set_preserve_mtime(&mut self, preserve: bool) {
         self.archive.set_preserve_mtime(preserve);
+        self.preserve_mtime = preserve;
     }
 
     /// Set whether to unpack extended attributes
@@ -105,29 +274,43 @@ impl<R: Read> TarArchiveHandler<R> {
     }
 
     /// Extract all entries to the specified directory
+    ///
+    /// Every entry path is resolved against `target_dir` by rejecting
+    /// absolute paths and `..` components and then verifying, after
+    /// canonicalization, that the resolved path still falls under
+    /// `target_dir`; symlink targets are checked the same way before the
+    /// link is created. Entries that fail either check are reported as
+    /// [`Error::UnsafeEntry`]/[`Error::UnsafePath`] instead of being written.
     pub async fn extract_to(&mut self, target_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
         let target_dir = target_dir.as_ref();
         fs::create_dir_all(target_dir).await?;
+        let target_dir_canonical = fs::canonicalize(target_dir).await?;
 
         let mut extracted_files = Vec::new();
 
         for entry in self.archive.entries()? {
             let mut entry = entry?;
             let path = entry.path()?.to_path_buf();
-            let target_path = target_dir.join(&path);
-
-            // Create parent directories
-            if let Some(parent) = target_path.parent() {
-                fs::create_dir_all(parent).await?;
-            }
+            let target_path = sanitize_entry_path(target_dir, &target_dir_canonical, &path).await?;
 
             match entry.header().entry_type() {
-                EntryType::Regular => {
+                EntryType::Regular | EntryType::GNUSparse => {
+                    let mode = entry.header().mode()?;
+                    let mtime = entry.header().mtime()?;
+
                     let mut content = Vec::new();
                     entry.read_to_end(&mut content)?;
 
                     let mut file = fs::File::create(&target_path).await?;
-                    file.write_all(&content).await?;
+                    Self::write_entry_content(self.preserve_sparse, &mut file, &content).await?;
+                    drop(file);
+
+                    if self.preserve_permissions {
+                        Self::restore_permissions(&target_path, mode)?;
+                    }
+                    if self.preserve_mtime {
+                        Self::restore_mtime(&target_path, mtime)?;
+                    }
 
                     extracted_files.push(target_path);
                 }
@@ -136,6 +319,7 @@ impl<R: Read> TarArchiveHandler<R> {
                 }
                 EntryType::Symlink => {
                     if let Ok(Some(link_target)) = entry.link_name() {
+                        resolve_symlink_target(target_dir, &target_path, &link_target)?;
                         #[cfg(unix)]
                         {
                             tokio::fs::symlink(&link_target, &target_path).await?;
@@ -154,7 +338,9 @@ impl<R: Read> TarArchiveHandler<R> {
                 EntryType::Link => {
                     // Hard links - create a copy for simplicity
                     if let Ok(Some(link_target)) = entry.link_name() {
-                        let source_path = target_dir.join(link_target);
+                        let source_path =
+                            sanitize_entry_path(target_dir, &target_dir_canonical, &link_target)
+                                .await?;
                         if source_path.exists() {
                             fs::copy(&source_path, &target_path).await?;
                             extracted_files.push(target_path);
@@ -171,27 +357,428 @@ impl<R: Read> TarArchiveHandler<R> {
         Ok(extracted_files)
     }
 
+    /// Extract only entries whose archive path matches one of the given glob patterns
+    ///
+    /// Bodies of non-matching regular entries are never buffered in memory
+    /// or written to disk — the underlying archive reader reads past them
+    /// and discards the bytes before the next header is parsed — so restoring a single file or
+    /// subtree from a large archive doesn't require unpacking everything.
+    ///
+    /// Entry paths and symlink targets are sanitized the same way as in
+    /// [`Self::extract_to`].
+    pub async fn extract_matching(
+        &mut self,
+        target_dir: impl AsRef<Path>,
+        patterns: &[impl AsRef<str>],
+    ) -> Result<Vec<PathBuf>> {
+        let patterns = Self::compile_patterns(patterns)?;
+        let target_dir = target_dir.as_ref();
+        fs::create_dir_all(target_dir).await?;
+        let target_dir_canonical = fs::canonicalize(target_dir).await?;
+
+        let mut extracted_files = Vec::new();
+
+        for entry in self.archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_path_buf();
+
+            if !Self::path_matches(&patterns, &path) {
+                continue;
+            }
+
+            let target_path = sanitize_entry_path(target_dir, &target_dir_canonical, &path).await?;
+
+            match entry.header().entry_type() {
+                EntryType::Regular | EntryType::GNUSparse => {
+                    let mode = entry.header().mode()?;
+                    let mtime = entry.header().mtime()?;
+
+                    let mut content = Vec::new();
+                    entry.read_to_end(&mut content)?;
+
+                    let mut file = fs::File::create(&target_path).await?;
+                    Self::write_entry_content(self.preserve_sparse, &mut file, &content).await?;
+                    drop(file);
+
+                    if self.preserve_permissions {
+                        Self::restore_permissions(&target_path, mode)?;
+                    }
+                    if self.preserve_mtime {
+                        Self::restore_mtime(&target_path, mtime)?;
+                    }
+
+                    extracted_files.push(target_path);
+                }
+                EntryType::Directory => {
+                    fs::create_dir_all(&target_path).await?;
+                }
+                EntryType::Symlink => {
+                    if let Ok(Some(link_target)) = entry.link_name() {
+                        resolve_symlink_target(target_dir, &target_path, &link_target)?;
+                        #[cfg(unix)]
+                        {
+                            tokio::fs::symlink(&link_target, &target_path).await?;
+                        }
+                        #[cfg(windows)]
+                        {
+                            if target_path.is_dir() {
+                                tokio::fs::symlink_dir(&link_target, &target_path).await?;
+                            } else {
+                                tokio::fs::symlink_file(&link_target, &target_path).await?;
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    // Handle other entry types as needed
+                    // For now, we skip unsupported types
+                }
+            }
+        }
+
+        Ok(extracted_files)
+    }
+
+    /// Extract all entries to `target_dir`, applying `options` to decide how
+    /// each entry's Unix metadata is restored and how pre-existing files at
+    /// the resolved destination are handled
+    ///
+    /// Path resolution and the symlink-escape checks are identical to
+    /// [`Self::extract_to`]; see its documentation for details. Permission,
+    /// ownership, and extended-attribute restoration failures are reported
+    /// as [`Error::PermissionDenied`]; a malformed entry header (e.g. an
+    /// mtime that can't be parsed) is reported as [`Error::Corrupted`].
+    pub async fn extract_with(
+        &mut self,
+        target_dir: impl AsRef<Path>,
+        options: &TarExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
+        let target_dir = target_dir.as_ref();
+        fs::create_dir_all(target_dir).await?;
+        let target_dir_canonical = fs::canonicalize(target_dir).await?;
+
+        let mut extracted_files = Vec::new();
+
+        for entry in self.archive.entries()? {
+            let mut entry = entry?;
+            let path = entry
+                .path()
+                .map_err(|e| Error::corrupted(format!("Invalid entry path: {e}")))?
+                .to_path_buf();
+            let target_path = sanitize_entry_path(target_dir, &target_dir_canonical, &path).await?;
+
+            match entry.header().entry_type() {
+                EntryType::Regular | EntryType::GNUSparse => {
+                    let mode = entry
+                        .header()
+                        .mode()
+                        .map_err(|e| Error::corrupted(format!("Invalid entry mode: {e}")))?;
+                    let mtime = entry
+                        .header()
+                        .mtime()
+                        .map_err(|e| Error::corrupted(format!("Invalid entry mtime: {e}")))?;
+                    let uid = entry.header().uid().unwrap_or(0);
+                    let gid = entry.header().gid().unwrap_or(0);
+                    let pax_xattrs = if options.unpack_xattrs {
+                        read_pax_xattrs(&mut entry)?
+                    } else {
+                        Vec::new()
+                    };
+
+                    if !Self::should_write(&target_path, mtime, options.overwrite).await? {
+                        continue;
+                    }
+
+                    let mut content = Vec::new();
+                    entry
+                        .read_to_end(&mut content)
+                        .map_err(|e| Error::corrupted(format!("Failed to read entry content: {e}")))?;
+
+                    let mut file = fs::File::create(&target_path).await?;
+                    Self::write_entry_content(self.preserve_sparse, &mut file, &content).await?;
+                    drop(file);
+
+                    if options.preserve_permissions {
+                        Self::restore_permissions(&target_path, mode).map_err(|e| {
+                            Error::permission_denied(format!(
+                                "Failed to set permissions on {target_path:?}: {e}"
+                            ))
+                        })?;
+                    }
+                    if options.preserve_mtime {
+                        Self::restore_mtime(&target_path, mtime).map_err(|e| {
+                            Error::permission_denied(format!(
+                                "Failed to set mtime on {target_path:?}: {e}"
+                            ))
+                        })?;
+                    }
+                    if options.preserve_ownerships {
+                        Self::restore_ownership(&target_path, uid, gid).map_err(|e| {
+                            Error::permission_denied(format!(
+                                "Failed to set ownership on {target_path:?}: {e}"
+                            ))
+                        })?;
+                    }
+                    if options.unpack_xattrs {
+                        Self::restore_xattrs(&target_path, &pax_xattrs).map_err(|e| {
+                            Error::permission_denied(format!(
+                                "Failed to set xattrs on {target_path:?}: {e}"
+                            ))
+                        })?;
+                    }
+
+                    extracted_files.push(target_path);
+                }
+                EntryType::Directory => {
+                    fs::create_dir_all(&target_path).await?;
+                }
+                EntryType::Symlink => {
+                    if let Ok(Some(link_target)) = entry.link_name() {
+                        resolve_symlink_target(target_dir, &target_path, &link_target)?;
+
+                        if target_path.exists() && options.overwrite == OverwritePolicy::Never {
+                            continue;
+                        }
+                        if target_path.exists() {
+                            fs::remove_file(&target_path).await.ok();
+                        }
+
+                        #[cfg(unix)]
+                        {
+                            tokio::fs::symlink(&link_target, &target_path).await?;
+                        }
+                        #[cfg(windows)]
+                        {
+                            if target_path.is_dir() {
+                                tokio::fs::symlink_dir(&link_target, &target_path).await?;
+                            } else {
+                                tokio::fs::symlink_file(&link_target, &target_path).await?;
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    // Handle other entry types as needed
+                    // For now, we skip unsupported types
+                }
+            }
+        }
+
+        Ok(extracted_files)
+    }
+
+    /// Decide whether an entry should be written, given `overwrite` and any
+    /// pre-existing file already at `target_path`
+    async fn should_write(
+        target_path: &Path,
+        entry_mtime: u64,
+        overwrite: OverwritePolicy,
+    ) -> Result<bool> {
+        let existing_metadata = match fs::metadata(target_path).await {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(match overwrite {
+            OverwritePolicy::Always => true,
+            OverwritePolicy::Never => false,
+            OverwritePolicy::IfNewer => {
+                let existing_mtime = existing_metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0);
+                entry_mtime > existing_mtime
+            }
+        })
+    }
+
+    /// Restore Unix mode bits onto an extracted file
+    #[cfg(unix)]
+    fn restore_permissions(path: &Path, mode: u32) -> std::io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+    }
+
+    /// No-op on non-Unix targets, where TAR mode bits don't map onto a
+    /// native permission model
+    #[cfg(not(unix))]
+    fn restore_permissions(_path: &Path, _mode: u32) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    /// Restore an entry's modification time (a Unix timestamp) onto an
+    /// extracted file
+    fn restore_mtime(path: &Path, mtime: u64) -> std::io::Result<()> {
+        let mtime = filetime::FileTime::from_unix_time(mtime as i64, 0);
+        filetime::set_file_times(path, mtime, mtime)
+    }
+
+    /// Restore an entry's owning uid/gid onto an extracted file
+    ///
+    /// Typically requires the process to be running as root; an unprivileged
+    /// caller should expect this to fail with a permission error rather than
+    /// silently keeping the extracting user's ownership.
+    #[cfg(unix)]
+    fn restore_ownership(path: &Path, uid: u64, gid: u64) -> std::io::Result<()> {
+        std::os::unix::fs::chown(path, Some(uid as u32), Some(gid as u32))
+    }
+
+    /// No-op on non-Unix targets, which have no uid/gid ownership model
+    #[cfg(not(unix))]
+    fn restore_ownership(_path: &Path, _uid: u64, _gid: u64) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    /// Restore extended attributes collected by [`read_pax_xattrs`]
+    /// onto an extracted file
+    #[cfg(all(unix, feature = "xattr"))]
+    fn restore_xattrs(path: &Path, xattrs: &[(String, Vec<u8>)]) -> std::io::Result<()> {
+        for (name, value) in xattrs {
+            xattr::set(path, name, value)?;
+        }
+        Ok(())
+    }
+
+    /// No-op when the `xattr` feature is disabled or the target isn't Unix
+    #[cfg(not(all(unix, feature = "xattr")))]
+    fn restore_xattrs(_path: &Path, _xattrs: &[(String, Vec<u8>)]) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    /// Write entry content to `file`, creating sparse holes for zero runs
+    /// when sparse preservation is enabled
+    async fn write_entry_content(
+        preserve_sparse: bool,
+        file: &mut fs::File,
+        content: &[u8],
+    ) -> Result<()> {
+        if preserve_sparse {
+            Self::write_sparse(file, content).await
+        } else {
+            file.write_all(content).await?;
+            Ok(())
+        }
+    }
+
+    /// Write `content` to `file`, seeking over zero runs of at least
+    /// [`SPARSE_BLOCK_SIZE`] instead of writing them out
+    ///
+    /// A file ending in a hole needs an explicit `set_len` afterwards, since
+    /// seeking past the current end of the file doesn't extend it on its own.
+    async fn write_sparse(file: &mut fs::File, content: &[u8]) -> Result<()> {
+        let mut pos = 0usize;
+
+        while pos < content.len() {
+            let zero_start = pos;
+            while pos < content.len() && content[pos] == 0 {
+                pos += 1;
+            }
+            let zero_run = pos - zero_start;
+
+            if zero_run as u64 >= SPARSE_BLOCK_SIZE {
+                file.seek(SeekFrom::Current(zero_run as i64)).await?;
+            } else if zero_run > 0 {
+                file.write_all(&content[zero_start..pos]).await?;
+            }
+
+            let data_start = pos;
+            while pos < content.len() && content[pos] != 0 {
+                pos += 1;
+            }
+            if pos > data_start {
+                file.write_all(&content[data_start..pos]).await?;
+            }
+        }
+
+        file.set_len(content.len() as u64).await?;
+        Ok(())
+    }
+
+    /// List only entries whose archive path matches one of the given glob patterns
+    pub fn list_matching(&mut self, patterns: &[impl AsRef<str>]) -> Result<Vec<TarEntryInfo>> {
+        let patterns = Self::compile_patterns(patterns)?;
+
+        self.list_entries_iter()?
+            .filter(|entry| match entry {
+                Ok(entry) => Self::path_matches(&patterns, &entry.path),
+                Err(_) => true,
+            })
+            .collect()
+    }
+
+    /// Compile glob patterns, reporting the offending pattern on failure
+    fn compile_patterns(patterns: &[impl AsRef<str>]) -> Result<Vec<glob::Pattern>> {
+        patterns
+            .iter()
+            .map(|pattern| {
+                glob::Pattern::new(pattern.as_ref()).map_err(|e| {
+                    Error::other(format!(
+                        "Invalid glob pattern '{}': {}",
+                        pattern.as_ref(),
+                        e
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Check whether an archive path matches any of the compiled patterns
+    fn path_matches(patterns: &[glob::Pattern], path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+        patterns.iter().any(|pattern| pattern.matches(&path_str))
+    }
+
     /// Get entries as an iterator
     pub fn entries(&mut self) -> Result<tar::Entries<'_, R>> {
         Ok(self.archive.entries()?)
     }
 
     /// List all entries without extracting
+    ///
+    /// PAX extended headers and GNU `L`/`K` long-name/long-link entries are
+    /// resolved transparently by the underlying `tar` crate, so `path` and
+    /// `link_name` already reflect any PAX `path`/`linkpath` override or
+    /// GNU long name/link regardless of the 100-byte name limit in the
+    /// classic TAR header. `xattrs` additionally surfaces any PAX
+    /// `SCHILY.xattr.*` records attached to the entry.
     pub fn list_entries(&mut self) -> Result<Vec<TarEntryInfo>> {
         let mut entries = Vec::new();
 
         for entry in self.archive.entries()? {
-            let entry = entry?;
+            let mut entry = entry?;
+            let xattrs = read_pax_xattrs(&mut entry)?;
             let header = entry.header();
 
+            let link_name = entry
+                .link_name()
+                .map_err(|e| Error::corrupted(format!("Invalid entry linkpath: {e}")))?
+                .map(|p| p.into_owned());
             let info = TarEntryInfo {
-                path: entry.path()?.to_path_buf(),
-                size: header.size()?,
+                path: entry
+                    .path()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry path: {e}")))?
+                    .to_path_buf(),
+                size: header
+                    .size()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry size: {e}")))?,
                 entry_type: header.entry_type(),
-                mode: header.mode()?,
-                uid: header.uid()?,
-                gid: header.gid()?,
-                mtime: header.mtime()?,
+                mode: header
+                    .mode()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry mode: {e}")))?,
+                uid: header
+                    .uid()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry uid: {e}")))?,
+                gid: header
+                    .gid()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry gid: {e}")))?,
+                mtime: header
+                    .mtime()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry mtime: {e}")))?,
+                link_name,
+                xattrs,
             };
 
             entries.push(info);
@@ -199,6 +786,70 @@ impl<R: Read> TarArchiveHandler<R> {
 
         Ok(entries)
     }
+
+    /// List entries lazily, yielding each [`TarEntryInfo`] as its header is parsed
+    ///
+    /// Unlike [`list_entries`](Self::list_entries), this doesn't wait for or
+    /// buffer the whole archive before returning anything, so a caller
+    /// streaming a large or remote archive sees output for the first entry
+    /// immediately instead of after the last one has been read.
+    pub fn list_entries_iter(&mut self) -> Result<TarEntryInfoIter<'_, R>> {
+        Ok(TarEntryInfoIter {
+            entries: self.archive.entries()?,
+        })
+    }
+}
+
+/// Lazy iterator over [`TarEntryInfo`], produced by [`TarArchiveHandler::list_entries_iter`]
+pub struct TarEntryInfoIter<'a, R: Read> {
+    entries: tar::Entries<'a, R>,
+}
+
+impl<'a, R: Read> Iterator for TarEntryInfoIter<'a, R> {
+    type Item = Result<TarEntryInfo>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut entry = match self.entries.next()? {
+            Ok(entry) => entry,
+            Err(e) => return Some(Err(e.into())),
+        };
+
+        let info = (|| -> Result<TarEntryInfo> {
+            let xattrs = read_pax_xattrs(&mut entry)?;
+            let header = entry.header();
+            let link_name = entry
+                .link_name()
+                .map_err(|e| Error::corrupted(format!("Invalid entry linkpath: {e}")))?
+                .map(|p| p.into_owned());
+
+            Ok(TarEntryInfo {
+                path: entry
+                    .path()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry path: {e}")))?
+                    .to_path_buf(),
+                size: header
+                    .size()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry size: {e}")))?,
+                entry_type: header.entry_type(),
+                mode: header
+                    .mode()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry mode: {e}")))?,
+                uid: header
+                    .uid()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry uid: {e}")))?,
+                gid: header
+                    .gid()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry gid: {e}")))?,
+                mtime: header
+                    .mtime()
+                    .map_err(|e| Error::corrupted(format!("Invalid entry mtime: {e}")))?,
+                link_name,
+                xattrs,
+            })
+        })();
+
+        Some(info)
+    }
 }
 
 /// Information about a TAR entry
@@ -218,6 +869,11 @@ pub struct TarEntryInfo {
     pub gid: u64,
     /// Modification time (Unix timestamp)
     pub mtime: u64,
+    /// Link target, for [`EntryType::Symlink`] and [`EntryType::Link`] entries
+    pub link_name: Option<PathBuf>,
+    /// Extended attributes captured in PAX `SCHILY.xattr.*` records
+    /// preceding this entry, as `(name, value)` pairs
+    pub xattrs: Vec<(String, Vec<u8>)>,
 }
 
 /// Builder for creating TAR archives
@@ -299,6 +955,86 @@ impl<W: Write> TarArchiveBuilder<W> {
         Ok(())
     }
 
+    /// Add data from a reader to the archive, writing a PAX extended header
+    /// first when the path exceeds the 99-byte ustar field or extra records
+    /// (e.g. extended attributes) are supplied
+    pub fn append_data_with_pax<P: AsRef<Path>, R: Read>(
+        &mut self,
+        path: P,
+        size: u64,
+        data: R,
+        extra: Option<BTreeMap<String, String>>,
+    ) -> Result<()> {
+        let path = path.as_ref();
+
+        let mut pax = PaxBuilder::new();
+        if path.to_string_lossy().len() > 99 {
+            pax = pax.with_record("path", path.to_string_lossy().into_owned());
+        }
+        for (key, value) in extra.into_iter().flatten() {
+            pax = pax.with_record(key, value);
+        }
+
+        if !pax.is_empty() {
+            self.append_pax_extension(pax)?;
+        }
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(size);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        self.builder.append_data(&mut header, path, data)?;
+        Ok(())
+    }
+
+    /// Add a symlink entry, writing a PAX extended header first when the
+    /// path or link target exceeds the 99-byte ustar field
+    pub fn append_long_path<P: AsRef<Path>, T: AsRef<Path>>(
+        &mut self,
+        path: P,
+        target: T,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let target = target.as_ref();
+
+        let mut pax = PaxBuilder::new();
+        if path.to_string_lossy().len() > 99 {
+            pax = pax.with_record("path", path.to_string_lossy().into_owned());
+        }
+        if target.to_string_lossy().len() > 99 {
+            pax = pax.with_record("linkpath", target.to_string_lossy().into_owned());
+        }
+
+        if !pax.is_empty() {
+            self.append_pax_extension(pax)?;
+        }
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(0o777);
+        header.set_cksum();
+
+        self.builder.append_link(&mut header, path, target)?;
+        Ok(())
+    }
+
+    /// Write a PAX extended header entry ahead of the next appended entry
+    fn append_pax_extension(&mut self, pax: PaxBuilder) -> Result<()> {
+        let body = pax.into_bytes();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(EntryType::XHeader);
+        header.set_size(body.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        self.builder
+            .append_data(&mut header, "pax_header", Cursor::new(body))?;
+        Ok(())
+    }
+
     /// Finish writing the archive
     pub fn finish(self) -> Result<W> {
         Ok(self.builder.into_inner()?)
@@ -320,25 +1056,77 @@ impl TarArchiveBuilder<std::fs::File> {
     pub async fn create_from_directory(self, source_dir: &Path, target_path: &Path) -> Result<()> {
         use std::fs;
 
-        // Collect all files in the directory
-        fn collect_files(dir: &Path) -> Result<Vec<PathBuf>> {
-            let mut files = Vec::new();
-            let entries = fs::read_dir(dir)?;
+        /// A directory entry destined for the archive: either a regular file
+        /// to be read from disk, or a symlink whose target is re-emitted
+        /// directly without following it
+        enum TreeEntry {
+            File(PathBuf),
+            Symlink { path: PathBuf, target: PathBuf },
+        }
+
+        impl TreeEntry {
+            fn path(&self) -> &Path {
+                match self {
+                    Self::File(path) => path,
+                    Self::Symlink { path, .. } => path,
+                }
+            }
+        }
 
-            for entry in entries {
+        /// Append a single collected entry to `builder`, preserving symlinks
+        /// instead of following them
+        fn append_tree_entry<W: Write>(
+            builder: &mut Builder<W>,
+            source_dir: &Path,
+            entry: &TreeEntry,
+        ) -> Result<()> {
+            let relative_path = entry.path().strip_prefix(source_dir).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid file path: {}", e),
+                )
+            })?;
+
+            match entry {
+                TreeEntry::File(path) => {
+                    builder.append_path_with_name(path, relative_path)?;
+                }
+                TreeEntry::Symlink { target, .. } => {
+                    let mut header = tar::Header::new_gnu();
+                    header.set_entry_type(EntryType::Symlink);
+                    header.set_size(0);
+                    header.set_mode(0o777);
+                    header.set_cksum();
+                    builder.append_link(&mut header, relative_path, target)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        // Collect all files and symlinks in the directory
+        fn collect_files(dir: &Path) -> Result<Vec<TreeEntry>> {
+            let mut entries = Vec::new();
+            let read_dir = fs::read_dir(dir)?;
+
+            for entry in read_dir {
                 let entry = entry?;
                 let path = entry.path();
-
-                if path.is_file() {
-                    files.push(path);
-                } else if path.is_dir() {
-                    let mut sub_files = collect_files(&path)?;
-                    files.append(&mut sub_files);
+                let metadata = fs::symlink_metadata(&path)?;
+
+                if metadata.file_type().is_symlink() {
+                    let target = fs::read_link(&path)?;
+                    entries.push(TreeEntry::Symlink { path, target });
+                } else if metadata.is_file() {
+                    entries.push(TreeEntry::File(path));
+                } else if metadata.is_dir() {
+                    let mut sub_entries = collect_files(&path)?;
+                    entries.append(&mut sub_entries);
                 }
             }
 
-            files.sort();
-            Ok(files)
+            entries.sort_by(|a, b| a.path().cmp(b.path()));
+            Ok(entries)
         }
 
         let files = collect_files(source_dir)?;
@@ -348,14 +1136,8 @@ impl TarArchiveBuilder<std::fs::File> {
                 let file = std::fs::File::create(target_path)?;
                 let mut builder = Builder::new(file);
 
-                for file_path in files {
-                    let relative_path = file_path.strip_prefix(source_dir).map_err(|e| {
-                        std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("Invalid file path: {}", e),
-                        )
-                    })?;
-                    builder.append_path_with_name(&file_path, relative_path)?;
+                for entry in &files {
+                    append_tree_entry(&mut builder, source_dir, entry)?;
                 }
 
                 builder.finish()?;
@@ -368,14 +1150,8 @@ impl TarArchiveBuilder<std::fs::File> {
                 let encoder = GzEncoder::new(file, Compression::default());
                 let mut builder = Builder::new(encoder);
 
-                for file_path in files {
-                    let relative_path = file_path.strip_prefix(source_dir).map_err(|e| {
-                        std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("Invalid file path: {}", e),
-                        )
-                    })?;
-                    builder.append_path_with_name(&file_path, relative_path)?;
+                for entry in &files {
+                    append_tree_entry(&mut builder, source_dir, entry)?;
                 }
 
                 builder.finish()?;
@@ -388,14 +1164,8 @@ impl TarArchiveBuilder<std::fs::File> {
                 let encoder = BzEncoder::new(file, Compression::default());
                 let mut builder = Builder::new(encoder);
 
-                for file_path in files {
-                    let relative_path = file_path.strip_prefix(source_dir).map_err(|e| {
-                        std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("Invalid file path: {}", e),
-                        )
-                    })?;
-                    builder.append_path_with_name(&file_path, relative_path)?;
+                for entry in &files {
+                    append_tree_entry(&mut builder, source_dir, entry)?;
                 }
 
                 builder.finish()?;
@@ -407,19 +1177,54 @@ impl TarArchiveBuilder<std::fs::File> {
                 let encoder = XzEncoder::new(file, 6);
                 let mut builder = Builder::new(encoder);
 
-                for file_path in files {
-                    let relative_path = file_path.strip_prefix(source_dir).map_err(|e| {
-                        std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("Invalid file path: {}", e),
-                        )
-                    })?;
-                    builder.append_path_with_name(&file_path, relative_path)?;
+                for entry in &files {
+                    append_tree_entry(&mut builder, source_dir, entry)?;
                 }
 
                 let encoder = builder.into_inner()?;
                 encoder.finish()?;
             }
+            #[cfg(feature = "zstd")]
+            ArchiveType::TarZst => {
+                let file = std::fs::File::create(target_path)?;
+                let encoder = zstd::stream::write::Encoder::new(file, 0).map_err(|e| {
+                    Error::other(format!("Failed to initialize Zstandard encoder: {}", e))
+                })?;
+                let mut builder = Builder::new(encoder);
+
+                for entry in &files {
+                    append_tree_entry(&mut builder, source_dir, entry)?;
+                }
+
+                let encoder = builder.into_inner()?;
+                encoder
+                    .finish()
+                    .map_err(|e| Error::other(format!("Failed to compress Zstandard: {}", e)))?;
+            }
+            #[cfg(not(feature = "zstd"))]
+            ArchiveType::TarZst => {
+                return Err(Error::unsupported_format("Zstandard support not enabled"));
+            }
+            #[cfg(feature = "lz4")]
+            ArchiveType::TarLz4 => {
+                let file = std::fs::File::create(target_path)?;
+                let encoder = lz4::EncoderBuilder::new()
+                    .build(file)
+                    .map_err(|e| Error::other(format!("Failed to initialize LZ4 encoder: {}", e)))?;
+                let mut builder = Builder::new(encoder);
+
+                for entry in &files {
+                    append_tree_entry(&mut builder, source_dir, entry)?;
+                }
+
+                let encoder = builder.into_inner()?;
+                let (_file, result) = encoder.finish();
+                result.map_err(|e| Error::other(format!("Failed to compress LZ4: {}", e)))?;
+            }
+            #[cfg(not(feature = "lz4"))]
+            ArchiveType::TarLz4 => {
+                return Err(Error::unsupported_format("LZ4 support not enabled"));
+            }
             _ => {
                 return Err(Error::unsupported_format(format!(
                     "Unsupported TAR variant: {}",
@@ -447,6 +1252,9 @@ impl TarArchiveHandler<Cursor<Vec<u8>>> {
                 Ok(TarArchiveHandler {
                     archive: Archive::new(reader),
                     archive_type,
+                    preserve_sparse: false,
+                    preserve_permissions: false,
+                    preserve_mtime: false,
                 })
             }
             ArchiveType::TarGz => {
@@ -456,6 +1264,9 @@ impl TarArchiveHandler<Cursor<Vec<u8>>> {
                 Ok(TarArchiveHandler {
                     archive: Archive::new(reader),
                     archive_type,
+                    preserve_sparse: false,
+                    preserve_permissions: false,
+                    preserve_mtime: false,
                 })
             }
             ArchiveType::TarBz2 => {
@@ -465,6 +1276,9 @@ impl TarArchiveHandler<Cursor<Vec<u8>>> {
                 Ok(TarArchiveHandler {
                     archive: Archive::new(reader),
                     archive_type,
+                    preserve_sparse: false,
+                    preserve_permissions: false,
+                    preserve_mtime: false,
                 })
             }
             ArchiveType::TarXz => {
@@ -474,14 +1288,87 @@ impl TarArchiveHandler<Cursor<Vec<u8>>> {
                 Ok(TarArchiveHandler {
                     archive: Archive::new(reader),
                     archive_type,
+                    preserve_sparse: false,
+                    preserve_permissions: false,
+                    preserve_mtime: false,
+                })
+            }
+            #[cfg(feature = "zstd")]
+            ArchiveType::TarZst => {
+                let decoder = zstd::stream::read::Decoder::new(cursor).map_err(|e| {
+                    Error::other(format!("Failed to initialize Zstandard decoder: {}", e))
+                })?;
+                let reader: Box<dyn Read> = Box::new(decoder);
+                Ok(TarArchiveHandler {
+                    archive: Archive::new(reader),
+                    archive_type,
+                    preserve_sparse: false,
+                    preserve_permissions: false,
+                    preserve_mtime: false,
                 })
             }
+            #[cfg(not(feature = "zstd"))]
+            ArchiveType::TarZst => Err(Error::unsupported_format("Zstandard support not enabled")),
+            #[cfg(feature = "lz4")]
+            ArchiveType::TarLz4 => {
+                let decoder = lz4::Decoder::new(cursor)
+                    .map_err(|e| Error::other(format!("Failed to initialize LZ4 decoder: {}", e)))?;
+                let reader: Box<dyn Read> = Box::new(decoder);
+                Ok(TarArchiveHandler {
+                    archive: Archive::new(reader),
+                    archive_type,
+                    preserve_sparse: false,
+                    preserve_permissions: false,
+                    preserve_mtime: false,
+                })
+            }
+            #[cfg(not(feature = "lz4"))]
+            ArchiveType::TarLz4 => Err(Error::unsupported_format("LZ4 support not enabled")),
             _ => Err(Error::unsupported_format(format!(
                 "Not a TAR variant: {}",
                 archive_type
             ))),
         }
     }
+
+    /// Detect the TAR compression variant from the leading bytes of `data`
+    /// and construct a handler for it, mirroring how general-purpose
+    /// decompressors sniff format rather than trusting a file extension
+    pub fn from_data_autodetect(
+        data: Vec<u8>,
+    ) -> Result<(TarArchiveHandler<Box<dyn Read>>, ArchiveType)> {
+        let archive_type = Self::detect_archive_type(&data)?;
+        let handler = Self::from_compressed_data(data, archive_type)?;
+        Ok((handler, archive_type))
+    }
+
+    /// Sniff an `ArchiveType` from magic bytes
+    fn detect_archive_type(data: &[u8]) -> Result<ArchiveType> {
+        const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+        const BZIP2_MAGIC: &[u8] = &[0x42, 0x5a, 0x68];
+        const XZ_MAGIC: &[u8] = &[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00];
+        const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+        const USTAR_OFFSET: usize = 257;
+        const USTAR_MAGIC: &[u8] = b"ustar";
+
+        if data.starts_with(GZIP_MAGIC) {
+            Ok(ArchiveType::TarGz)
+        } else if data.starts_with(BZIP2_MAGIC) {
+            Ok(ArchiveType::TarBz2)
+        } else if data.starts_with(XZ_MAGIC) {
+            Ok(ArchiveType::TarXz)
+        } else if data.starts_with(ZSTD_MAGIC) {
+            Ok(ArchiveType::TarZst)
+        } else if data.len() >= USTAR_OFFSET + USTAR_MAGIC.len()
+            && &data[USTAR_OFFSET..USTAR_OFFSET + USTAR_MAGIC.len()] == USTAR_MAGIC
+        {
+            Ok(ArchiveType::Tar)
+        } else {
+            Err(Error::unsupported_format(
+                "Could not detect archive type from data",
+            ))
+        }
+    }
 }
 
 /// Convenience functions for creating compressed TAR builders
@@ -530,6 +1417,32 @@ impl<W: Write + Send + 'static> TarArchiveBuilder<W> {
                     archive_type,
                 })
             }
+            #[cfg(feature = "zstd")]
+            ArchiveType::TarZst => {
+                let encoder = zstd::stream::write::Encoder::new(writer, 0).map_err(|e| {
+                    Error::other(format!("Failed to initialize Zstandard encoder: {}", e))
+                })?;
+                let writer: Box<dyn Write + Send> = Box::new(encoder.auto_finish());
+                Ok(TarArchiveBuilder {
+                    builder: Builder::new(writer),
+                    archive_type,
+                })
+            }
+            #[cfg(not(feature = "zstd"))]
+            ArchiveType::TarZst => Err(Error::unsupported_format("Zstandard support not enabled")),
+            #[cfg(feature = "lz4")]
+            ArchiveType::TarLz4 => {
+                let encoder = lz4::EncoderBuilder::new()
+                    .build(writer)
+                    .map_err(|e| Error::other(format!("Failed to initialize LZ4 encoder: {}", e)))?;
+                let writer: Box<dyn Write + Send> = Box::new(encoder);
+                Ok(TarArchiveBuilder {
+                    builder: Builder::new(writer),
+                    archive_type,
+                })
+            }
+            #[cfg(not(feature = "lz4"))]
+            ArchiveType::TarLz4 => Err(Error::unsupported_format("LZ4 support not enabled")),
             _ => Err(Error::unsupported_format(format!(
                 "Not a TAR variant: {}",
                 archive_type
@@ -538,6 +1451,132 @@ impl<W: Write + Send + 'static> TarArchiveBuilder<W> {
     }
 }
 
+/// Resolve a TAR entry's path against `target_dir`, guarding against Zip
+/// Slip
+///
+/// Mirrors [`zip_handler`](crate::handler::zip_handler)'s sanitizer: entries
+/// with an absolute or `..` component are rejected outright, and once the
+/// resolved path's parent directories are created, they're canonicalized
+/// and checked to still fall under `target_dir_canonical` — this catches
+/// escapes through a symlink already present in `target_dir`.
+async fn sanitize_entry_path(
+    target_dir: &Path,
+    target_dir_canonical: &Path,
+    entry_path: &Path,
+) -> Result<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in entry_path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::unsafe_entry(entry_path.to_string_lossy()));
+            }
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(Error::unsafe_entry(entry_path.to_string_lossy()));
+    }
+
+    let resolved = target_dir.join(&relative);
+
+    if let Some(parent) = resolved.parent() {
+        fs::create_dir_all(parent).await?;
+        let canonical_parent = fs::canonicalize(parent).await?;
+        let canonical_resolved = match resolved.file_name() {
+            Some(file_name) => canonical_parent.join(file_name),
+            None => canonical_parent,
+        };
+
+        if !canonical_resolved.starts_with(target_dir_canonical) {
+            return Err(Error::unsafe_path(
+                entry_path.to_string_lossy(),
+                canonical_resolved,
+            ));
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Collect an entry's extended attributes from its PAX extended header
+/// records
+///
+/// TAR has no native xattr support; GNU/BSD tar smuggle them through PAX
+/// records under the `SCHILY.xattr.<name>` key, one record per attribute.
+/// A malformed or truncated extended header is reported as
+/// [`Error::Corrupted`] naming the offending key where one was parsed.
+fn read_pax_xattrs<R: Read>(entry: &mut tar::Entry<'_, R>) -> Result<Vec<(String, Vec<u8>)>> {
+    const XATTR_PREFIX: &str = "SCHILY.xattr.";
+
+    let Some(extensions) = entry
+        .pax_extensions()
+        .map_err(|e| Error::corrupted(format!("Invalid PAX extensions: {e}")))?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut xattrs = Vec::new();
+    for extension in extensions {
+        let extension =
+            extension.map_err(|e| Error::corrupted(format!("Invalid PAX extension record: {e}")))?;
+        let key = extension
+            .key()
+            .map_err(|e| Error::corrupted(format!("Invalid PAX extension key: {e}")))?;
+        if let Some(name) = key.strip_prefix(XATTR_PREFIX) {
+            xattrs.push((name.to_string(), extension.value_bytes().to_vec()));
+        }
+    }
+    Ok(xattrs)
+}
+
+/// Verify that a symlink entry's target, once resolved relative to its own
+/// location under `target_dir`, still falls inside `target_dir`
+///
+/// `target` isn't required to exist yet (the pointee may not have been
+/// extracted, or may live outside the archive entirely), so this walks the
+/// target's components lexically against the symlink's position rather
+/// than canonicalizing. Absolute targets are rejected outright since they
+/// point outside `target_dir` by construction.
+fn resolve_symlink_target(target_dir: &Path, link_path: &Path, target: &Path) -> Result<()> {
+    if target.is_absolute() {
+        return Err(Error::unsafe_path(
+            target.to_string_lossy(),
+            target_dir.join(target),
+        ));
+    }
+
+    let link_parent = link_path.parent().unwrap_or(target_dir);
+    let mut components: Vec<&std::ffi::OsStr> = link_parent
+        .strip_prefix(target_dir)
+        .unwrap_or(link_parent)
+        .iter()
+        .collect();
+
+    for component in target.components() {
+        match component {
+            Component::Normal(part) => components.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if components.pop().is_none() {
+                    return Err(Error::unsafe_path(
+                        target.to_string_lossy(),
+                        target_dir.join(target),
+                    ));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::unsafe_path(
+                    target.to_string_lossy(),
+                    target_dir.join(target),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
@@ -574,6 +1613,235 @@ mod tests {
         assert!(builder.is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_zstd_compressed_builder_creation() {
+        let writer = Vec::new();
+        let builder = TarArchiveBuilder::compressed(writer, ArchiveType::TarZst);
+        assert!(builder.is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "lz4")]
+    fn test_lz4_compressed_builder_creation() {
+        let writer = Vec::new();
+        let builder = TarArchiveBuilder::compressed(writer, ArchiveType::TarLz4);
+        assert!(builder.is_ok());
+    }
+
+    #[test]
+    fn test_pax_builder_encodes_record_with_self_referential_length() {
+        let body = PaxBuilder::new()
+            .with_record("path", "foo")
+            .into_bytes();
+
+        // "12 path=foo\n" is 12 bytes: the declared length counts its own
+        // digits as well as the space, the key=value pair and the newline.
+        assert_eq!(body, b"12 path=foo\n");
+    }
+
+    #[test]
+    fn test_pax_builder_empty_has_no_records() {
+        assert!(PaxBuilder::new().is_empty());
+        assert!(!PaxBuilder::new().with_record("path", "foo").is_empty());
+    }
+
+    #[test]
+    fn test_append_data_with_pax_for_long_path() {
+        let writer = Vec::new();
+        let mut builder = TarArchiveBuilder::new(writer, ArchiveType::Tar).unwrap();
+
+        let long_name = "a".repeat(150);
+        let data = b"hello world".to_vec();
+        builder
+            .append_data_with_pax(&long_name, data.len() as u64, Cursor::new(data), None)
+            .unwrap();
+
+        let written = builder.finish().unwrap();
+        assert!(!written.is_empty());
+    }
+
+    #[test]
+    fn test_list_entries_iter_matches_list_entries() {
+        let mut data = Vec::new();
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            builder
+                .append_data("a.txt", 5, Cursor::new(b"hello".to_vec()))
+                .unwrap();
+            builder
+                .append_data("b.txt", 5, Cursor::new(b"world".to_vec()))
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let mut handler = TarArchiveHandler::new(Cursor::new(data.clone()), ArchiveType::Tar).unwrap();
+        let eager = handler.list_entries().unwrap();
+
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let lazy: Vec<TarEntryInfo> = handler
+            .list_entries_iter()
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(eager.len(), lazy.len());
+        for (e, l) in eager.iter().zip(lazy.iter()) {
+            assert_eq!(e.path, l.path);
+            assert_eq!(e.size, l.size);
+        }
+    }
+
+    #[test]
+    fn test_list_entries_surfaces_pax_xattrs() {
+        let mut data = Vec::new();
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            let mut extra = BTreeMap::new();
+            extra.insert("SCHILY.xattr.user.comment".to_string(), "hello".to_string());
+            let content = b"hello world".to_vec();
+            builder
+                .append_data_with_pax("a.txt", content.len() as u64, Cursor::new(content), Some(extra))
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let entries = handler.list_entries().unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].xattrs,
+            vec![("user.comment".to_string(), b"hello".to_vec())]
+        );
+    }
+
+    #[test]
+    fn test_list_entries_resolves_long_path_via_pax() {
+        let mut data = Vec::new();
+        let long_name = format!("deeply/nested/{}.txt", "a".repeat(150));
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            let content = b"payload".to_vec();
+            builder
+                .append_data_with_pax(&long_name, content.len() as u64, Cursor::new(content), None)
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let entries = handler.list_entries().unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, PathBuf::from(&long_name));
+    }
+
+    #[test]
+    fn test_list_matching_filters_by_glob() {
+        let mut data = Vec::new();
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            builder
+                .append_data("src/a.rs", 5, Cursor::new(b"hello".to_vec()))
+                .unwrap();
+            builder
+                .append_data("src/b.txt", 5, Cursor::new(b"world".to_vec()))
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let matches = handler.list_matching(&["src/*.rs"]).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, PathBuf::from("src/a.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_matching_only_writes_matching_entries() {
+        let mut data = Vec::new();
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            builder
+                .append_data("keep.txt", 4, Cursor::new(b"keep".to_vec()))
+                .unwrap();
+            builder
+                .append_data("skip.txt", 4, Cursor::new(b"skip".to_vec()))
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let extracted = handler
+            .extract_matching(temp_dir.path(), &["keep.txt"])
+            .await
+            .unwrap();
+
+        assert_eq!(extracted, vec![temp_dir.path().join("keep.txt")]);
+        assert!(temp_dir.path().join("keep.txt").exists());
+        assert!(!temp_dir.path().join("skip.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_preserves_sparse_zero_runs() {
+        let mut content = vec![0u8; SPARSE_BLOCK_SIZE as usize * 2];
+        content.extend_from_slice(b"tail data");
+
+        let mut data = Vec::new();
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            builder
+                .append_data("sparse.bin", content.len() as u64, Cursor::new(content.clone()))
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        handler.set_preserve_sparse(true);
+        handler.extract_to(temp_dir.path()).await.unwrap();
+
+        let extracted = tokio::fs::read(temp_dir.path().join("sparse.bin"))
+            .await
+            .unwrap();
+        assert_eq!(extracted, content);
+    }
+
+    #[test]
+    fn test_from_data_autodetect_gzip() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello").unwrap();
+        let data = encoder.finish().unwrap();
+
+        let (_, archive_type) = TarArchiveHandler::from_data_autodetect(data).unwrap();
+        assert_eq!(archive_type, ArchiveType::TarGz);
+    }
+
+    #[test]
+    fn test_from_data_autodetect_plain_tar() {
+        let mut data = Vec::new();
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            builder
+                .append_data("a.txt", 5, Cursor::new(b"hello".to_vec()))
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let (_, archive_type) = TarArchiveHandler::from_data_autodetect(data).unwrap();
+        assert_eq!(archive_type, ArchiveType::Tar);
+    }
+
+    #[test]
+    fn test_from_data_autodetect_rejects_unknown_data() {
+        let result = TarArchiveHandler::from_data_autodetect(vec![0u8; 16]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_entry_info() {
         let info = TarEntryInfo {
@@ -584,10 +1852,214 @@ mod tests {
             uid: 1000,
             gid: 1000,
             mtime: 1234567890,
+            link_name: None,
+            xattrs: Vec::new(),
         };
 
         assert_eq!(info.path, PathBuf::from("test.txt"));
         assert_eq!(info.size, 100);
         assert_eq!(info.mode, 0o644);
+        assert!(info.link_name.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_restores_permissions_and_mtime() {
+        let mut builder =
+            TarArchiveBuilder::new(Cursor::new(Vec::new()), ArchiveType::Tar).unwrap();
+        builder
+            .append_data("file.txt", 5, Cursor::new(b"hello".to_vec()))
+            .unwrap();
+        let tar_data = builder.finish().unwrap().into_inner();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut handler =
+            TarArchiveHandler::new(Cursor::new(tar_data), ArchiveType::Tar).unwrap();
+        handler.set_preserve_permissions(true);
+        handler.set_preserve_mtime(true);
+
+        let extracted = handler.extract_to(temp_dir.path()).await.unwrap();
+        assert_eq!(extracted.len(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = std::fs::metadata(&extracted[0]).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o644);
+            // append_data leaves the header mtime at 0
+            assert_eq!(metadata.modified().unwrap(), std::time::UNIX_EPOCH);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_with_restores_permissions_and_mtime() {
+        let mut builder =
+            TarArchiveBuilder::new(Cursor::new(Vec::new()), ArchiveType::Tar).unwrap();
+        builder
+            .append_data("file.txt", 5, Cursor::new(b"hello".to_vec()))
+            .unwrap();
+        let tar_data = builder.finish().unwrap().into_inner();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut handler =
+            TarArchiveHandler::new(Cursor::new(tar_data), ArchiveType::Tar).unwrap();
+        let options = TarExtractOptions::default()
+            .with_preserve_permissions(true)
+            .with_preserve_mtime(true);
+
+        let extracted = handler
+            .extract_with(temp_dir.path(), &options)
+            .await
+            .unwrap();
+        assert_eq!(extracted.len(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = std::fs::metadata(&extracted[0]).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o644);
+            // append_data leaves the header mtime at 0
+            assert_eq!(metadata.modified().unwrap(), std::time::UNIX_EPOCH);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_with_never_overwrite_skips_existing_file() {
+        let mut builder =
+            TarArchiveBuilder::new(Cursor::new(Vec::new()), ArchiveType::Tar).unwrap();
+        builder
+            .append_data("file.txt", 5, Cursor::new(b"hello".to_vec()))
+            .unwrap();
+        let tar_data = builder.finish().unwrap().into_inner();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("file.txt"), b"existing").unwrap();
+
+        let mut handler =
+            TarArchiveHandler::new(Cursor::new(tar_data), ArchiveType::Tar).unwrap();
+        let options = TarExtractOptions::default().with_overwrite(OverwritePolicy::Never);
+
+        let extracted = handler
+            .extract_with(temp_dir.path(), &options)
+            .await
+            .unwrap();
+        assert!(extracted.is_empty());
+
+        let contents = std::fs::read(temp_dir.path().join("file.txt")).unwrap();
+        assert_eq!(contents, b"existing");
+    }
+
+    #[tokio::test]
+    async fn test_extract_with_if_newer_overwrites_only_when_entry_is_newer() {
+        let mut builder =
+            TarArchiveBuilder::new(Cursor::new(Vec::new()), ArchiveType::Tar).unwrap();
+        builder
+            .append_data("file.txt", 5, Cursor::new(b"hello".to_vec()))
+            .unwrap();
+        let tar_data = builder.finish().unwrap().into_inner();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dest = temp_dir.path().join("file.txt");
+        std::fs::write(&dest, b"existing").unwrap();
+
+        let far_future = std::time::SystemTime::now() + std::time::Duration::from_secs(3600);
+        filetime::set_file_mtime(&dest, filetime::FileTime::from_system_time(far_future)).unwrap();
+
+        let mut handler =
+            TarArchiveHandler::new(Cursor::new(tar_data), ArchiveType::Tar).unwrap();
+        let options = TarExtractOptions::default().with_overwrite(OverwritePolicy::IfNewer);
+
+        let extracted = handler
+            .extract_with(temp_dir.path(), &options)
+            .await
+            .unwrap();
+        assert!(extracted.is_empty());
+        assert_eq!(std::fs::read(&dest).unwrap(), b"existing");
+    }
+
+    #[tokio::test]
+    async fn test_create_from_directory_preserves_symlinks() {
+        let source_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(source_dir.path().join("real.txt"), b"hello").unwrap();
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("real.txt", source_dir.path().join("link.txt")).unwrap();
+
+            let target_file = tempfile::NamedTempFile::new().unwrap();
+            let builder = TarArchiveBuilder::for_directory(ArchiveType::Tar);
+            builder
+                .create_from_directory(source_dir.path(), target_file.path())
+                .await
+                .unwrap();
+
+            let data = std::fs::read(target_file.path()).unwrap();
+            let mut handler =
+                TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+            let entries = handler.list_entries().unwrap();
+
+            let link_entry = entries
+                .iter()
+                .find(|entry| entry.path == PathBuf::from("link.txt"))
+                .expect("symlink entry should be present in repacked archive");
+            assert_eq!(link_entry.entry_type, EntryType::Symlink);
+            assert_eq!(link_entry.link_name, Some(PathBuf::from("real.txt")));
+        }
+    }
+
+    /// Build a single-entry TAR whose header name is written verbatim, since
+    /// `tar::Builder::append_data` refuses names with `..` components
+    fn tar_with_raw_entry_name(name: &[u8], content: &[u8]) -> Vec<u8> {
+        let mut header = tar::Header::new_gnu();
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append(&header, content).unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_rejects_parent_dir_traversal() {
+        let data = tar_with_raw_entry_name(b"../../etc/passwd", b"evil");
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let result = handler.extract_to(temp_dir.path()).await;
+
+        assert!(matches!(result, Err(Error::UnsafeEntry { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_rejects_escaping_symlink_target() {
+        let mut data = Vec::new();
+        {
+            let mut builder = TarArchiveBuilder::new(&mut data, ArchiveType::Tar).unwrap();
+            builder
+                .append_long_path("link.txt", "../../../etc/passwd")
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let result = handler.extract_to(temp_dir.path()).await;
+
+        assert!(matches!(result, Err(Error::UnsafePath { .. })));
+        assert!(!temp_dir.path().join("link.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_extract_matching_rejects_parent_dir_traversal() {
+        let data = tar_with_raw_entry_name(b"../outside.txt", b"evil");
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut handler = TarArchiveHandler::new(Cursor::new(data), ArchiveType::Tar).unwrap();
+        let result = handler
+            .extract_matching(temp_dir.path(), &["../outside.txt"])
+            .await;
+
+        assert!(matches!(result, Err(Error::UnsafeEntry { .. })));
     }
 }
@@ -3,14 +3,16 @@
 //! This module provides specialized handling for ZIP archives using the zip crate,
 //! with support for various compression methods and ZIP-specific features.
 
+use std::fmt;
 use std::io::{Cursor, Read, Seek, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use zip::read::ZipFile;
 use zip::write::{ExtendedFileOptions, SimpleFileOptions};
-use zip::{CompressionMethod, DateTime, ZipArchive, ZipWriter};
+use zip::{AesMode, CompressionMethod, DateTime, ZipArchive, ZipWriter};
 
 use crate::{ArchiveType, Error, Result};
 
@@ -60,42 +62,100 @@ impl<R: Read + Seek> ZipArchiveHandler<R> {
     }
 
     /// Extract all entries to the specified directory
+    ///
+    /// Entry names are sanitized against Zip Slip (absolute paths and `..`
+    /// components) before anything is written; see [`ZipExtractOptions`] for
+    /// extraction knobs, and [`Self::extract_to_with_options`] to set them.
     pub async fn extract_to(&mut self, target_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        self.extract_to_with_options(target_dir, &ZipExtractOptions::default())
+            .await
+    }
+
+    /// Extract all entries to the specified directory using the given
+    /// [`ZipExtractOptions`]
+    ///
+    /// Every entry name is resolved against `target_dir` by rejecting
+    /// absolute paths and `..` components and then verifying, after
+    /// canonicalization, that the resolved path still falls under
+    /// `target_dir`. Entries that fail this check are reported as
+    /// [`Error::UnsafePath`] instead of being written.
+    pub async fn extract_to_with_options(
+        &mut self,
+        target_dir: impl AsRef<Path>,
+        options: &ZipExtractOptions,
+    ) -> Result<Vec<PathBuf>> {
         let target_dir = target_dir.as_ref();
         fs::create_dir_all(target_dir).await?;
+        let target_dir_canonical = fs::canonicalize(target_dir).await?;
 
         let mut extracted_files = Vec::new();
 
         for i in 0..self.archive.len() {
             let mut file = self.archive.by_index(i)?;
-            let file_path = target_dir.join(file.name());
-
-            // Create parent directories
-            if let Some(parent) = file_path.parent() {
-                fs::create_dir_all(parent).await?;
-            }
+            let name = file.name().to_string();
+            let file_path =
+                sanitize_entry_path(target_dir, &target_dir_canonical, &name, options).await?;
 
             if file.is_dir() {
                 fs::create_dir_all(&file_path).await?;
-            } else {
-                let mut content = Vec::with_capacity(file.size() as usize);
-                std::io::Read::read_to_end(&mut file, &mut content)?;
-
-                let mut output_file = fs::File::create(&file_path).await?;
-                output_file.write_all(&content).await?;
-
-                // Set file permissions on Unix systems
-                #[cfg(unix)]
-                {
-                    if let Some(mode) = file.unix_mode() {
-                        use std::os::unix::fs::PermissionsExt;
-                        let permissions = std::fs::Permissions::from_mode(mode);
-                        std::fs::set_permissions(&file_path, permissions)?;
-                    }
-                }
+                continue;
+            }
 
+            #[cfg(unix)]
+            let is_symlink = file
+                .unix_mode()
+                .map(|mode| mode & 0o170000 == 0o120000)
+                .unwrap_or(false);
+            #[cfg(not(unix))]
+            let is_symlink = false;
+
+            if is_symlink && !options.allow_symlinks {
+                continue;
+            }
+
+            ensure_decodable(file.compression())?;
+            let timestamps = parse_extra_field_timestamps(file.extra_data().unwrap_or(&[]));
+
+            let mut content = Vec::with_capacity(file.size() as usize);
+            std::io::Read::read_to_end(&mut file, &mut content)?;
+
+            if !options.overwrite && fs::try_exists(&file_path).await.unwrap_or(false) {
+                continue;
+            }
+
+            #[cfg(unix)]
+            if is_symlink {
+                let target = String::from_utf8_lossy(&content).into_owned();
+                resolve_symlink_target(target_dir, &file_path, &target)?;
+                let _ = std::fs::remove_file(&file_path);
+                std::os::unix::fs::symlink(target, &file_path)?;
                 extracted_files.push(file_path);
+                continue;
             }
+
+            let mut output_file = fs::File::create(&file_path).await?;
+            output_file.write_all(&content).await?;
+
+            // Set file permissions on Unix systems
+            #[cfg(unix)]
+            {
+                if let Some(mode) = file.unix_mode() {
+                    use std::os::unix::fs::PermissionsExt;
+                    let permissions = std::fs::Permissions::from_mode(mode);
+                    std::fs::set_permissions(&file_path, permissions)?;
+                }
+            }
+
+            if let Some(modified) = timestamps.modified {
+                let mtime = filetime::FileTime::from_system_time(modified);
+                let atime = timestamps
+                    .accessed
+                    .map(filetime::FileTime::from_system_time)
+                    .unwrap_or(mtime);
+                filetime::set_file_times(&file_path, atime, mtime)?;
+            }
+
+            extracted_files.push(file_path);
         }
 
         Ok(extracted_files)
@@ -110,6 +170,7 @@ impl<R: Read + Seek> ZipArchiveHandler<R> {
             fs::create_dir_all(parent).await?;
         }
 
+        ensure_decodable(file.compression())?;
         let mut content = Vec::with_capacity(file.size() as usize);
         std::io::Read::read_to_end(&mut file, &mut content)?;
 
@@ -122,6 +183,7 @@ impl<R: Read + Seek> ZipArchiveHandler<R> {
     /// Read a file's content directly into memory
     pub fn read_file(&mut self, name: &str) -> Result<Vec<u8>> {
         let mut file = self.archive.by_name(name)?;
+        ensure_decodable(file.compression())?;
         let mut content = Vec::with_capacity(file.size() as usize);
         std::io::Read::read_to_end(&mut file, &mut content)?;
         Ok(content)
@@ -137,12 +199,83 @@ impl<R: Read + Seek> ZipArchiveHandler<R> {
         Ok(self.archive.by_name(name)?)
     }
 
+    /// Read a password-protected file's content directly into memory
+    ///
+    /// Works for both AES and legacy ZipCrypto encrypted entries; the zip
+    /// crate picks the decryption path based on the entry's own metadata.
+    pub fn read_file_with_password(&mut self, name: &str, password: &str) -> Result<Vec<u8>> {
+        let mut file = self.archive.by_name_decrypt(name, password.as_bytes())?;
+        ensure_decodable(file.compression())?;
+        let mut content = Vec::with_capacity(file.size() as usize);
+        std::io::Read::read_to_end(&mut file, &mut content)?;
+        Ok(content)
+    }
+
+    /// Extract all entries to the specified directory, decrypting any
+    /// password-protected entries with the given password
+    ///
+    /// Entries that aren't encrypted are extracted normally; the password is
+    /// only consulted when the zip crate reports the entry as encrypted.
+    pub async fn extract_to_with_password(
+        &mut self,
+        target_dir: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let target_dir = target_dir.as_ref();
+        fs::create_dir_all(target_dir).await?;
+        let target_dir_canonical = fs::canonicalize(target_dir).await?;
+
+        let mut extracted_files = Vec::new();
+
+        for i in 0..self.archive.len() {
+            // `by_index` refuses encrypted entries, so metadata is read raw
+            let file = self.archive.by_index_raw(i)?;
+            let is_encrypted = file.encrypted();
+            let is_dir = file.is_dir();
+            let compression = file.compression();
+            let name = file.name().to_string();
+            drop(file);
+            let file_path = sanitize_entry_path(
+                target_dir,
+                &target_dir_canonical,
+                &name,
+                &ZipExtractOptions::default(),
+            )
+            .await?;
+
+            if is_dir {
+                fs::create_dir_all(&file_path).await?;
+                continue;
+            }
+
+            ensure_decodable(compression)?;
+
+            let mut file = if is_encrypted {
+                self.archive.by_index_decrypt(i, password.as_bytes())?
+            } else {
+                self.archive.by_index(i)?
+            };
+            let mut content = Vec::with_capacity(file.size() as usize);
+            std::io::Read::read_to_end(&mut file, &mut content)?;
+            drop(file);
+
+            let mut output_file = fs::File::create(&file_path).await?;
+            output_file.write_all(&content).await?;
+
+            extracted_files.push(file_path);
+        }
+
+        Ok(extracted_files)
+    }
+
     /// List all entries without extracting
     pub fn list_entries(&mut self) -> Result<Vec<ZipEntryInfo>> {
         let mut entries = Vec::new();
 
         for i in 0..self.archive.len() {
             let file = self.archive.by_index(i)?;
+            let extra_data = file.extra_data().unwrap_or(&[]).to_vec();
+            let timestamps = parse_extra_field_timestamps(&extra_data);
 
             let info = ZipEntryInfo {
                 name: file.name().to_string(),
@@ -154,8 +287,13 @@ impl<R: Read + Seek> ZipArchiveHandler<R> {
                 unix_mode: file.unix_mode(),
                 last_modified: file.last_modified().unwrap_or_default(),
                 crc32: file.crc32(),
-                extra_data: file.extra_data().unwrap_or(&[]).to_vec(),
+                extra_data,
                 comment: file.comment().to_string(),
+                encryption: ZipEncryption::from_entry(&file),
+                codec: CompressionCodec::from(file.compression()),
+                modified: timestamps.modified,
+                accessed: timestamps.accessed,
+                created: timestamps.created,
             };
 
             entries.push(info);
@@ -180,6 +318,107 @@ impl<R: Read + Seek> ZipArchiveHandler<R> {
     }
 }
 
+/// Handler for walking ZIP entries sequentially from a non-seekable reader
+///
+/// Unlike [`ZipArchiveHandler`], which requires `Read + Seek` to locate the
+/// central directory, this walks entries one at a time using their local
+/// file headers, so it works with a reader backed by a pipe, socket, or
+/// streamed HTTP body. The tradeoff is that central-directory-only
+/// information — the archive comment, and knowing the total entry count
+/// ahead of time — isn't available until the stream has been fully
+/// consumed.
+pub struct ZipStreamHandler<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> ZipStreamHandler<R> {
+    /// Create a new streaming ZIP handler over a non-seekable reader
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Read the next entry's metadata and a reader positioned at its
+    /// content, if any entries remain
+    ///
+    /// Returns `Ok(None)` once the stream's central directory marker is
+    /// reached with no further local file headers to read.
+    pub fn next_entry(&mut self) -> Result<Option<(ZipEntryInfo, ZipFile<'_, R>)>> {
+        let file = match zip::read::read_zipfile_from_stream(&mut self.reader)? {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+
+        let extra_data = file.extra_data().unwrap_or(&[]).to_vec();
+        let timestamps = parse_extra_field_timestamps(&extra_data);
+
+        let info = ZipEntryInfo {
+            name: file.name().to_string(),
+            size: file.size(),
+            compressed_size: file.compressed_size(),
+            compression_method: file.compression(),
+            is_dir: file.is_dir(),
+            is_file: file.is_file(),
+            unix_mode: file.unix_mode(),
+            last_modified: file.last_modified().unwrap_or_default(),
+            crc32: file.crc32(),
+            extra_data,
+            comment: file.comment().to_string(),
+            encryption: ZipEncryption::from_entry(&file),
+            codec: CompressionCodec::from(file.compression()),
+            modified: timestamps.modified,
+            accessed: timestamps.accessed,
+            created: timestamps.created,
+        };
+
+        Ok(Some((info, file)))
+    }
+
+    /// Extract every entry to `target_dir`, writing each entry's content as
+    /// it is read from the stream rather than buffering the whole archive
+    /// up front
+    ///
+    /// Applies the same Zip Slip sanitization as
+    /// [`ZipArchiveHandler::extract_to`].
+    pub async fn stream_extract_to(
+        mut reader: R,
+        target_dir: impl AsRef<Path>,
+    ) -> Result<Vec<PathBuf>> {
+        let target_dir = target_dir.as_ref();
+        fs::create_dir_all(target_dir).await?;
+        let target_dir_canonical = fs::canonicalize(target_dir).await?;
+
+        let mut extracted_files = Vec::new();
+
+        while let Some(mut file) = zip::read::read_zipfile_from_stream(&mut reader)? {
+            let name = file.name().to_string();
+            let file_path = sanitize_entry_path(
+                target_dir,
+                &target_dir_canonical,
+                &name,
+                &ZipExtractOptions::default(),
+            )
+            .await?;
+
+            if file.is_dir() {
+                fs::create_dir_all(&file_path).await?;
+                continue;
+            }
+
+            ensure_decodable(file.compression())?;
+
+            let mut content = Vec::with_capacity(file.size() as usize);
+            std::io::Read::read_to_end(&mut file, &mut content)?;
+
+            let mut output_file = fs::File::create(&file_path).await?;
+            output_file.write_all(&content).await?;
+
+            extracted_files.push(file_path);
+        }
+
+        Ok(extracted_files)
+    }
+}
+
 /// Information about a ZIP entry
 #[derive(Debug, Clone)]
 pub struct ZipEntryInfo {
@@ -205,6 +444,512 @@ pub struct ZipEntryInfo {
     pub extra_data: Vec<u8>,
     /// File comment
     pub comment: String,
+    /// Encryption scheme the entry was stored with, if any
+    pub encryption: Option<ZipEncryption>,
+    /// Compression codec the entry was stored with
+    pub codec: CompressionCodec,
+    /// Modification time parsed from the entry's extra field (Info-ZIP
+    /// extended timestamp 0x5455 or NTFS 0x000a), if present
+    pub modified: Option<SystemTime>,
+    /// Access time parsed from the entry's extra field, if present
+    pub accessed: Option<SystemTime>,
+    /// Creation time parsed from the entry's extra field, if present
+    pub created: Option<SystemTime>,
+}
+
+/// Compression codec used (or requested) for a ZIP entry
+///
+/// Wraps [`CompressionMethod`] with the subset this crate knows how to
+/// select when writing, plus an [`Self::Other`] bucket for methods the zip
+/// crate supports that this type doesn't model explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionCodec {
+    /// No compression
+    Stored,
+    /// DEFLATE (the ubiquitous ZIP default)
+    Deflated,
+    /// BZIP2
+    Bzip2,
+    /// Zstandard
+    Zstd,
+    /// LZMA
+    Lzma,
+    /// Deflate64 — only ever encountered when reading; the zip crate has no
+    /// encoder for it
+    Deflate64,
+    /// Standard DEFLATE, but encoded with zopfli's exhaustive search for a
+    /// smaller result instead of flate2's encoder
+    ///
+    /// The output is still plain DEFLATE — any unzip tool reads it the same
+    /// as [`Self::Deflated`] through the normal decode path — this only
+    /// changes how the writer searches for a compact encoding. `iterations`
+    /// trades CPU time for a (usually) smaller archive.
+    DeflateZopfli {
+        /// Number of zopfli compression passes to run
+        iterations: u32,
+    },
+    /// A compression method this type doesn't have a dedicated variant for
+    Other,
+}
+
+impl CompressionCodec {
+    /// Build [`SimpleFileOptions`] selecting this codec at the given
+    /// compression level (ignored for [`Self::Stored`] and
+    /// [`Self::DeflateZopfli`], which takes its own `iterations`)
+    ///
+    /// Returns [`Error::unsupported_format`] when the codec's zip-crate
+    /// feature wasn't compiled in, or for [`Self::Deflate64`] and
+    /// [`Self::Other`], which can only be read, never written.
+    pub fn to_options(self, level: Option<i32>) -> Result<SimpleFileOptions> {
+        if let Self::DeflateZopfli { iterations } = self {
+            #[cfg(not(feature = "zip-deflate-zopfli"))]
+            {
+                return Err(Error::unsupported_format(
+                    "Zopfli-backed Deflate compression requires the zip-deflate-zopfli feature",
+                ));
+            }
+            #[cfg(feature = "zip-deflate-zopfli")]
+            {
+                return Ok(SimpleFileOptions::default()
+                    .compression_method(CompressionMethod::Deflated)
+                    .compression_level(Some(iterations.into())));
+            }
+        }
+
+        let method = match self {
+            Self::Stored => CompressionMethod::Stored,
+            Self::Deflated => CompressionMethod::Deflated,
+            #[cfg(feature = "zip-bzip2")]
+            Self::Bzip2 => CompressionMethod::Bzip2,
+            #[cfg(not(feature = "zip-bzip2"))]
+            Self::Bzip2 => {
+                return Err(Error::unsupported_format(
+                    "BZIP2 compression requires the zip-bzip2 feature",
+                ));
+            }
+            #[cfg(feature = "zip-zstd")]
+            Self::Zstd => CompressionMethod::Zstd,
+            #[cfg(not(feature = "zip-zstd"))]
+            Self::Zstd => {
+                return Err(Error::unsupported_format(
+                    "Zstandard compression requires the zip-zstd feature",
+                ));
+            }
+            #[cfg(feature = "zip-lzma")]
+            Self::Lzma => CompressionMethod::Lzma,
+            #[cfg(not(feature = "zip-lzma"))]
+            Self::Lzma => {
+                return Err(Error::unsupported_format(
+                    "LZMA compression requires the zip-lzma feature",
+                ));
+            }
+            Self::Deflate64 | Self::Other => {
+                return Err(Error::unsupported_format(format!(
+                    "{:?} compression can only be read, not written",
+                    self
+                )));
+            }
+            Self::DeflateZopfli { .. } => unreachable!("handled above"),
+        };
+
+        Ok(SimpleFileOptions::default()
+            .compression_method(method)
+            .compression_level(level.map(Into::into)))
+    }
+}
+
+impl From<CompressionMethod> for CompressionCodec {
+    fn from(method: CompressionMethod) -> Self {
+        match method {
+            CompressionMethod::Stored => Self::Stored,
+            CompressionMethod::Deflated => Self::Deflated,
+            #[cfg(feature = "zip-bzip2")]
+            CompressionMethod::Bzip2 => Self::Bzip2,
+            #[cfg(feature = "zip-zstd")]
+            CompressionMethod::Zstd => Self::Zstd,
+            #[cfg(feature = "zip-lzma")]
+            CompressionMethod::Lzma => Self::Lzma,
+            #[cfg(feature = "zip-deflate64")]
+            CompressionMethod::Deflate64 => Self::Deflate64,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Options controlling how [`ZipArchiveHandler::extract_to_with_options`]
+/// lays entries out on disk
+#[derive(Debug, Clone, Copy)]
+pub struct ZipExtractOptions {
+    /// Whether entries whose Unix mode marks them as symlinks are written as
+    /// real symlinks (entry content is taken as the link target). When
+    /// `false`, such entries are skipped entirely rather than written as
+    /// regular files, since a symlink planted by the archive could otherwise
+    /// be used to escape `target_dir` on a later write.
+    pub allow_symlinks: bool,
+    /// Whether an existing file at the resolved path is overwritten.
+    pub overwrite: bool,
+    /// Number of leading path components to strip from each entry name
+    /// before resolving it against the target directory, mirroring `tar
+    /// --strip-components`.
+    pub strip_components: usize,
+}
+
+impl Default for ZipExtractOptions {
+    fn default() -> Self {
+        Self {
+            allow_symlinks: false,
+            overwrite: true,
+            strip_components: 0,
+        }
+    }
+}
+
+impl ZipExtractOptions {
+    /// Set whether symlink entries are materialized as real symlinks
+    pub fn with_allow_symlinks(mut self, allow_symlinks: bool) -> Self {
+        self.allow_symlinks = allow_symlinks;
+        self
+    }
+
+    /// Set whether existing files at the resolved path are overwritten
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Set the number of leading path components to strip from entry names
+    pub fn with_strip_components(mut self, strip_components: usize) -> Self {
+        self.strip_components = strip_components;
+        self
+    }
+}
+
+/// Resolve an archive entry name against `target_dir`, guarding against Zip
+/// Slip
+///
+/// Rejects any entry containing an absolute path component (`Component::Root`
+/// / `Component::Prefix`) or a `..` component outright. The remaining
+/// components (after stripping `options.strip_components` leading ones) are
+/// joined onto `target_dir`, the resulting path's parent directories are
+/// created, and the joined path is canonicalized and checked to still begin
+/// with `target_dir_canonical` — this catches entries that would escape via
+/// a symlink already present in `target_dir`. Entries that fail either check
+/// are reported as [`Error::UnsafePath`].
+async fn sanitize_entry_path(
+    target_dir: &Path,
+    target_dir_canonical: &Path,
+    entry_name: &str,
+    options: &ZipExtractOptions,
+) -> Result<PathBuf> {
+    let entry_path = Path::new(entry_name);
+    let mut relative = PathBuf::new();
+
+    for component in entry_path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::unsafe_path(entry_name, target_dir.join(entry_name)));
+            }
+        }
+    }
+
+    let stripped: PathBuf = relative
+        .components()
+        .skip(options.strip_components)
+        .collect();
+    if stripped.as_os_str().is_empty() {
+        return Err(Error::unsafe_path(entry_name, target_dir.join(entry_name)));
+    }
+
+    let resolved = target_dir.join(&stripped);
+
+    if let Some(parent) = resolved.parent() {
+        fs::create_dir_all(parent).await?;
+        let canonical_parent = fs::canonicalize(parent).await?;
+        let canonical_resolved = match resolved.file_name() {
+            Some(file_name) => canonical_parent.join(file_name),
+            None => canonical_parent,
+        };
+
+        if !canonical_resolved.starts_with(target_dir_canonical) {
+            return Err(Error::unsafe_path(entry_name, canonical_resolved));
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Verify that a symlink entry's target, once resolved relative to its own
+/// location under `target_dir`, still falls inside `target_dir`
+///
+/// `target` isn't required to exist yet (the pointee may not have been
+/// extracted, or may live outside the archive entirely), so this walks the
+/// target's components lexically against the symlink's position rather
+/// than canonicalizing. Absolute targets are rejected outright since they
+/// point outside `target_dir` by construction. Returns the resolved,
+/// target-dir-relative path on success; the caller only needs the `?` for
+/// its error path, since the real symlink is created with the raw target
+/// string so the OS can resolve it as usual.
+fn resolve_symlink_target(target_dir: &Path, link_path: &Path, target: &str) -> Result<PathBuf> {
+    let target_path = Path::new(target);
+    if target_path.is_absolute() {
+        return Err(Error::unsafe_path(target, target_path));
+    }
+
+    let link_parent = link_path.parent().unwrap_or(target_dir);
+    let mut components: Vec<&std::ffi::OsStr> = link_parent
+        .strip_prefix(target_dir)
+        .unwrap_or(link_parent)
+        .iter()
+        .collect();
+
+    for component in target_path.components() {
+        match component {
+            Component::Normal(part) => components.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if components.pop().is_none() {
+                    return Err(Error::unsafe_path(target, target_dir.join(target)));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::unsafe_path(target, target_dir.join(target)));
+            }
+        }
+    }
+
+    let mut resolved = target_dir.to_path_buf();
+    resolved.extend(components);
+    Ok(resolved)
+}
+
+/// High-resolution timestamps recovered from a ZIP entry's extra field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ExtraFieldTimestamps {
+    modified: Option<SystemTime>,
+    accessed: Option<SystemTime>,
+    created: Option<SystemTime>,
+}
+
+/// Parse an entry's raw extra field data for timestamps
+///
+/// Understands the Info-ZIP extended timestamp block (header id `0x5455`:
+/// a flags byte followed by up to three little-endian Unix second counts
+/// for mtime/atime/ctime, in that order) and the NTFS extra field (header
+/// id `0x000a`: 4 reserved bytes followed by tagged sub-blocks, where tag
+/// `0x0001` holds mtime/atime/ctime as 64-bit Windows `FILETIME` values).
+/// Unrecognized blocks are skipped. When both are present, whichever is
+/// encountered first wins for a given field.
+fn parse_extra_field_timestamps(extra_data: &[u8]) -> ExtraFieldTimestamps {
+    let mut result = ExtraFieldTimestamps::default();
+    let mut cursor = extra_data;
+
+    while cursor.len() >= 4 {
+        let header_id = u16::from_le_bytes([cursor[0], cursor[1]]);
+        let size = u16::from_le_bytes([cursor[2], cursor[3]]) as usize;
+        if cursor.len() < 4 + size {
+            break;
+        }
+        let block = &cursor[4..4 + size];
+
+        match header_id {
+            0x5455 => parse_unix_extended_timestamp(block, &mut result),
+            0x000a => parse_ntfs_timestamp(block, &mut result),
+            _ => {}
+        }
+
+        cursor = &cursor[4 + size..];
+    }
+
+    result
+}
+
+fn parse_unix_extended_timestamp(block: &[u8], result: &mut ExtraFieldTimestamps) {
+    let Some((&flags, rest)) = block.split_first() else {
+        return;
+    };
+
+    let fields = [
+        (0b001u8, &mut result.modified),
+        (0b010u8, &mut result.accessed),
+        (0b100u8, &mut result.created),
+    ];
+
+    let mut rest = rest;
+    for (bit, slot) in fields {
+        if flags & bit == 0 || rest.len() < 4 {
+            continue;
+        }
+        let secs = i32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]);
+        *slot = unix_seconds_to_system_time(secs);
+        rest = &rest[4..];
+    }
+}
+
+fn parse_ntfs_timestamp(block: &[u8], result: &mut ExtraFieldTimestamps) {
+    if block.len() < 4 {
+        return;
+    }
+    let mut tags = &block[4..];
+
+    while tags.len() >= 4 {
+        let tag = u16::from_le_bytes([tags[0], tags[1]]);
+        let tag_size = u16::from_le_bytes([tags[2], tags[3]]) as usize;
+        if tags.len() < 4 + tag_size {
+            break;
+        }
+        let tag_data = &tags[4..4 + tag_size];
+
+        if tag == 0x0001 && tag_data.len() >= 24 {
+            result.modified = result
+                .modified
+                .or_else(|| filetime_to_system_time(u64_le(&tag_data[0..8])));
+            result.accessed = result
+                .accessed
+                .or_else(|| filetime_to_system_time(u64_le(&tag_data[8..16])));
+            result.created = result
+                .created
+                .or_else(|| filetime_to_system_time(u64_le(&tag_data[16..24])));
+        }
+
+        tags = &tags[4 + tag_size..];
+    }
+}
+
+fn u64_le(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().expect("slice is exactly 8 bytes"))
+}
+
+fn unix_seconds_to_system_time(secs: i32) -> Option<SystemTime> {
+    if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs((-i64::from(secs)) as u64))
+    }
+}
+
+/// Number of seconds between the Windows `FILETIME` epoch (1601-01-01) and
+/// the Unix epoch (1970-01-01)
+const FILETIME_EPOCH_OFFSET_SECS: u64 = 11_644_473_600;
+
+/// Convert a 64-bit Windows `FILETIME` (100ns intervals since 1601-01-01)
+/// into a [`SystemTime`]
+fn filetime_to_system_time(filetime: u64) -> Option<SystemTime> {
+    let unix_100ns = filetime.checked_sub(FILETIME_EPOCH_OFFSET_SECS * 10_000_000)?;
+    let secs = unix_100ns / 10_000_000;
+    let nanos = ((unix_100ns % 10_000_000) * 100) as u32;
+    UNIX_EPOCH.checked_add(Duration::new(secs, nanos))
+}
+
+/// Encode an Info-ZIP extended timestamp (0x5455) extra field body for a
+/// file being written
+///
+/// Mirrors [`parse_unix_extended_timestamp`]'s layout: a flags byte
+/// followed by the Unix second count for each of `modified`/`accessed`/
+/// `created` that's present, in that order.
+fn encode_extended_timestamp(
+    modified: Option<SystemTime>,
+    accessed: Option<SystemTime>,
+    created: Option<SystemTime>,
+) -> Vec<u8> {
+    let mut flags = 0u8;
+    let mut data = Vec::new();
+
+    for (bit, time) in [(0b001u8, modified), (0b010, accessed), (0b100, created)] {
+        if let Some(time) = time {
+            flags |= bit;
+            let unix_secs = time
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i32)
+                .unwrap_or(0);
+            data.extend_from_slice(&unix_secs.to_le_bytes());
+        }
+    }
+
+    let mut extra = Vec::with_capacity(1 + data.len());
+    extra.push(flags);
+    extra.append(&mut data);
+    extra
+}
+
+/// Check whether a ZIP entry's compression method can actually be decoded
+/// with the features compiled into this build
+///
+/// The zip crate itself would error here too, but with an opaque message;
+/// this reports which specific method was encountered so the caller knows
+/// which feature to enable.
+fn ensure_decodable(method: CompressionMethod) -> Result<()> {
+    match method {
+        CompressionMethod::Stored | CompressionMethod::Deflated => Ok(()),
+        #[cfg(feature = "zip-bzip2")]
+        CompressionMethod::Bzip2 => Ok(()),
+        #[cfg(not(feature = "zip-bzip2"))]
+        CompressionMethod::Bzip2 => Err(Error::unsupported_format(
+            "Entry uses BZIP2 compression, but the zip-bzip2 feature is not enabled",
+        )),
+        #[cfg(feature = "zip-zstd")]
+        CompressionMethod::Zstd => Ok(()),
+        #[cfg(not(feature = "zip-zstd"))]
+        CompressionMethod::Zstd => Err(Error::unsupported_format(
+            "Entry uses Zstandard compression, but the zip-zstd feature is not enabled",
+        )),
+        #[cfg(feature = "zip-lzma")]
+        CompressionMethod::Lzma => Ok(()),
+        #[cfg(not(feature = "zip-lzma"))]
+        CompressionMethod::Lzma => Err(Error::unsupported_format(
+            "Entry uses LZMA compression, but the zip-lzma feature is not enabled",
+        )),
+        #[cfg(feature = "zip-deflate64")]
+        CompressionMethod::Deflate64 => Ok(()),
+        #[cfg(not(feature = "zip-deflate64"))]
+        CompressionMethod::Deflate64 => Err(Error::unsupported_format(
+            "Entry uses Deflate64 compression, but the zip-deflate64 feature is not enabled",
+        )),
+        other => Err(Error::unsupported_format(format!(
+            "Entry uses unsupported compression method: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Encryption scheme used to protect a ZIP entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZipEncryption {
+    /// AES-128 (WinZip AE-1/AE-2)
+    Aes128,
+    /// AES-192 (WinZip AE-1/AE-2)
+    Aes192,
+    /// AES-256 (WinZip AE-1/AE-2)
+    Aes256,
+    /// Legacy PKWARE ZipCrypto stream cipher
+    ZipCrypto,
+}
+
+impl ZipEncryption {
+    /// Determine the encryption scheme used by a ZIP entry, if any
+    fn from_entry<R>(file: &ZipFile<'_, R>) -> Option<Self> {
+        if let Some((aes_mode, _vendor_version)) = file.aes_mode() {
+            return Some(match aes_mode {
+                AesMode::Aes128 => Self::Aes128,
+                AesMode::Aes192 => Self::Aes192,
+                AesMode::Aes256 => Self::Aes256,
+            });
+        }
+
+        file.encrypted().then_some(Self::ZipCrypto)
+    }
+}
+
+impl fmt::Display for ZipEncryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Aes128 => write!(f, "AES-128"),
+            Self::Aes192 => write!(f, "AES-192"),
+            Self::Aes256 => write!(f, "AES-256"),
+            Self::ZipCrypto => write!(f, "ZipCrypto"),
+        }
+    }
 }
 
 /// Builder for creating ZIP archives
@@ -239,6 +984,18 @@ impl<W: Write + Seek> ZipArchiveBuilder<W> {
         Ok(())
     }
 
+    /// Start a new file using a specific compression codec and level
+    pub fn start_file_with_codec(
+        &mut self,
+        name: &str,
+        codec: CompressionCodec,
+        level: Option<i32>,
+    ) -> Result<()> {
+        let options = codec.to_options(level)?;
+        self.writer.start_file(name, options)?;
+        Ok(())
+    }
+
     /// Start a new file with custom options
     pub fn start_file_with_options(
         &mut self,
@@ -291,6 +1048,24 @@ impl<W: Write + Seek> ZipArchiveBuilder<W> {
         Ok(())
     }
 
+    /// Add a file from a path using a specific compression codec and level
+    pub async fn add_file_from_path_with_codec(
+        &mut self,
+        archive_path: &str,
+        file_path: impl AsRef<Path>,
+        codec: CompressionCodec,
+        level: Option<i32>,
+    ) -> Result<()> {
+        let file_path = file_path.as_ref();
+        let content = fs::read(file_path).await?;
+
+        let options = codec.to_options(level)?;
+        self.writer.start_file(archive_path, options)?;
+        self.writer.write_all(&content)?;
+
+        Ok(())
+    }
+
     /// Add a file from memory
     pub fn add_file_from_memory(&mut self, name: &str, data: &[u8]) -> Result<()> {
         let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
@@ -301,6 +1076,81 @@ impl<W: Write + Seek> ZipArchiveBuilder<W> {
         Ok(())
     }
 
+    /// Add a file from memory using a specific compression codec and level
+    pub fn add_file_from_memory_with_codec(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        codec: CompressionCodec,
+        level: Option<i32>,
+    ) -> Result<()> {
+        let options = codec.to_options(level)?;
+        self.writer.start_file(name, options)?;
+        self.writer.write_all(data)?;
+
+        Ok(())
+    }
+
+    /// Add a file from memory, attaching an Info-ZIP extended timestamp
+    /// (0x5455) extra field so `modified`/`accessed`/`created` survive the
+    /// round trip through [`ZipArchiveHandler::extract_to`] at second
+    /// resolution, instead of only the coarse 2-second-resolution DOS
+    /// timestamp ZIP entries carry by default
+    pub fn add_file_from_memory_with_timestamps(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        modified: SystemTime,
+        accessed: Option<SystemTime>,
+        created: Option<SystemTime>,
+    ) -> Result<()> {
+        let dos_time = DateTime::try_from(modified).unwrap_or_default();
+        let mut options = ExtendedFileOptions::from(
+            SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .last_modified_time(dos_time),
+        );
+        options
+            .add_extra_data(
+                0x5455,
+                encode_extended_timestamp(Some(modified), accessed, created),
+                false,
+            )
+            .map_err(|e| Error::invalid_archive(e.to_string()))?;
+
+        self.writer.start_file(name, options)?;
+        self.writer.write_all(data)?;
+        Ok(())
+    }
+
+    /// Add an AES-encrypted file from memory
+    pub fn add_file_from_memory_encrypted(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        options: SimpleFileOptions,
+    ) -> Result<()> {
+        self.writer.start_file(name, options)?;
+        self.writer.write_all(data)?;
+        Ok(())
+    }
+
+    /// Add a symlink entry
+    ///
+    /// The link target is stored as the entry's content and the entry's
+    /// Unix mode is set to the `S_IFLNK` file-type bits, matching the
+    /// encoding `extract_to` (with `allow_symlinks` enabled) expects when
+    /// recreating the symlink.
+    pub fn add_symlink(&mut self, archive_path: &str, target: impl AsRef<Path>) -> Result<()> {
+        let target = target.as_ref().to_string_lossy().into_owned();
+        // `unix_permissions` keeps only the 0o777 bits, so the S_IFLNK type
+        // bits are set through the writer's own symlink entry instead
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+        self.writer.add_symlink(archive_path, target, options)?;
+        Ok(())
+    }
+
     /// Add a directory entry
     pub fn add_directory(&mut self, name: &str) -> Result<()> {
         let dir_name = if name.ends_with('/') {
@@ -335,7 +1185,12 @@ impl<W: Write + Seek> ZipArchiveBuilder<W> {
                 format!("{}/{}", archive_prefix, file_name_str)
             };
 
-            if entry_path.is_dir() {
+            let metadata = fs::symlink_metadata(&entry_path).await?;
+
+            if metadata.is_symlink() {
+                let target = fs::read_link(&entry_path).await?;
+                self.add_symlink(&archive_path, &target)?;
+            } else if metadata.is_dir() {
                 self.add_directory(&archive_path)?;
                 self.add_directory_recursively(&archive_path, &entry_path)
                     .await?;
@@ -366,6 +1221,39 @@ impl<W: Write + Seek> ZipArchiveBuilder<W> {
             .compression_level(Some(level.into()))
     }
 
+    /// Create options for zopfli-backed maximum-compression Deflate
+    ///
+    /// Unlike the other `*_options` constructors here, this can fail: it
+    /// requires the `zip-deflate-zopfli` feature, since zopfli support is a
+    /// distinct backend in the zip crate rather than always available.
+    /// `iterations` trades CPU time for a smaller archive; output is
+    /// ordinary DEFLATE and decodes through the normal path.
+    pub fn zopfli_options(iterations: u32) -> Result<SimpleFileOptions> {
+        CompressionCodec::DeflateZopfli { iterations }.to_options(None)
+    }
+
+    /// Create options for an AES-encrypted entry
+    ///
+    /// Pair with [`Self::add_file_from_memory_encrypted`] to write a
+    /// password-protected entry using the modern WinZip AES scheme rather
+    /// than legacy ZipCrypto.
+    pub fn aes_options(level: AesMode, password: &str) -> SimpleFileOptions {
+        SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .with_aes_encryption(level, password)
+    }
+
+    /// Create options for a legacy ZipCrypto-encrypted entry
+    ///
+    /// ZipCrypto is much weaker than AES and is only supported for
+    /// compatibility with older unarchivers; prefer [`Self::aes_options`]
+    /// when the consumer supports it.
+    pub fn zipcrypto_options(password: &str) -> SimpleFileOptions {
+        SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .with_deprecated_encryption(password.as_bytes())
+    }
+
     /// Finish writing the archive and return the underlying writer
     pub fn finish(self) -> Result<W> {
         Ok(self.writer.finish()?)
@@ -435,6 +1323,61 @@ impl ZipArchiveBuilder<std::fs::File> {
         zip.finish()?;
         Ok(())
     }
+
+    /// Create a ZIP archive from a directory using a specific compression
+    /// codec and level for every entry
+    pub async fn create_from_directory_with_codec(
+        self,
+        source_dir: &Path,
+        target_path: &Path,
+        codec: CompressionCodec,
+        level: Option<i32>,
+    ) -> Result<()> {
+        use std::fs;
+
+        fn collect_files(dir: &Path) -> Result<Vec<PathBuf>> {
+            let mut files = Vec::new();
+            let entries = fs::read_dir(dir)?;
+
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+
+                if path.is_file() {
+                    files.push(path);
+                } else if path.is_dir() {
+                    let mut sub_files = collect_files(&path)?;
+                    files.append(&mut sub_files);
+                }
+            }
+
+            files.sort();
+            Ok(files)
+        }
+
+        let files = collect_files(source_dir)?;
+        let file = std::fs::File::create(target_path)?;
+        let mut zip = ZipWriter::new(file);
+
+        let options = codec.to_options(level)?;
+
+        for file_path in files {
+            let relative_path = file_path.strip_prefix(source_dir).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid file path: {}", e),
+                )
+            })?;
+
+            let file_content = tokio::fs::read(&file_path).await?;
+
+            zip.start_file(relative_path.to_string_lossy().as_ref(), options.clone())?;
+            zip.write_all(&file_content)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
 }
 
 /// Convenience constructor for ZIP handlers from memory
@@ -568,6 +1511,11 @@ mod tests {
             crc32: 12345,
             extra_data: Vec::new(),
             comment: String::new(),
+            encryption: None,
+            codec: CompressionCodec::Deflated,
+            modified: None,
+            accessed: None,
+            created: None,
         };
 
         assert_eq!(info.name, "test.txt");
@@ -575,5 +1523,384 @@ mod tests {
         assert_eq!(info.compressed_size, 80);
         assert!(!info.is_dir);
         assert!(info.is_file);
+        assert_eq!(info.encryption, None);
+    }
+
+    #[test]
+    fn test_zipcrypto_roundtrip() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        let options = ZipArchiveBuilder::<Cursor<Vec<u8>>>::zipcrypto_options("hunter2");
+        builder
+            .add_file_from_memory_encrypted("secret.txt", b"top secret", options)
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+
+        let content = handler
+            .read_file_with_password("secret.txt", "hunter2")
+            .unwrap();
+        assert_eq!(content, b"top secret");
+
+        let entries = handler.list_entries().unwrap();
+        assert_eq!(entries[0].encryption, Some(ZipEncryption::ZipCrypto));
+    }
+
+    #[test]
+    fn test_aes_roundtrip() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        let options = ZipArchiveBuilder::<Cursor<Vec<u8>>>::aes_options(AesMode::Aes256, "hunter2");
+        builder
+            .add_file_from_memory_encrypted("secret.txt", b"top secret", options)
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+
+        let content = handler
+            .read_file_with_password("secret.txt", "hunter2")
+            .unwrap();
+        assert_eq!(content, b"top secret");
+
+        let entries = handler.list_entries().unwrap();
+        assert_eq!(entries[0].encryption, Some(ZipEncryption::Aes256));
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_with_password() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        let options = ZipArchiveBuilder::<Cursor<Vec<u8>>>::aes_options(AesMode::Aes256, "hunter2");
+        builder
+            .add_file_from_memory_encrypted("secret.txt", b"top secret", options)
+            .unwrap();
+        builder
+            .add_file_from_memory("plain.txt", b"not secret")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let extracted = handler
+            .extract_to_with_password(temp_dir.path(), "hunter2")
+            .await
+            .unwrap();
+        assert_eq!(extracted.len(), 2);
+
+        let secret_content = std::fs::read(temp_dir.path().join("secret.txt")).unwrap();
+        assert_eq!(secret_content, b"top secret");
+    }
+
+    #[test]
+    fn test_codec_roundtrip_with_stored_and_deflated() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory_with_codec("stored.txt", b"abc", CompressionCodec::Stored, None)
+            .unwrap();
+        builder
+            .add_file_from_memory_with_codec(
+                "deflated.txt",
+                b"abc",
+                CompressionCodec::Deflated,
+                Some(9),
+            )
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+
+        let entries = handler.list_entries().unwrap();
+        let stored = entries.iter().find(|e| e.name == "stored.txt").unwrap();
+        let deflated = entries.iter().find(|e| e.name == "deflated.txt").unwrap();
+        assert_eq!(stored.codec, CompressionCodec::Stored);
+        assert_eq!(deflated.codec, CompressionCodec::Deflated);
+
+        assert_eq!(handler.read_file("stored.txt").unwrap(), b"abc");
+        assert_eq!(handler.read_file("deflated.txt").unwrap(), b"abc");
+    }
+
+    #[test]
+    fn test_codec_without_feature_reports_clear_error() {
+        #[cfg(not(feature = "zip-bzip2"))]
+        {
+            let err = CompressionCodec::Bzip2.to_options(None).unwrap_err();
+            assert!(err.to_string().contains("zip-bzip2"));
+        }
+    }
+
+    #[test]
+    fn test_deflate64_cannot_be_written() {
+        assert!(CompressionCodec::Deflate64.to_options(None).is_err());
+        assert!(CompressionCodec::Other.to_options(None).is_err());
+    }
+
+    #[test]
+    fn test_zopfli_options_without_feature_reports_clear_error() {
+        #[cfg(not(feature = "zip-deflate-zopfli"))]
+        {
+            let err = ZipArchiveBuilder::<Cursor<Vec<u8>>>::zopfli_options(15).unwrap_err();
+            assert!(err.to_string().contains("zip-deflate-zopfli"));
+        }
+    }
+
+    #[cfg(feature = "zip-deflate-zopfli")]
+    #[test]
+    fn test_zopfli_roundtrip() {
+        let options = ZipArchiveBuilder::<Cursor<Vec<u8>>>::zopfli_options(15).unwrap();
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory_encrypted("zopfli.txt", b"compress me please", options)
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        assert_eq!(
+            handler.read_file("zopfli.txt").unwrap(),
+            b"compress me please"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_rejects_parent_dir_traversal() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("../../etc/passwd", b"owned")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = handler.extract_to(temp_dir.path()).await;
+        assert!(matches!(result, Err(Error::UnsafePath { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_rejects_absolute_path() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("/etc/passwd", b"owned")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = handler.extract_to(temp_dir.path()).await;
+        assert!(matches!(result, Err(Error::UnsafePath { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_with_options_strip_components() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("wrapper/inner/file.txt", b"payload")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let options = ZipExtractOptions::default().with_strip_components(2);
+        let extracted = handler
+            .extract_to_with_options(temp_dir.path(), &options)
+            .await
+            .unwrap();
+
+        assert_eq!(extracted, vec![temp_dir.path().join("file.txt")]);
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("file.txt")).unwrap(),
+            b"payload"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_with_options_overwrite_false_skips_existing() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("file.txt", b"new content")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("file.txt"), b"original").unwrap();
+
+        let options = ZipExtractOptions::default().with_overwrite(false);
+        let extracted = handler
+            .extract_to_with_options(temp_dir.path(), &options)
+            .await
+            .unwrap();
+
+        assert!(extracted.is_empty());
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("file.txt")).unwrap(),
+            b"original"
+        );
+    }
+
+    #[test]
+    fn test_stream_handler_reads_entries_in_order() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("first.txt", b"one")
+            .unwrap();
+        builder
+            .add_file_from_memory("second.txt", b"two")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let data = cursor.into_inner();
+
+        let mut stream = ZipStreamHandler::new(Cursor::new(data));
+
+        let (first_info, mut first_file) = stream.next_entry().unwrap().unwrap();
+        let mut first_content = Vec::new();
+        std::io::Read::read_to_end(&mut first_file, &mut first_content).unwrap();
+        drop(first_file);
+        assert_eq!(first_info.name, "first.txt");
+        assert_eq!(first_content, b"one");
+
+        let (second_info, mut second_file) = stream.next_entry().unwrap().unwrap();
+        let mut second_content = Vec::new();
+        std::io::Read::read_to_end(&mut second_file, &mut second_content).unwrap();
+        drop(second_file);
+        assert_eq!(second_info.name, "second.txt");
+        assert_eq!(second_content, b"two");
+    }
+
+    #[tokio::test]
+    async fn test_stream_extract_to() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory("streamed.txt", b"streamed content")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let data = cursor.into_inner();
+        let temp_dir = TempDir::new().unwrap();
+
+        let extracted =
+            ZipStreamHandler::stream_extract_to(Cursor::new(data), temp_dir.path())
+                .await
+                .unwrap();
+
+        assert_eq!(extracted, vec![temp_dir.path().join("streamed.txt")]);
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("streamed.txt")).unwrap(),
+            b"streamed content"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extract_to_preserves_symlink_when_allowed() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder.add_file_from_memory("target.txt", b"real file").unwrap();
+        builder.add_symlink("link.txt", "target.txt").unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let options = ZipExtractOptions::default().with_allow_symlinks(true);
+        handler
+            .extract_to_with_options(temp_dir.path(), &options)
+            .await
+            .unwrap();
+
+        let link_path = temp_dir.path().join("link.txt");
+        let metadata = std::fs::symlink_metadata(&link_path).unwrap();
+        assert!(metadata.file_type().is_symlink());
+        assert_eq!(std::fs::read_link(&link_path).unwrap(), PathBuf::from("target.txt"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extract_to_skips_symlink_by_default() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder.add_symlink("link.txt", "target.txt").unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let extracted = handler.extract_to(temp_dir.path()).await.unwrap();
+        assert!(extracted.is_empty());
+        assert!(!temp_dir.path().join("link.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extract_to_rejects_escaping_symlink_target() {
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_symlink("link.txt", "../../../etc/passwd")
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let options = ZipExtractOptions::default().with_allow_symlinks(true);
+        let result = handler
+            .extract_to_with_options(temp_dir.path(), &options)
+            .await;
+        assert!(matches!(result, Err(Error::UnsafePath { .. })));
+    }
+
+    #[test]
+    fn test_parse_extra_field_timestamps_unix_extended() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let extra = encode_unix_extended_timestamp_block(modified);
+
+        let parsed = parse_extra_field_timestamps(&extra);
+        assert_eq!(parsed.modified, Some(modified));
+        assert_eq!(parsed.accessed, None);
+        assert_eq!(parsed.created, None);
+    }
+
+    fn encode_unix_extended_timestamp_block(modified: SystemTime) -> Vec<u8> {
+        let body = encode_extended_timestamp(Some(modified), None, None);
+        let mut extra = Vec::new();
+        extra.extend_from_slice(&0x5455u16.to_le_bytes());
+        extra.extend_from_slice(&(body.len() as u16).to_le_bytes());
+        extra.extend_from_slice(&body);
+        extra
+    }
+
+    #[test]
+    fn test_timestamp_roundtrip_through_builder_and_handler() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory_with_timestamps("file.txt", b"data", modified, None, None)
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+
+        let entries = handler.list_entries().unwrap();
+        assert_eq!(entries[0].modified, Some(modified));
+    }
+
+    #[tokio::test]
+    async fn test_extract_to_restores_modified_time() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let mut builder = ZipArchiveBuilder::new_in_memory();
+        builder
+            .add_file_from_memory_with_timestamps("file.txt", b"data", modified, None, None)
+            .unwrap();
+
+        let cursor = builder.finish().unwrap();
+        let mut handler = ZipArchiveHandler::from_memory(cursor.into_inner()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        handler.extract_to(temp_dir.path()).await.unwrap();
+
+        let metadata = std::fs::metadata(temp_dir.path().join("file.txt")).unwrap();
+        assert_eq!(metadata.modified().unwrap(), modified);
     }
 }
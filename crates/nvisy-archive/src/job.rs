@@ -0,0 +1,213 @@
+//! Cancellable, progress-reporting jobs for long-running archive operations
+//!
+//! Gated behind the `jobs` feature, which is the first dependency this crate
+//! takes on `nvisy-error`: [`ArchiveHandler`](crate::ArchiveHandler) implements
+//! [`Component`] so a caller can poll `current_status()`/`cached_status()`
+//! while a job runs, and each [`JobEvent`] is tagged with an [`UpdateSeverity`]
+//! so progress/completion/failure can be routed through the same monitoring
+//! path as any other component. [`CancellationToken`] lets a caller abort a
+//! running job between items; the job is responsible for cleaning up whatever
+//! partial output it produced.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use nvisy_error::status::{ComponentStatus, HealthStatus};
+use nvisy_error::UpdateSeverity;
+use tokio::sync::mpsc;
+
+/// Cooperative cancellation flag shared between a running job and its caller
+///
+/// Jobs check this between items rather than being preempted, so cancellation
+/// takes effect at the next checkpoint, not immediately.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new, not-yet-cancelled token
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of any job holding a clone of this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Check whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Incremental progress reported by a running job
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct JobProgress {
+    /// Number of entries fully processed so far
+    pub files_done: u64,
+    /// Number of bytes processed so far
+    pub bytes_done: u64,
+    /// Archive-relative path of the entry currently being processed
+    pub current_entry: Option<String>,
+}
+
+/// One update emitted by a running job on its progress channel
+#[derive(Debug, Clone)]
+pub enum JobEvent {
+    /// An entry finished processing; `progress` reflects the running totals
+    Progress(JobProgress),
+    /// The job finished successfully
+    Completed(JobProgress),
+    /// The job was cancelled via its [`CancellationToken`] before completing
+    Cancelled(JobProgress),
+    /// The job failed; `progress` reflects how far it got before failing
+    Failed { progress: JobProgress, message: String },
+}
+
+impl JobEvent {
+    /// The [`UpdateSeverity`] a monitor should treat this event with
+    pub fn severity(&self) -> UpdateSeverity {
+        match self {
+            Self::Progress(_) | Self::Completed(_) => UpdateSeverity::Info,
+            Self::Cancelled(_) => UpdateSeverity::Warning,
+            Self::Failed { .. } => UpdateSeverity::Error,
+        }
+    }
+}
+
+/// Handle to a job running on a background task
+///
+/// Progress updates arrive on `events` as the job runs; the final result is
+/// only available by awaiting [`Job::join`], since the underlying work runs
+/// on a [`tokio::task::spawn_blocking`] task rather than cooperatively.
+#[derive(Debug)]
+pub struct Job<T> {
+    /// Channel of progress/completion/failure events emitted by the job
+    pub events: mpsc::UnboundedReceiver<JobEvent>,
+    task: tokio::task::JoinHandle<crate::Result<T>>,
+}
+
+impl<T> Job<T> {
+    pub(crate) fn new(
+        events: mpsc::UnboundedReceiver<JobEvent>,
+        task: tokio::task::JoinHandle<crate::Result<T>>,
+    ) -> Self {
+        Self { events, task }
+    }
+
+    /// Wait for the job to finish and return its result
+    ///
+    /// Cancelling the job's token still surfaces through this as an error
+    /// (matching the [`JobEvent::Cancelled`] already sent on `events`), since
+    /// a cancelled job has no meaningful `T` to return.
+    pub async fn join(self) -> crate::Result<T> {
+        self.task
+            .await
+            .map_err(|e| crate::Error::other(format!("Job panicked: {e}")))?
+    }
+}
+
+/// Shared bookkeeping used by a job-instrumented operation to emit progress,
+/// update the owning [`ArchiveHandler`](crate::ArchiveHandler)'s cached
+/// [`ComponentStatus`], and check for cancellation
+///
+/// Constructed by job-launching methods (e.g.
+/// [`ArchiveHandler::pack_dedup_job`](crate::ArchiveHandler::pack_dedup_job))
+/// and threaded through to the blocking work they spawn.
+pub(crate) struct JobReporter {
+    tx: mpsc::UnboundedSender<JobEvent>,
+    cancel: CancellationToken,
+    status: Arc<Mutex<ComponentStatus>>,
+    progress: JobProgress,
+}
+
+impl JobReporter {
+    pub(crate) fn new(
+        tx: mpsc::UnboundedSender<JobEvent>,
+        cancel: CancellationToken,
+        status: Arc<Mutex<ComponentStatus>>,
+    ) -> Self {
+        Self {
+            tx,
+            cancel,
+            status,
+            progress: JobProgress::default(),
+        }
+    }
+
+    /// Check whether the caller has requested cancellation
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Record one finished entry and notify any listener
+    pub(crate) fn report_entry(&mut self, entry: impl Into<String>, bytes: u64) {
+        self.progress.files_done += 1;
+        self.progress.bytes_done += bytes;
+        self.progress.current_entry = Some(entry.into());
+        self.set_status(ComponentStatus::new(HealthStatus::Online));
+        let _ = self.tx.send(JobEvent::Progress(self.progress.clone()));
+    }
+
+    /// Mark the job as finished successfully
+    pub(crate) fn completed(&mut self) {
+        self.set_status(ComponentStatus::new(HealthStatus::Online));
+        let _ = self.tx.send(JobEvent::Completed(self.progress.clone()));
+    }
+
+    /// Mark the job as cancelled before it finished
+    pub(crate) fn cancelled(&mut self) {
+        self.set_status(
+            ComponentStatus::new(HealthStatus::Unknown).with_message("Job cancelled"),
+        );
+        let _ = self.tx.send(JobEvent::Cancelled(self.progress.clone()));
+    }
+
+    /// Mark the job as failed
+    pub(crate) fn failed(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        self.set_status(
+            ComponentStatus::new(HealthStatus::Offline).with_message(message.clone()),
+        );
+        let _ = self.tx.send(JobEvent::Failed {
+            progress: self.progress.clone(),
+            message,
+        });
+    }
+
+    fn set_status(&self, status: ComponentStatus) {
+        *self.status.lock().expect("job status mutex poisoned") = status;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancellation_token_starts_uncancelled() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancellation_token_shared_across_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_job_event_severity() {
+        let progress = JobProgress::default();
+        assert_eq!(JobEvent::Progress(progress.clone()).severity(), UpdateSeverity::Info);
+        assert_eq!(JobEvent::Completed(progress.clone()).severity(), UpdateSeverity::Info);
+        assert_eq!(JobEvent::Cancelled(progress.clone()).severity(), UpdateSeverity::Warning);
+        assert_eq!(
+            JobEvent::Failed { progress, message: "oops".to_string() }.severity(),
+            UpdateSeverity::Error
+        );
+    }
+}
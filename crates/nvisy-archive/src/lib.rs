@@ -4,12 +4,24 @@
 //! including ZIP, TAR, and other compressed archive types. It supports both
 //! reading from files and memory, with flexible loading options.
 
+use std::path::PathBuf;
+
+#[cfg(feature = "dedup")]
+pub mod dedup;
 pub mod file;
 pub mod handler;
+#[cfg(feature = "jobs")]
+pub mod job;
 
 // Re-exports for convenience
-pub use file::{ArchiveFile, ArchiveType};
+#[cfg(feature = "dedup")]
+pub use dedup::{Chunk, ChunkHash, DedupStats};
+pub use file::{
+    ArchiveFile, ArchiveType, CompressionFilter, ContainerFormat, ExtractOptions, PackEntry,
+};
 pub use handler::ArchiveHandler;
+#[cfg(feature = "jobs")]
+pub use job::{CancellationToken, Job, JobEvent, JobProgress};
 
 /// Archive processing errors
 ///
@@ -31,6 +43,15 @@ pub enum Error {
     #[error("Unsupported archive format: {format}")]
     UnsupportedFormat { format: String },
 
+    /// A container/filter combination was detected (e.g. via
+    /// [`ArchiveType::detect`](file::ArchiveType::detect)) but no decoder
+    /// is available for it
+    #[error("Unsupported archive format: {container} container with {filter} compression")]
+    UnsupportedArchiveFormat {
+        container: ContainerFormat,
+        filter: CompressionFilter,
+    },
+
     /// Invalid archive structure or data
     #[error("Invalid archive: {message}")]
     InvalidArchive { message: String },
@@ -39,6 +60,15 @@ pub enum Error {
     #[error("Entry not found: {name}")]
     EntryNotFound { name: String },
 
+    /// Archive entry would escape the extraction directory
+    #[error("Unsafe archive entry path: {path}")]
+    UnsafeEntry { path: String },
+
+    /// Archive entry resolved to a path outside the extraction directory
+    /// after canonicalization
+    #[error("Entry {entry:?} resolves outside the extraction directory: {resolved:?}")]
+    UnsafePath { entry: String, resolved: PathBuf },
+
     /// Permission denied
     #[error("Permission denied: {message}")]
     PermissionDenied { message: String },
@@ -51,6 +81,10 @@ pub enum Error {
     #[error("Resource limit exceeded: {message}")]
     ResourceLimit { message: String },
 
+    /// Extraction size or compression-ratio limit exceeded
+    #[error("Extraction size limit exceeded: {message}")]
+    SizeLimitExceeded { message: String },
+
     /// Generic error with custom message
     #[error("{message}")]
     Other { message: String },
@@ -64,6 +98,12 @@ impl Error {
         }
     }
 
+    /// Create a new unsupported archive format error from a detected
+    /// container/filter pair
+    pub fn unsupported_archive_format(container: ContainerFormat, filter: CompressionFilter) -> Self {
+        Self::UnsupportedArchiveFormat { container, filter }
+    }
+
     /// Create a new invalid archive error
     pub fn invalid_archive(message: impl Into<String>) -> Self {
         Self::InvalidArchive {
@@ -76,6 +116,19 @@ impl Error {
         Self::EntryNotFound { name: name.into() }
     }
 
+    /// Create a new unsafe entry error
+    pub fn unsafe_entry(path: impl Into<String>) -> Self {
+        Self::UnsafeEntry { path: path.into() }
+    }
+
+    /// Create a new unsafe path error
+    pub fn unsafe_path(entry: impl Into<String>, resolved: impl Into<PathBuf>) -> Self {
+        Self::UnsafePath {
+            entry: entry.into(),
+            resolved: resolved.into(),
+        }
+    }
+
     /// Create a new permission denied error
     pub fn permission_denied(message: impl Into<String>) -> Self {
         Self::PermissionDenied {
@@ -97,6 +150,13 @@ impl Error {
         }
     }
 
+    /// Create a new size limit exceeded error
+    pub fn size_limit_exceeded(message: impl Into<String>) -> Self {
+        Self::SizeLimitExceeded {
+            message: message.into(),
+        }
+    }
+
     /// Create a new generic error
     pub fn other(message: impl Into<String>) -> Self {
         Self::Other {
@@ -117,12 +177,25 @@ mod tests {
         let error = Error::unsupported_format("custom");
         assert!(matches!(error, Error::UnsupportedFormat { .. }));
 
+        let error = Error::unsupported_archive_format(ContainerFormat::Rar, CompressionFilter::None);
+        assert!(matches!(error, Error::UnsupportedArchiveFormat { .. }));
+        assert_eq!(
+            error.to_string(),
+            "Unsupported archive format: RAR container with no compression"
+        );
+
         let error = Error::invalid_archive("test message");
         assert!(matches!(error, Error::InvalidArchive { .. }));
 
         let error = Error::entry_not_found("missing.txt");
         assert!(matches!(error, Error::EntryNotFound { .. }));
 
+        let error = Error::unsafe_entry("../../etc/passwd");
+        assert!(matches!(error, Error::UnsafeEntry { .. }));
+
+        let error = Error::unsafe_path("../../etc/passwd", "/tmp/out/etc/passwd");
+        assert!(matches!(error, Error::UnsafePath { .. }));
+
         let error = Error::permission_denied("access denied");
         assert!(matches!(error, Error::PermissionDenied { .. }));
 
@@ -132,6 +205,9 @@ mod tests {
         let error = Error::resource_limit("too big");
         assert!(matches!(error, Error::ResourceLimit { .. }));
 
+        let error = Error::size_limit_exceeded("entry too large");
+        assert!(matches!(error, Error::SizeLimitExceeded { .. }));
+
         let error = Error::other("generic error");
         assert!(matches!(error, Error::Other { .. }));
     }
@@ -0,0 +1,111 @@
+//! Compound (multi-part) archive extension detection
+//!
+//! A filename like `archive.tar.gz` is a TAR container with a gzip
+//! compression filter applied on top, not a bare `.gz` file — but naive
+//! "extension is whatever follows the last dot" detection only ever sees
+//! the `gz` part. This module matches a filename's tail against an ordered
+//! table of known compound suffixes before any single-extension fallback
+//! is considered.
+
+use strum::{Display, EnumIter, EnumString};
+
+/// Outer container format recognized from a compound extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "lowercase")]
+pub enum ContainerFormat {
+    /// TAR container (`.tar`, or a compressed variant such as `.tar.gz`/`.tgz`)
+    Tar,
+}
+
+/// Compression filter applied on top of a [`ContainerFormat`], recognized
+/// from a compound extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "lowercase")]
+pub enum CompressionFilter {
+    /// Gzip compression (`.gz`)
+    Gzip,
+    /// Bzip2 compression (`.bz2`)
+    Bzip2,
+    /// XZ/LZMA2 compression (`.xz`)
+    Xz,
+    /// Zstandard compression (`.zst`)
+    Zstd,
+    /// LZ4 compression (`.lz4`)
+    Lz4,
+}
+
+/// Known compound suffixes, each paired with the container/filter it
+/// implies. Checked longest-form-first so `.tar.gz` is matched before a
+/// caller could ever fall back to treating it as a bare `.gz`.
+const COMPOUND_SUFFIXES: &[(&str, ContainerFormat, CompressionFilter)] = &[
+    (".tar.gz", ContainerFormat::Tar, CompressionFilter::Gzip),
+    (".tar.bz2", ContainerFormat::Tar, CompressionFilter::Bzip2),
+    (".tar.xz", ContainerFormat::Tar, CompressionFilter::Xz),
+    (".tar.zst", ContainerFormat::Tar, CompressionFilter::Zstd),
+    (".tar.lz4", ContainerFormat::Tar, CompressionFilter::Lz4),
+    (".tgz", ContainerFormat::Tar, CompressionFilter::Gzip),
+    (".tbz2", ContainerFormat::Tar, CompressionFilter::Bzip2),
+    (".txz", ContainerFormat::Tar, CompressionFilter::Xz),
+    (".tzst", ContainerFormat::Tar, CompressionFilter::Zstd),
+];
+
+/// Match `filename`'s tail, case-insensitively, against [`COMPOUND_SUFFIXES`]
+///
+/// Returns the matched suffix (with its leading dot) alongside the
+/// container/filter pair it implies.
+pub(crate) fn match_compound_suffix(
+    filename: &str,
+) -> Option<(&'static str, ContainerFormat, CompressionFilter)> {
+    let lower = filename.to_lowercase();
+    COMPOUND_SUFFIXES
+        .iter()
+        .find(|(suffix, _, _)| lower.ends_with(suffix) && lower.len() > suffix.len())
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_tar_dot_gz() {
+        let (suffix, container, filter) = match_compound_suffix("archive.tar.gz").unwrap();
+        assert_eq!(suffix, ".tar.gz");
+        assert_eq!(container, ContainerFormat::Tar);
+        assert_eq!(filter, CompressionFilter::Gzip);
+    }
+
+    #[test]
+    fn test_matches_tgz_short_form() {
+        let (suffix, container, filter) = match_compound_suffix("backup.tgz").unwrap();
+        assert_eq!(suffix, ".tgz");
+        assert_eq!(container, ContainerFormat::Tar);
+        assert_eq!(filter, CompressionFilter::Gzip);
+    }
+
+    #[test]
+    fn test_matches_are_case_insensitive() {
+        assert!(match_compound_suffix("ARCHIVE.TAR.GZ").is_some());
+    }
+
+    #[test]
+    fn test_no_match_for_plain_extension() {
+        assert!(match_compound_suffix("document.pdf").is_none());
+        assert!(match_compound_suffix("data.gz").is_none());
+    }
+
+    #[test]
+    fn test_no_match_for_unrelated_multi_dot_name() {
+        assert!(match_compound_suffix("my.report.final.pdf").is_none());
+    }
+
+    #[test]
+    fn test_no_match_for_dotfile() {
+        assert!(match_compound_suffix(".gitignore").is_none());
+    }
+
+    #[test]
+    fn test_no_match_when_filename_is_only_the_suffix() {
+        assert!(match_compound_suffix(".tar.gz").is_none());
+    }
+}
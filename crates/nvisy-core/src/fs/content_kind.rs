@@ -22,6 +22,8 @@ pub enum ContentKind {
     Document,
     /// Image files
     Image,
+    /// Compressed or archive content (ZIP, gzip, bzip2, xz, zstd, ...)
+    Archive,
     /// Unknown or unsupported content type
     #[default]
     Unknown,
@@ -35,6 +37,47 @@ impl ContentKind {
             .unwrap_or(ContentKind::Unknown)
     }
 
+    /// Detect content kind by sniffing a content prefix's magic bytes
+    ///
+    /// Defers to [`SupportedFormat::from_bytes`] for the document/image/text
+    /// signatures it already recognizes (so a ZIP-based `.docx` is still
+    /// classified as [`Self::Document`]), and additionally recognizes the
+    /// compressed-archive signatures `SupportedFormat` has no variant for
+    /// (plain ZIP, gzip, bzip2, xz, zstd). Returns [`Self::Unknown`] when
+    /// nothing matches, which also covers non-UTF-8 binary data that isn't
+    /// one of the recognized signatures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nvisy_core::fs::ContentKind;
+    ///
+    /// assert_eq!(ContentKind::from_bytes(b"PK\x03\x04"), ContentKind::Archive);
+    /// assert_eq!(ContentKind::from_bytes(b"%PDF-1.7"), ContentKind::Document);
+    /// assert_eq!(ContentKind::from_bytes(b"hello world"), ContentKind::Text);
+    /// ```
+    pub fn from_bytes(data: &[u8]) -> Self {
+        if let Some(format) = SupportedFormat::from_bytes(data) {
+            return format.content_kind();
+        }
+
+        if Self::looks_like_compressed_archive(data) {
+            return ContentKind::Archive;
+        }
+
+        ContentKind::Unknown
+    }
+
+    /// Whether `data` starts with one of the common compression/archive
+    /// magic numbers that `SupportedFormat` has no variant for
+    fn looks_like_compressed_archive(data: &[u8]) -> bool {
+        data.starts_with(b"PK\x03\x04")
+            || data.starts_with(&[0x1f, 0x8b])
+            || data.starts_with(b"BZh")
+            || data.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00])
+            || data.starts_with(&[0x28, 0xb5, 0x2f, 0xfd])
+    }
+
     /// Check if this content kind represents text-based content
     pub fn is_text_based(&self) -> bool {
         matches!(self, ContentKind::Text)
@@ -88,15 +131,57 @@ mod tests {
         assert_eq!(ContentKind::Text.to_string(), "text");
         assert_eq!(ContentKind::Document.to_string(), "document");
         assert_eq!(ContentKind::Image.to_string(), "image");
+        assert_eq!(ContentKind::Archive.to_string(), "archive");
         assert_eq!(ContentKind::Unknown.to_string(), "unknown");
     }
 
+    #[test]
+    fn test_content_kind_from_bytes_archive_signatures() {
+        assert_eq!(ContentKind::from_bytes(b"PK\x03\x04"), ContentKind::Archive);
+        assert_eq!(
+            ContentKind::from_bytes(&[0x1f, 0x8b, 0x08, 0x00]),
+            ContentKind::Archive
+        );
+        assert_eq!(ContentKind::from_bytes(b"BZh91AY"), ContentKind::Archive);
+        assert_eq!(
+            ContentKind::from_bytes(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]),
+            ContentKind::Archive
+        );
+        assert_eq!(
+            ContentKind::from_bytes(&[0x28, 0xb5, 0x2f, 0xfd]),
+            ContentKind::Archive
+        );
+    }
+
+    #[test]
+    fn test_content_kind_from_bytes_document_and_image() {
+        assert_eq!(ContentKind::from_bytes(b"%PDF-1.7"), ContentKind::Document);
+        assert_eq!(
+            ContentKind::from_bytes(&[0xFF, 0xD8, 0xFF]),
+            ContentKind::Image
+        );
+    }
+
+    #[test]
+    fn test_content_kind_from_bytes_text_fallback() {
+        assert_eq!(ContentKind::from_bytes(b"hello world"), ContentKind::Text);
+    }
+
+    #[test]
+    fn test_content_kind_from_bytes_unknown() {
+        assert_eq!(
+            ContentKind::from_bytes(&[0x00, 0xff, 0x13, 0x37]),
+            ContentKind::Unknown
+        );
+    }
+
     #[test]
     fn test_content_kind_text_classification() {
         assert!(ContentKind::Text.is_text_based());
         assert!(!ContentKind::Document.is_text_based());
         assert!(!ContentKind::Unknown.is_text_based());
         assert!(!ContentKind::Image.is_text_based());
+        assert!(!ContentKind::Archive.is_text_based());
     }
 
     #[test]
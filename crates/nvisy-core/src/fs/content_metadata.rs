@@ -4,13 +4,29 @@
 //! about content files, including paths, content types, and source tracking.
 
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use super::{ContentKind, SupportedFormat};
+use super::compound_format::match_compound_suffix;
+use super::{CompressionFilter, ContainerFormat, ContentKind, SupportedFormat};
 use crate::path::ContentSource;
 
+/// A weak-validator fingerprint derived from filesystem metadata
+///
+/// Mirrors how static file servers build a cache validator from `(len,
+/// mtime)` rather than hashing the full content: cheap to compute, but only
+/// as precise as the filesystem's mtime resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContentFingerprint {
+    /// Content length in bytes
+    pub len: u64,
+    /// Last-modified time, as reported by the filesystem
+    pub mtime: SystemTime,
+}
+
 /// Metadata associated with content files
 ///
 /// This struct stores metadata about content including its source identifier,
@@ -22,6 +38,8 @@ pub struct ContentMetadata {
     pub content_source: ContentSource,
     /// Optional path to the source file
     pub source_path: Option<PathBuf>,
+    /// Weak-validator fingerprint derived from filesystem metadata, if known
+    pub fingerprint: Option<ContentFingerprint>,
 }
 
 impl ContentMetadata {
@@ -39,6 +57,7 @@ impl ContentMetadata {
         Self {
             content_source,
             source_path: None,
+            fingerprint: None,
         }
     }
 
@@ -58,9 +77,71 @@ impl ContentMetadata {
         Self {
             content_source,
             source_path: Some(path.into()),
+            fingerprint: None,
         }
     }
 
+    /// Create content metadata with a file path and a fingerprint derived
+    /// from the file's [`std::fs::Metadata`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nvisy_core::{fs::ContentMetadata, ContentSource};
+    /// use std::path::PathBuf;
+    ///
+    /// # fn example() -> std::io::Result<()> {
+    /// let source = ContentSource::new();
+    /// let path = PathBuf::from("document.pdf");
+    /// let fs_metadata = std::fs::metadata(&path)?;
+    /// let metadata = ContentMetadata::with_metadata(source, path, fs_metadata);
+    /// assert!(metadata.weak_etag().is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_metadata(
+        content_source: ContentSource,
+        path: impl Into<PathBuf>,
+        metadata: std::fs::Metadata,
+    ) -> Self {
+        let fingerprint = metadata.modified().ok().map(|mtime| ContentFingerprint {
+            len: metadata.len(),
+            mtime,
+        });
+
+        Self {
+            content_source,
+            source_path: Some(path.into()),
+            fingerprint,
+        }
+    }
+
+    /// A weak cache validator derived from the stored [`ContentFingerprint`],
+    /// formatted as `W/"{len:x}-{mtime_secs:x}.{mtime_nanos:x}"`
+    ///
+    /// Returns `None` if no fingerprint is set (e.g. this metadata wasn't
+    /// built via [`Self::with_metadata`]).
+    pub fn weak_etag(&self) -> Option<String> {
+        let fingerprint = self.fingerprint?;
+        let since_epoch = fingerprint
+            .mtime
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Some(format!(
+            "W/\"{:x}-{:x}.{:x}\"",
+            fingerprint.len,
+            since_epoch.as_secs(),
+            since_epoch.subsec_nanos()
+        ))
+    }
+
+    /// A strong cache validator built from an already-computed content hash
+    /// (e.g. a SHA-256 digest), formatted as a quoted hex string
+    pub fn strong_etag_from_hash(hash: &[u8]) -> String {
+        format!("\"{}\"", hex::encode(hash))
+    }
+
     /// Get the file extension if available
     pub fn file_extension(&self) -> Option<&str> {
         self.source_path
@@ -82,11 +163,65 @@ impl ContentMetadata {
     /// assert_eq!(metadata.content_kind(), Some(ContentKind::Image));
     /// ```
     pub fn content_kind(&self) -> ContentKind {
+        if self.split_format().is_some() {
+            return ContentKind::Archive;
+        }
+
         self.file_extension()
             .map(ContentKind::from_file_extension)
             .unwrap_or_default()
     }
 
+    /// Get the compound (multi-part) extension, if the filename ends with
+    /// one of the known container+compression suffixes (`.tar.gz`, `.tgz`,
+    /// ...)
+    ///
+    /// Unlike [`Self::file_extension`], which only looks at the last path
+    /// component after the final dot, this matches the filename's tail
+    /// against an ordered table of compound suffixes first, so
+    /// `archive.tar.gz` reports `"tar.gz"` rather than just `"gz"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nvisy_core::{fs::ContentMetadata, ContentSource};
+    /// use std::path::PathBuf;
+    ///
+    /// let source = ContentSource::new();
+    /// let metadata = ContentMetadata::with_path(source, PathBuf::from("archive.tar.gz"));
+    /// assert_eq!(metadata.compound_extension(), Some("tar.gz"));
+    /// ```
+    pub fn compound_extension(&self) -> Option<&'static str> {
+        let filename = self.filename()?;
+        match_compound_suffix(filename).map(|(suffix, _, _)| suffix.trim_start_matches('.'))
+    }
+
+    /// Split the filename's extension into a container format and outer
+    /// compression filter, if it matches a known compound suffix
+    ///
+    /// Returns `None` for filenames with no extension, a single
+    /// (non-compound) extension, or an unrecognized extension. This is
+    /// independent of [`Self::supported_format`], since `.tar.gz` never
+    /// maps to a [`SupportedFormat`] variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nvisy_core::{fs::{ContentMetadata, ContainerFormat, CompressionFilter}, ContentSource};
+    /// use std::path::PathBuf;
+    ///
+    /// let source = ContentSource::new();
+    /// let metadata = ContentMetadata::with_path(source, PathBuf::from("backup.tgz"));
+    /// assert_eq!(
+    ///     metadata.split_format(),
+    ///     Some((ContainerFormat::Tar, CompressionFilter::Gzip))
+    /// );
+    /// ```
+    pub fn split_format(&self) -> Option<(ContainerFormat, CompressionFilter)> {
+        let filename = self.filename()?;
+        match_compound_suffix(filename).map(|(_, container, filter)| (container, filter))
+    }
+
     /// Get the filename if available
     pub fn filename(&self) -> Option<&str> {
         self.source_path
@@ -201,6 +336,92 @@ mod tests {
         assert_eq!(metadata.supported_format(), Some(SupportedFormat::Png));
     }
 
+    #[test]
+    fn test_compound_extension_detects_tar_dot_gz() {
+        let source = ContentSource::new();
+        let metadata = ContentMetadata::with_path(source, PathBuf::from("archive.tar.gz"));
+
+        assert_eq!(metadata.compound_extension(), Some("tar.gz"));
+        assert_eq!(
+            metadata.split_format(),
+            Some((ContainerFormat::Tar, CompressionFilter::Gzip))
+        );
+        assert_eq!(metadata.content_kind(), ContentKind::Archive);
+    }
+
+    #[test]
+    fn test_compound_extension_detects_short_forms() {
+        let source = ContentSource::new();
+        let metadata = ContentMetadata::with_path(source, PathBuf::from("data.tzst"));
+
+        assert_eq!(metadata.compound_extension(), Some("tzst"));
+        assert_eq!(
+            metadata.split_format(),
+            Some((ContainerFormat::Tar, CompressionFilter::Zstd))
+        );
+    }
+
+    #[test]
+    fn test_compound_extension_is_case_insensitive() {
+        let source = ContentSource::new();
+        let metadata = ContentMetadata::with_path(source, PathBuf::from("BACKUP.TAR.BZ2"));
+
+        assert_eq!(
+            metadata.split_format(),
+            Some((ContainerFormat::Tar, CompressionFilter::Bzip2))
+        );
+    }
+
+    #[test]
+    fn test_compound_extension_none_for_dotfile() {
+        let source = ContentSource::new();
+        let metadata = ContentMetadata::with_path(source, PathBuf::from(".gitignore"));
+
+        assert_eq!(metadata.file_extension(), None);
+        assert_eq!(metadata.compound_extension(), None);
+        assert_eq!(metadata.split_format(), None);
+    }
+
+    #[test]
+    fn test_compound_extension_none_for_unrelated_multi_dot_name() {
+        let source = ContentSource::new();
+        let metadata = ContentMetadata::with_path(source, PathBuf::from("my.report.final.pdf"));
+
+        assert_eq!(metadata.file_extension(), Some("pdf"));
+        assert_eq!(metadata.compound_extension(), None);
+        assert_eq!(metadata.split_format(), None);
+    }
+
+    #[test]
+    fn test_with_metadata_populates_fingerprint_and_weak_etag() {
+        let path = std::env::temp_dir().join("nvisy_core_content_metadata_etag_test.txt");
+        std::fs::write(&path, b"hello world").unwrap();
+        let fs_metadata = std::fs::metadata(&path).unwrap();
+
+        let source = ContentSource::new();
+        let metadata = ContentMetadata::with_metadata(source, &path, fs_metadata);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(metadata.fingerprint.is_some());
+        let etag = metadata.weak_etag().unwrap();
+        assert!(etag.starts_with("W/\""));
+        assert!(etag.contains(&format!("{:x}", b"hello world".len())));
+    }
+
+    #[test]
+    fn test_weak_etag_none_without_fingerprint() {
+        let source = ContentSource::new();
+        let metadata = ContentMetadata::with_path(source, PathBuf::from("document.pdf"));
+
+        assert_eq!(metadata.weak_etag(), None);
+    }
+
+    #[test]
+    fn test_strong_etag_from_hash_formats_as_quoted_hex() {
+        let etag = ContentMetadata::strong_etag_from_hash(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(etag, "\"deadbeef\"");
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serde_serialization() {
@@ -0,0 +1,96 @@
+//! Concrete handling controls derived from a data sensitivity level
+//!
+//! This module turns the boolean `requires_*` predicates on [`DataSensitivity`]
+//! into a single serializable object, so downstream code can attach one
+//! computed policy to a record instead of calling four separate predicates.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::fs::DataSensitivity;
+
+/// Concrete handling requirements implied by a [`DataSensitivity`] level
+///
+/// # Examples
+///
+/// ```rust
+/// use nvisy_core::fs::{DataPolicy, DataSensitivity};
+///
+/// let policy = DataPolicy::for_sensitivity(DataSensitivity::High);
+/// assert!(policy.encrypt);
+/// assert!(policy.access_log);
+/// assert_eq!(policy.max_retention_days, Some(90));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DataPolicy {
+    /// The sensitivity level this policy was derived from
+    pub sensitivity: DataSensitivity,
+    /// Whether data at this sensitivity must be encrypted at rest and in transit
+    pub encrypt: bool,
+    /// Whether access to this data must be logged for audit purposes
+    pub access_log: bool,
+    /// Maximum number of days this data may be retained (`None` = indefinite)
+    pub max_retention_days: Option<u32>,
+    /// Whether this data requires regulatory compliance oversight
+    pub compliance_oversight: bool,
+}
+
+impl DataPolicy {
+    /// Derive the required controls for a given sensitivity level
+    pub fn for_sensitivity(sensitivity: DataSensitivity) -> Self {
+        Self {
+            sensitivity,
+            encrypt: sensitivity.requires_encryption(),
+            access_log: sensitivity.requires_access_logging(),
+            max_retention_days: sensitivity.max_retention_days(),
+            compliance_oversight: sensitivity.requires_compliance_oversight(),
+        }
+    }
+
+    /// Derive the required controls for the combined sensitivity of several parts
+    ///
+    /// Equivalent to `DataPolicy::for_sensitivity(DataSensitivity::from_iter(levels))`.
+    pub fn for_combined(levels: impl IntoIterator<Item = DataSensitivity>) -> Self {
+        Self::for_sensitivity(DataSensitivity::from_iter(levels))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_for_high_sensitivity() {
+        let policy = DataPolicy::for_sensitivity(DataSensitivity::High);
+        assert!(policy.encrypt);
+        assert!(policy.access_log);
+        assert!(policy.compliance_oversight);
+        assert_eq!(policy.max_retention_days, Some(90));
+    }
+
+    #[test]
+    fn test_policy_for_none_sensitivity() {
+        let policy = DataPolicy::for_sensitivity(DataSensitivity::None);
+        assert!(!policy.encrypt);
+        assert!(!policy.access_log);
+        assert!(!policy.compliance_oversight);
+        assert_eq!(policy.max_retention_days, None);
+    }
+
+    #[test]
+    fn test_policy_for_medium_requires_encryption_but_not_logging() {
+        let policy = DataPolicy::for_sensitivity(DataSensitivity::Medium);
+        assert!(policy.encrypt);
+        assert!(!policy.access_log);
+        assert!(!policy.compliance_oversight);
+    }
+
+    #[test]
+    fn test_policy_for_combined_uses_highest_sensitivity() {
+        let policy =
+            DataPolicy::for_combined(vec![DataSensitivity::Low, DataSensitivity::High]);
+        assert_eq!(policy.sensitivity, DataSensitivity::High);
+        assert!(policy.encrypt);
+    }
+}
@@ -118,6 +118,44 @@ impl DataSensitivity {
             _ => None,
         }
     }
+
+    /// Combine with another sensitivity level, keeping the higher of the two.
+    ///
+    /// Composite data (a record with several fields, say) inherits the
+    /// sensitivity of its most sensitive part, so `combine` saturates at
+    /// `High`: combining anything with `High` always yields `High`.
+    ///
+    /// ```rust
+    /// use nvisy_core::DataSensitivity;
+    ///
+    /// assert_eq!(
+    ///     DataSensitivity::Low.combine(DataSensitivity::Medium),
+    ///     DataSensitivity::Medium
+    /// );
+    /// assert_eq!(
+    ///     DataSensitivity::High.combine(DataSensitivity::None),
+    ///     DataSensitivity::High
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn combine(self, other: DataSensitivity) -> DataSensitivity {
+        if (self as u8) >= (other as u8) {
+            self
+        } else {
+            other
+        }
+    }
+
+    /// Combine an iterator of sensitivity levels into the single highest level.
+    ///
+    /// Returns `DataSensitivity::None` for an empty iterator, since that's the
+    /// identity element for [`DataSensitivity::combine`].
+    #[must_use]
+    pub fn from_iter(levels: impl IntoIterator<Item = DataSensitivity>) -> DataSensitivity {
+        levels
+            .into_iter()
+            .fold(DataSensitivity::None, DataSensitivity::combine)
+    }
 }
 
 impl PartialOrd for DataSensitivity {
@@ -218,4 +256,35 @@ mod tests {
         let deserialized: DataSensitivity = serde_json::from_str(&json).unwrap();
         assert_eq!(level, deserialized);
     }
+
+    #[test]
+    fn test_combine_keeps_higher_level() {
+        assert_eq!(
+            DataSensitivity::Low.combine(DataSensitivity::Medium),
+            DataSensitivity::Medium
+        );
+        assert_eq!(
+            DataSensitivity::Medium.combine(DataSensitivity::Low),
+            DataSensitivity::Medium
+        );
+    }
+
+    #[test]
+    fn test_combine_saturates_at_high() {
+        assert_eq!(
+            DataSensitivity::High.combine(DataSensitivity::None),
+            DataSensitivity::High
+        );
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let levels = vec![
+            DataSensitivity::Low,
+            DataSensitivity::High,
+            DataSensitivity::Medium,
+        ];
+        assert_eq!(DataSensitivity::from_iter(levels), DataSensitivity::High);
+        assert_eq!(DataSensitivity::from_iter(vec![]), DataSensitivity::None);
+    }
 }
@@ -0,0 +1,235 @@
+//! Two-tier fast/slow format matching
+//!
+//! This module provides [`FormatMatcher`], which separates a cheap
+//! extension-only "fast" classification path from a more accurate
+//! MIME-type/magic-byte "slow" path, mirroring the fast-vs-slow matcher
+//! split used by content-routing tools: trust the fast path by default,
+//! but let an accurate slow-path signal override or merge with it.
+
+use super::ContentKind;
+
+/// A file extension matcher used on the fast path
+///
+/// Thin wrapper around the extension string (without the leading dot) so
+/// [`FormatMatcher`]'s fields read as a list of matchers rather than a list
+/// of bare strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension(pub String);
+
+impl Extension {
+    /// Create a new extension matcher
+    pub fn new(extension: impl Into<String>) -> Self {
+        Self(extension.into())
+    }
+
+    /// Whether `candidate` (a file extension, without the leading dot)
+    /// matches this extension, case-insensitively
+    pub fn matches(&self, candidate: &str) -> bool {
+        self.0.eq_ignore_ascii_case(candidate)
+    }
+}
+
+/// A slow-path matcher: either a MIME type or a leading magic-byte signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MimeOrSignature {
+    /// Match against a `Content-Type`/MIME type string
+    Mime(String),
+    /// Match against a leading magic-byte signature
+    Signature(Vec<u8>),
+}
+
+impl MimeOrSignature {
+    /// Whether this matcher accepts the given content, using whichever of
+    /// `mime_type`/`content_prefix` applies to its own variant
+    pub fn matches(&self, mime_type: Option<&str>, content_prefix: &[u8]) -> bool {
+        match self {
+            Self::Mime(expected) => mime_type.is_some_and(|m| expected.eq_ignore_ascii_case(m)),
+            Self::Signature(expected) => content_prefix.starts_with(expected),
+        }
+    }
+}
+
+/// A single extension-to-kind mapping
+type FastRule = (Extension, ContentKind);
+/// A single MIME/signature-to-kind mapping
+type SlowRule = (MimeOrSignature, ContentKind);
+
+/// Two-tier format matcher combining a cheap extension-only "fast" path
+/// with a more accurate MIME-type/magic-byte "slow" path
+///
+/// Formats that are reliably identified by extension alone (e.g. `.tar`,
+/// which has no distinctive magic number of its own) can rely solely on
+/// `fast_matchers`. Formats where the extension is easily spoofed or
+/// ambiguous (e.g. `.zip`) should also register a `slow_matchers` rule, so
+/// that [`Self::resolve`] can verify or override the fast guess once
+/// accurate detection is available.
+#[derive(Debug, Clone, Default)]
+pub struct FormatMatcher {
+    /// Extension-based rules, consulted cheaply without reading file content
+    pub fast_matchers: Vec<FastRule>,
+    /// MIME-type or magic-byte rules, consulted only when the caller
+    /// reports that accurate (slow-path) detection is available
+    pub slow_matchers: Vec<SlowRule>,
+    /// When a slow-path rule matches, whether to keep the fast-path result
+    /// instead of letting the slow-path result replace it
+    pub keep_fast_if_accurate: bool,
+}
+
+impl FormatMatcher {
+    /// Create an empty matcher
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether a confirmed slow-path match should still defer to the
+    /// fast-path result rather than replacing it
+    pub fn with_keep_fast_if_accurate(mut self, keep_fast_if_accurate: bool) -> Self {
+        self.keep_fast_if_accurate = keep_fast_if_accurate;
+        self
+    }
+
+    /// Register a fast-path extension rule
+    pub fn with_fast_matcher(mut self, extension: impl Into<String>, kind: ContentKind) -> Self {
+        self.fast_matchers.push((Extension::new(extension), kind));
+        self
+    }
+
+    /// Register a slow-path MIME/signature rule
+    pub fn with_slow_matcher(mut self, matcher: MimeOrSignature, kind: ContentKind) -> Self {
+        self.slow_matchers.push((matcher, kind));
+        self
+    }
+
+    /// Look up the fast-path kind for `extension`, if any rule matches
+    pub fn match_fast(&self, extension: &str) -> Option<ContentKind> {
+        self.fast_matchers
+            .iter()
+            .find(|(matcher, _)| matcher.matches(extension))
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Look up the slow-path kind for `content_prefix`/`mime_type`, if any
+    /// rule matches
+    pub fn match_slow(&self, mime_type: Option<&str>, content_prefix: &[u8]) -> Option<ContentKind> {
+        self.slow_matchers
+            .iter()
+            .find(|(matcher, _)| matcher.matches(mime_type, content_prefix))
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Resolve the best [`ContentKind`] for a file, combining the fast and
+    /// slow signals
+    ///
+    /// `filename` drives the fast (extension-only) path. `content_prefix`
+    /// (and optionally `mime_type`) drive the slow path, but only when
+    /// `accurate` is `true` — callers pass `false` for formats where slow
+    /// detection wasn't run or isn't trustworthy (e.g. tar, whose MIME type
+    /// is ambiguous across tools), keeping the extension-based guess.
+    ///
+    /// When a slow-path rule matches and `accurate` is `true`, the result
+    /// replaces the fast-path guess unless `keep_fast_if_accurate` is set
+    /// and the fast path already produced a match, in which case the fast
+    /// result is kept.
+    pub fn resolve(
+        &self,
+        filename: &str,
+        mime_type: Option<&str>,
+        content_prefix: &[u8],
+        accurate: bool,
+    ) -> ContentKind {
+        let extension = filename.rsplit('.').next().unwrap_or(filename);
+        let fast_kind = self.match_fast(extension);
+
+        if accurate {
+            if let Some(slow_kind) = self.match_slow(mime_type, content_prefix) {
+                if !(self.keep_fast_if_accurate && fast_kind.is_some()) {
+                    return slow_kind;
+                }
+            }
+        }
+
+        fast_kind.unwrap_or(ContentKind::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zip_and_tar_matcher(keep_fast_if_accurate: bool) -> FormatMatcher {
+        FormatMatcher::new()
+            .with_keep_fast_if_accurate(keep_fast_if_accurate)
+            .with_fast_matcher("zip", ContentKind::Archive)
+            .with_fast_matcher("tar", ContentKind::Archive)
+            .with_slow_matcher(
+                MimeOrSignature::Signature(b"PK\x03\x04".to_vec()),
+                ContentKind::Archive,
+            )
+            .with_slow_matcher(
+                MimeOrSignature::Mime("application/pdf".to_string()),
+                ContentKind::Document,
+            )
+            .with_slow_matcher(
+                MimeOrSignature::Signature(b"%PDF".to_vec()),
+                ContentKind::Document,
+            )
+    }
+
+    #[test]
+    fn test_fast_path_only_when_not_accurate() {
+        let matcher = zip_and_tar_matcher(false);
+        assert_eq!(
+            matcher.resolve("archive.tar", None, b"not actually a tar", false),
+            ContentKind::Archive
+        );
+        assert_eq!(
+            matcher.resolve("unknown.xyz", None, b"", false),
+            ContentKind::Unknown
+        );
+    }
+
+    #[test]
+    fn test_slow_path_overrides_fast_by_default() {
+        let matcher = zip_and_tar_matcher(false);
+        // Extension says zip/Archive, but content sniffing says PDF/Document
+        // and accurate detection ran, so the slow result wins.
+        assert_eq!(
+            matcher.resolve("renamed.zip", None, b"%PDF-1.7", true),
+            ContentKind::Document
+        );
+    }
+
+    #[test]
+    fn test_keep_fast_if_accurate_suppresses_slow_override() {
+        let matcher = zip_and_tar_matcher(true);
+        assert_eq!(
+            matcher.resolve("renamed.zip", None, b"%PDF-1.7", true),
+            ContentKind::Archive
+        );
+    }
+
+    #[test]
+    fn test_slow_path_fills_in_when_fast_path_has_no_match() {
+        let matcher = zip_and_tar_matcher(true);
+        assert_eq!(
+            matcher.resolve("noext", None, b"PK\x03\x04", true),
+            ContentKind::Archive
+        );
+    }
+
+    #[test]
+    fn test_mime_matcher() {
+        let matcher = zip_and_tar_matcher(false);
+        assert_eq!(
+            matcher.resolve("file.bin", Some("application/pdf"), b"", true),
+            ContentKind::Document
+        );
+    }
+
+    #[test]
+    fn test_extension_matcher_case_insensitive() {
+        let extension = Extension::new("zip");
+        assert!(extension.matches("ZIP"));
+        assert!(!extension.matches("tar"));
+    }
+}
@@ -28,24 +28,30 @@
 //! }
 //! ```
 
+mod compound_format;
 mod content_file;
 mod content_kind;
 mod content_metadata;
+mod data_policy;
 mod data_sensitivity;
 mod data_structure_kind;
+mod format_matcher;
 mod supported_format;
 
 use std::path::PathBuf;
 
 // Re-export main types
+pub use compound_format::{CompressionFilter, ContainerFormat};
 pub use content_file::ContentFile;
 pub use content_kind::ContentKind;
 pub use content_metadata::ContentMetadata;
+pub use data_policy::DataPolicy;
 pub use data_sensitivity::DataSensitivity;
 pub use data_structure_kind::DataStructureKind;
+pub use format_matcher::{Extension, FormatMatcher, MimeOrSignature};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-pub use supported_format::SupportedFormat;
+pub use supported_format::{conversion_path, FormatCategory, FormatVerdict, SupportedFormat};
 
 use crate::path::ContentSource;
 
@@ -3,12 +3,36 @@
 //! This module provides the [`SupportedFormat`] struct and related enums
 //! for identifying and categorizing different file formats supported by nvisy.
 
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Seek, SeekFrom};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumIter, EnumString};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
 
 use crate::fs::{ContentKind, DataStructureKind};
 
+/// Maximum number of leading bytes read when sniffing a format's magic
+/// signature, so detection also works on streams rather than requiring the
+/// whole file in memory
+const SNIFF_BUFFER_SIZE: usize = 8192;
+
+/// Direct input→output conversion edges between [`SupportedFormat`] values
+///
+/// This is deliberately a small, explicit starting set rather than anything
+/// derived from [`SupportedFormat::content_kind`] — not every pair of formats
+/// in the same category is actually convertible (e.g. a `.txt` file can't be
+/// losslessly turned into `.csv`), so edges are only added as real conversion
+/// support is implemented.
+const CONVERSION_EDGES: &[(SupportedFormat, SupportedFormat)] = &[
+    (SupportedFormat::Docx, SupportedFormat::Pdf),
+    (SupportedFormat::Doc, SupportedFormat::Pdf),
+    (SupportedFormat::Svg, SupportedFormat::Png),
+    (SupportedFormat::Jpeg, SupportedFormat::Png),
+    (SupportedFormat::Png, SupportedFormat::Jpeg),
+    (SupportedFormat::Csv, SupportedFormat::Json),
+];
+
 /// Individual supported formats with their categories
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -191,6 +215,311 @@ impl SupportedFormat {
             | Self::Svg => DataStructureKind::Unstructured,
         }
     }
+
+    /// Identify a format from its leading magic-number signature
+    ///
+    /// Falls back to UTF-8 validity plus a light structural probe for the
+    /// text-based formats, which have no true magic number. Returns `None`
+    /// when nothing matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nvisy_core::fs::SupportedFormat;
+    ///
+    /// assert_eq!(SupportedFormat::from_bytes(b"%PDF-1.7"), Some(SupportedFormat::Pdf));
+    /// assert_eq!(SupportedFormat::from_bytes(&[0xFF, 0xD8, 0xFF]), Some(SupportedFormat::Jpeg));
+    /// ```
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            return Some(Self::Jpeg);
+        }
+        if data.starts_with(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]) {
+            return Some(Self::Png);
+        }
+        if data.starts_with(b"%PDF-") {
+            return Some(Self::Pdf);
+        }
+        if data.starts_with(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]) {
+            return Some(Self::Doc);
+        }
+        if data.starts_with(b"{\\rtf") {
+            return Some(Self::Rtf);
+        }
+        if data.starts_with(b"PK\x03\x04") {
+            return Self::looks_like_docx(data).then_some(Self::Docx);
+        }
+
+        let text = std::str::from_utf8(data).ok()?;
+        let trimmed = text.trim_start();
+
+        if trimmed.starts_with("<svg") {
+            Some(Self::Svg)
+        } else if trimmed.starts_with("<?xml") {
+            if trimmed.contains("<svg") {
+                Some(Self::Svg)
+            } else {
+                Some(Self::Xml)
+            }
+        } else if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            Some(Self::Json)
+        } else {
+            Some(Self::Txt)
+        }
+    }
+
+    /// Identify a format by sniffing the leading bytes of a seekable reader
+    ///
+    /// Reads at most [`SNIFF_BUFFER_SIZE`] bytes and restores the reader's
+    /// original position before returning, so this works on streams as well
+    /// as in-memory buffers.
+    pub fn from_reader<R: Read + Seek>(reader: &mut R) -> std::io::Result<Option<Self>> {
+        let start = reader.stream_position()?;
+
+        let mut buf = Vec::new();
+        reader
+            .by_ref()
+            .take(SNIFF_BUFFER_SIZE as u64)
+            .read_to_end(&mut buf)?;
+
+        reader.seek(SeekFrom::Start(start))?;
+        Ok(Self::from_bytes(&buf))
+    }
+
+    /// Whether a ZIP-container buffer's leading bytes look like an OOXML
+    /// (DOCX) package rather than a plain ZIP archive
+    fn looks_like_docx(data: &[u8]) -> bool {
+        contains_subslice(data, b"[Content_Types].xml") || contains_subslice(data, b"word/")
+    }
+
+    /// Compare a declared file extension against sniffed content and report
+    /// whether they agree
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nvisy_core::fs::SupportedFormat;
+    ///
+    /// let verdict = SupportedFormat::verify("png", &[0xFF, 0xD8, 0xFF]);
+    /// assert!(verdict.is_mismatch());
+    /// ```
+    pub fn verify(path_extension: &str, content: &[u8]) -> FormatVerdict {
+        let Some(detected) = Self::from_bytes(content) else {
+            return FormatVerdict::Unknown;
+        };
+
+        match Self::from_extension(path_extension) {
+            Some(declared) if Self::are_equivalent(declared, detected) => FormatVerdict::Match,
+            Some(declared) if declared.is_text() && detected.is_text() => {
+                FormatVerdict::CompatibleButMoreSpecific { detected }
+            }
+            _ => FormatVerdict::Mismatch { detected },
+        }
+    }
+
+    /// Whether two formats should be treated as the same format under
+    /// different names (e.g. `jpg` and `jpeg`) rather than a mismatch
+    fn are_equivalent(a: Self, b: Self) -> bool {
+        a == b || matches!((a, b), (Self::Jpg, Self::Jpeg) | (Self::Jpeg, Self::Jpg))
+    }
+
+    /// Resolve a format from a MIME type / `Content-Type` string
+    ///
+    /// Strips any `; charset=...` parameters and matches case-insensitively,
+    /// handling the real-world aliases tools commonly emit (`text/xml` and
+    /// `application/xml` both resolve to [`Self::Xml`], and so on).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nvisy_core::fs::SupportedFormat;
+    ///
+    /// assert_eq!(
+    ///     SupportedFormat::from_mime_type("text/xml; charset=utf-8"),
+    ///     Some(SupportedFormat::Xml)
+    /// );
+    /// ```
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        let essence = mime_type
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_lowercase();
+
+        match essence.as_str() {
+            "text/plain" => Some(Self::Txt),
+            "text/xml" | "application/xml" => Some(Self::Xml),
+            "application/json" | "text/json" => Some(Self::Json),
+            "text/csv" => Some(Self::Csv),
+            "application/pdf" => Some(Self::Pdf),
+            "application/msword" => Some(Self::Doc),
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
+                Some(Self::Docx)
+            }
+            "application/rtf" | "text/rtf" => Some(Self::Rtf),
+            "image/jpeg" | "image/jpg" => Some(Self::Jpeg),
+            "image/png" => Some(Self::Png),
+            "image/svg+xml" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    /// The broad category this format belongs to
+    pub const fn to_category(self) -> FormatCategory {
+        match self.content_kind() {
+            ContentKind::Text => FormatCategory::Text,
+            ContentKind::Document => FormatCategory::Documents,
+            ContentKind::Image => FormatCategory::Images,
+            // `content_kind()` never actually produces `Unknown` or
+            // `Archive` (no `SupportedFormat` variant maps to either), but
+            // the match has to stay exhaustive over all of `ContentKind`.
+            ContentKind::Unknown | ContentKind::Archive => FormatCategory::Text,
+        }
+    }
+
+    /// All formats belonging to the given category
+    pub fn in_category(category: FormatCategory) -> impl Iterator<Item = Self> {
+        Self::iter().filter(move |format| format.to_category() == category)
+    }
+
+    /// Formats this format can be directly converted into
+    ///
+    /// Mirrors [`Self::extensions`] in shape: a small static table rather than
+    /// a runtime-filtered search over [`CONVERSION_EDGES`], since the edge set
+    /// is fixed at compile time.
+    pub const fn convertible_to(self) -> &'static [Self] {
+        match self {
+            Self::Docx => &[Self::Pdf],
+            Self::Doc => &[Self::Pdf],
+            Self::Svg => &[Self::Png],
+            Self::Jpeg => &[Self::Png],
+            Self::Png => &[Self::Jpeg],
+            Self::Csv => &[Self::Json],
+            _ => &[],
+        }
+    }
+
+    /// Whether this format can be directly converted into `target`
+    pub fn can_convert_to(self, target: Self) -> bool {
+        self.convertible_to().contains(&target)
+    }
+}
+
+/// Broad grouping of [`SupportedFormat`] variants, mirroring [`ContentKind`]
+/// but exposed as an iterable, extension-expandable set for "scan all
+/// images"-style selectors rather than a single-file classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter)]
+#[strum(serialize_all = "lowercase")]
+pub enum FormatCategory {
+    /// Image formats (JPEG, PNG, SVG, ...)
+    Images,
+    /// Document formats (PDF, DOC, DOCX, RTF, ...)
+    Documents,
+    /// Plain and structured text formats (TXT, JSON, XML, CSV, ...)
+    Text,
+}
+
+impl FormatCategory {
+    /// Every file extension used by any format in this category
+    pub fn extensions(self) -> Vec<&'static str> {
+        SupportedFormat::in_category(self)
+            .flat_map(SupportedFormat::extensions)
+            .copied()
+            .collect()
+    }
+}
+
+/// Outcome of comparing a declared file extension against sniffed content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatVerdict {
+    /// The extension and the sniffed content agree
+    Match,
+    /// The extension is a looser or less specific text format than what the
+    /// content actually is (e.g. a JSON file declared with a generic `.txt`
+    /// extension) — compatible, but worth noting rather than flagging
+    CompatibleButMoreSpecific {
+        /// The format the content was actually sniffed as
+        detected: SupportedFormat,
+    },
+    /// The extension and the sniffed content disagree
+    Mismatch {
+        /// The format the content was actually sniffed as
+        detected: SupportedFormat,
+    },
+    /// The content's format could not be determined from its bytes
+    Unknown,
+}
+
+impl FormatVerdict {
+    /// Whether the declared extension should be considered wrong
+    pub const fn is_mismatch(&self) -> bool {
+        matches!(self, Self::Mismatch { .. })
+    }
+
+    /// The format the bytes were actually sniffed as, when it differs from
+    /// the declared extension; `None` for [`Self::Match`] and [`Self::Unknown`]
+    pub const fn detected(&self) -> Option<SupportedFormat> {
+        match self {
+            Self::CompatibleButMoreSpecific { detected } | Self::Mismatch { detected } => {
+                Some(*detected)
+            }
+            Self::Match | Self::Unknown => None,
+        }
+    }
+
+    /// The file extension recommended for the sniffed content, for both a
+    /// [`Self::Mismatch`] and a [`Self::CompatibleButMoreSpecific`] verdict
+    pub fn recommended_extension(&self) -> Option<&'static str> {
+        self.detected().map(SupportedFormat::primary_extension)
+    }
+}
+
+/// Whether `needle` occurs anywhere in `haystack`
+fn contains_subslice(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+/// Find a route of direct conversions from `from` to `to`
+///
+/// Performs a breadth-first search over [`CONVERSION_EDGES`] so pipeline code
+/// can plan a multi-step transformation (e.g. input format → processable
+/// intermediate format → output format) instead of encoding conversion
+/// routing as ad-hoc `match` arms. Returns the full path including `from` and
+/// `to`, or `None` if no route exists. Returns `Some(vec![from])` when
+/// `from == to`.
+pub fn conversion_path(from: SupportedFormat, to: SupportedFormat) -> Option<Vec<SupportedFormat>> {
+    if from == to {
+        return Some(vec![from]);
+    }
+
+    let mut predecessors: HashMap<SupportedFormat, SupportedFormat> = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(from);
+
+    while let Some(current) = queue.pop_front() {
+        for &(source, target) in CONVERSION_EDGES {
+            if source != current || predecessors.contains_key(&target) || target == from {
+                continue;
+            }
+
+            predecessors.insert(target, current);
+            if target == to {
+                let mut path = vec![target];
+                let mut step = target;
+                while let Some(&prev) = predecessors.get(&step) {
+                    path.push(prev);
+                    step = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            queue.push_back(target);
+        }
+    }
+
+    None
 }
 
 #[cfg(test)]
@@ -266,4 +595,212 @@ mod tests {
         let deserialized: SupportedFormat = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, format);
     }
+
+    #[test]
+    fn test_from_bytes_detects_binary_signatures() {
+        assert_eq!(
+            SupportedFormat::from_bytes(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some(SupportedFormat::Jpeg)
+        );
+        assert_eq!(
+            SupportedFormat::from_bytes(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]),
+            Some(SupportedFormat::Png)
+        );
+        assert_eq!(
+            SupportedFormat::from_bytes(b"%PDF-1.4"),
+            Some(SupportedFormat::Pdf)
+        );
+        assert_eq!(
+            SupportedFormat::from_bytes(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]),
+            Some(SupportedFormat::Doc)
+        );
+        assert_eq!(
+            SupportedFormat::from_bytes(b"{\\rtf1\\ansi"),
+            Some(SupportedFormat::Rtf)
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_disambiguates_docx_from_plain_zip() {
+        let mut docx_bytes = b"PK\x03\x04".to_vec();
+        docx_bytes.extend_from_slice(b"[Content_Types].xml");
+        assert_eq!(SupportedFormat::from_bytes(&docx_bytes), Some(SupportedFormat::Docx));
+
+        let plain_zip = b"PK\x03\x04random.bin".to_vec();
+        assert_eq!(SupportedFormat::from_bytes(&plain_zip), None);
+    }
+
+    #[test]
+    fn test_from_bytes_detects_text_subtypes() {
+        assert_eq!(SupportedFormat::from_bytes(b"hello world"), Some(SupportedFormat::Txt));
+        assert_eq!(
+            SupportedFormat::from_bytes(b"{\"a\": 1}"),
+            Some(SupportedFormat::Json)
+        );
+        assert_eq!(
+            SupportedFormat::from_bytes(b"<?xml version=\"1.0\"?><root/>"),
+            Some(SupportedFormat::Xml)
+        );
+        assert_eq!(
+            SupportedFormat::from_bytes(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+            Some(SupportedFormat::Svg)
+        );
+        assert_eq!(
+            SupportedFormat::from_bytes(b"<?xml version=\"1.0\"?><svg/>"),
+            Some(SupportedFormat::Svg)
+        );
+    }
+
+    #[test]
+    fn test_from_reader_restores_position() {
+        let mut cursor = std::io::Cursor::new(b"%PDF-1.4 rest of file".to_vec());
+
+        let detected = SupportedFormat::from_reader(&mut cursor).unwrap();
+        assert_eq!(detected, Some(SupportedFormat::Pdf));
+        assert_eq!(cursor.position(), 0);
+    }
+
+    #[test]
+    fn test_verify_reports_match() {
+        let verdict = SupportedFormat::verify("pdf", b"%PDF-1.4");
+        assert_eq!(verdict, FormatVerdict::Match);
+        assert!(!verdict.is_mismatch());
+    }
+
+    #[test]
+    fn test_verify_treats_jpg_jpeg_as_equivalent() {
+        let verdict = SupportedFormat::verify("jpg", &[0xFF, 0xD8, 0xFF]);
+        assert_eq!(verdict, FormatVerdict::Match);
+    }
+
+    #[test]
+    fn test_verify_recommends_correct_extension_on_mismatch() {
+        let verdict = SupportedFormat::verify("png", &[0xFF, 0xD8, 0xFF]);
+        assert!(verdict.is_mismatch());
+        assert_eq!(verdict.detected(), Some(SupportedFormat::Jpeg));
+        assert_eq!(verdict.recommended_extension(), Some("jpeg"));
+    }
+
+    #[test]
+    fn test_verify_treats_text_subtype_as_compatible() {
+        let verdict = SupportedFormat::verify("txt", b"{\"a\": 1}");
+        assert_eq!(
+            verdict,
+            FormatVerdict::CompatibleButMoreSpecific {
+                detected: SupportedFormat::Json
+            }
+        );
+        assert!(!verdict.is_mismatch());
+    }
+
+    #[test]
+    fn test_verify_unknown_content() {
+        let verdict = SupportedFormat::verify("bin", &[0x00, 0x01, 0x02, 0x80]);
+        assert_eq!(verdict, FormatVerdict::Unknown);
+        assert_eq!(verdict.detected(), None);
+    }
+
+    #[test]
+    fn test_from_mime_type_resolves_aliases() {
+        assert_eq!(
+            SupportedFormat::from_mime_type("text/xml"),
+            Some(SupportedFormat::Xml)
+        );
+        assert_eq!(
+            SupportedFormat::from_mime_type("application/xml"),
+            Some(SupportedFormat::Xml)
+        );
+        assert_eq!(
+            SupportedFormat::from_mime_type("image/jpeg"),
+            Some(SupportedFormat::Jpeg)
+        );
+        assert_eq!(
+            SupportedFormat::from_mime_type("text/plain"),
+            Some(SupportedFormat::Txt)
+        );
+    }
+
+    #[test]
+    fn test_from_mime_type_strips_charset_and_is_case_insensitive() {
+        assert_eq!(
+            SupportedFormat::from_mime_type("TEXT/XML; charset=utf-8"),
+            Some(SupportedFormat::Xml)
+        );
+    }
+
+    #[test]
+    fn test_from_mime_type_unknown() {
+        assert_eq!(SupportedFormat::from_mime_type("application/octet-stream"), None);
+    }
+
+    #[test]
+    fn test_to_category_matches_content_kind_grouping() {
+        assert_eq!(SupportedFormat::Png.to_category(), FormatCategory::Images);
+        assert_eq!(SupportedFormat::Jpeg.to_category(), FormatCategory::Images);
+        assert_eq!(SupportedFormat::Pdf.to_category(), FormatCategory::Documents);
+        assert_eq!(SupportedFormat::Docx.to_category(), FormatCategory::Documents);
+        assert_eq!(SupportedFormat::Json.to_category(), FormatCategory::Text);
+    }
+
+    #[test]
+    fn test_in_category_returns_only_matching_formats() {
+        let images: Vec<_> = SupportedFormat::in_category(FormatCategory::Images).collect();
+        assert!(images.contains(&SupportedFormat::Png));
+        assert!(images.contains(&SupportedFormat::Svg));
+        assert!(!images.contains(&SupportedFormat::Pdf));
+    }
+
+    #[test]
+    fn test_format_category_extensions_flattens_members() {
+        let extensions = FormatCategory::Images.extensions();
+        assert!(extensions.contains(&"png"));
+        assert!(extensions.contains(&"svg"));
+        assert!(extensions.contains(&"jpg"));
+        assert!(!extensions.contains(&"pdf"));
+    }
+
+    #[test]
+    fn test_convertible_to_and_can_convert_to() {
+        assert_eq!(SupportedFormat::Docx.convertible_to(), &[SupportedFormat::Pdf]);
+        assert!(SupportedFormat::Docx.can_convert_to(SupportedFormat::Pdf));
+        assert!(!SupportedFormat::Docx.can_convert_to(SupportedFormat::Png));
+        assert!(SupportedFormat::Pdf.convertible_to().is_empty());
+    }
+
+    #[test]
+    fn test_conversion_path_direct_edge() {
+        let path = conversion_path(SupportedFormat::Svg, SupportedFormat::Png);
+        assert_eq!(path, Some(vec![SupportedFormat::Svg, SupportedFormat::Png]));
+    }
+
+    #[test]
+    fn test_conversion_path_multi_step() {
+        // Svg -> Png is direct, and Png -> Jpeg is direct, so Svg -> Jpeg
+        // should route through Png even though there's no direct edge.
+        let path = conversion_path(SupportedFormat::Svg, SupportedFormat::Jpeg);
+        assert_eq!(
+            path,
+            Some(vec![
+                SupportedFormat::Svg,
+                SupportedFormat::Png,
+                SupportedFormat::Jpeg,
+            ])
+        );
+
+        // Docx -> Pdf is direct, but Pdf has no outgoing edges.
+        assert_eq!(conversion_path(SupportedFormat::Pdf, SupportedFormat::Png), None);
+    }
+
+    #[test]
+    fn test_conversion_path_same_format_is_trivial() {
+        assert_eq!(
+            conversion_path(SupportedFormat::Txt, SupportedFormat::Txt),
+            Some(vec![SupportedFormat::Txt])
+        );
+    }
+
+    #[test]
+    fn test_conversion_path_no_route() {
+        assert_eq!(conversion_path(SupportedFormat::Txt, SupportedFormat::Png), None);
+    }
 }
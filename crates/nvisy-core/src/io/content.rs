@@ -3,10 +3,16 @@
 //! This module provides the Content enum for representing different types
 //! of data content within the system.
 
+use std::fmt;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+
 use bytes::Bytes;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::fs::ContentKind;
+
 /// Content types supported by the Nvisy system
 ///
 /// Simplified content representation for efficient processing.
@@ -26,8 +32,10 @@ use serde::{Deserialize, Serialize};
 /// assert!(text_content.is_textual());
 /// assert!(!binary_content.is_textual());
 /// ```
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+///
+/// `Content` can't derive `Debug`/`Clone`/`PartialEq` once [`Content::Stream`]
+/// is in play, since a boxed reader supports none of those on its own; see
+/// the manual impls below.
 pub enum Content {
     /// Text content stored as UTF-8 string
     Text(String),
@@ -40,6 +48,46 @@ pub enum Content {
         mime_type: String,
     },
 
+    /// Image content with MIME type (e.g. `image/png`, `image/jpeg`)
+    Image {
+        /// Raw image bytes
+        data: Bytes,
+        /// MIME type describing the image format
+        mime_type: String,
+    },
+
+    /// Audio content with MIME type (e.g. `audio/mpeg`, `audio/wav`)
+    Audio {
+        /// Raw audio bytes
+        data: Bytes,
+        /// MIME type describing the audio format
+        mime_type: String,
+    },
+
+    /// Video content with MIME type (e.g. `video/mp4`, `video/webm`)
+    Video {
+        /// Raw video bytes
+        data: Bytes,
+        /// MIME type describing the video format
+        mime_type: String,
+    },
+
+    /// Content too large to buffer in memory, read lazily from a reader
+    ///
+    /// The reader is wrapped in `Arc<Mutex<_>>` so `Content` stays cheaply
+    /// cloneable even for streaming bodies, the same way [`super::ContentData`]
+    /// uses a mutex for its hash cache rather than giving up on `Clone`.
+    /// Two `Stream` values are equal only if they share the same reader.
+    Stream {
+        /// The underlying reader, shared so cloning doesn't require
+        /// re-opening or buffering the stream
+        reader: Arc<Mutex<Box<dyn Read + Send>>>,
+        /// MIME type describing the streamed content, if known
+        mime_type: String,
+        /// Known content length in bytes, if available up front
+        size_hint: Option<usize>,
+    },
+
     /// Empty or null content
     Empty,
 }
@@ -50,6 +98,10 @@ impl Content {
         match self {
             Content::Text(_) => "text",
             Content::Binary { .. } => "binary",
+            Content::Image { .. } => "image",
+            Content::Audio { .. } => "audio",
+            Content::Video { .. } => "video",
+            Content::Stream { .. } => "stream",
             Content::Empty => "empty",
         }
     }
@@ -61,7 +113,14 @@ impl Content {
 
     /// Check if this content is multimedia (audio, video, image)
     pub fn is_multimedia(&self) -> bool {
-        false // Simplified - no specific multimedia types
+        match self {
+            Content::Image { .. } | Content::Audio { .. } | Content::Video { .. } => true,
+            Content::Stream { mime_type, .. } => {
+                let prefix = mime_type.split('/').next().unwrap_or("");
+                matches!(prefix, "image" | "audio" | "video")
+            }
+            Content::Text(_) | Content::Binary { .. } | Content::Empty => false,
+        }
     }
 
     /// Check if this content has binary data
@@ -69,12 +128,19 @@ impl Content {
         !matches!(self, Content::Text(_) | Content::Empty)
     }
 
-    /// Get the estimated size in bytes
-    pub fn estimated_size(&self) -> usize {
+    /// Get the estimated size in bytes, if known
+    ///
+    /// Always `Some` except for [`Content::Stream`], which only reports a
+    /// size if its `size_hint` was supplied up front.
+    pub fn estimated_size(&self) -> Option<usize> {
         match self {
-            Content::Text(text) => text.len(),
-            Content::Binary { data, .. } => data.len(),
-            Content::Empty => 0,
+            Content::Text(text) => Some(text.len()),
+            Content::Binary { data, .. }
+            | Content::Image { data, .. }
+            | Content::Audio { data, .. }
+            | Content::Video { data, .. } => Some(data.len()),
+            Content::Stream { size_hint, .. } => *size_hint,
+            Content::Empty => Some(0),
         }
     }
 
@@ -82,16 +148,27 @@ impl Content {
     pub fn format(&self) -> Option<&str> {
         match self {
             Content::Text(_) => Some("text/plain"),
-            Content::Binary { mime_type, .. } => Some(mime_type),
+            Content::Binary { mime_type, .. }
+            | Content::Image { mime_type, .. }
+            | Content::Audio { mime_type, .. }
+            | Content::Video { mime_type, .. }
+            | Content::Stream { mime_type, .. } => Some(mime_type),
             Content::Empty => None,
         }
     }
 
     /// Extract raw bytes from content, if available
+    ///
+    /// Returns `None` for [`Content::Stream`] even though it carries binary
+    /// data, since reading it requires locking and draining the reader
+    /// rather than borrowing already-buffered bytes.
     pub fn as_bytes(&self) -> Option<&Bytes> {
         match self {
-            Content::Binary { data, .. } => Some(data),
-            Content::Text(_) | Content::Empty => None,
+            Content::Binary { data, .. }
+            | Content::Image { data, .. }
+            | Content::Audio { data, .. }
+            | Content::Video { data, .. } => Some(data),
+            Content::Text(_) | Content::Stream { .. } | Content::Empty => None,
         }
     }
 
@@ -115,6 +192,271 @@ impl Content {
             mime_type: mime_type.into(),
         }
     }
+
+    /// Create image content
+    pub fn image<S: Into<String>>(data: Bytes, mime_type: S) -> Self {
+        Content::Image {
+            data,
+            mime_type: mime_type.into(),
+        }
+    }
+
+    /// Create audio content
+    pub fn audio<S: Into<String>>(data: Bytes, mime_type: S) -> Self {
+        Content::Audio {
+            data,
+            mime_type: mime_type.into(),
+        }
+    }
+
+    /// Create video content
+    pub fn video<S: Into<String>>(data: Bytes, mime_type: S) -> Self {
+        Content::Video {
+            data,
+            mime_type: mime_type.into(),
+        }
+    }
+
+    /// Create streaming content from a boxed reader
+    ///
+    /// `size_hint` should be supplied whenever the caller already knows the
+    /// final length (e.g. from a `Content-Length` header or archive entry
+    /// metadata), so [`Self::estimated_size`] keeps working without reading
+    /// the stream.
+    pub fn stream<S: Into<String>>(
+        reader: Box<dyn Read + Send>,
+        mime_type: S,
+        size_hint: Option<usize>,
+    ) -> Self {
+        Content::Stream {
+            reader: Arc::new(Mutex::new(reader)),
+            mime_type: mime_type.into(),
+            size_hint,
+        }
+    }
+
+    /// Sniff this content's binary payload to determine its [`ContentKind`]
+    ///
+    /// Returns `None` for [`Content::Text`]/[`Content::Empty`], since those
+    /// already know their own kind without needing to inspect bytes, and
+    /// for [`Content::Image`]/[`Content::Audio`]/[`Content::Video`]/
+    /// [`Content::Stream`], which already carry an explicit MIME type
+    /// rather than needing it sniffed from bytes.
+    pub fn detect_kind(&self) -> Option<ContentKind> {
+        match self {
+            Content::Binary { data, .. } => Some(ContentKind::from_bytes(data)),
+            Content::Text(_)
+            | Content::Image { .. }
+            | Content::Audio { .. }
+            | Content::Video { .. }
+            | Content::Stream { .. }
+            | Content::Empty => None,
+        }
+    }
+}
+
+impl fmt::Debug for Content {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Content::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Content::Binary { data, mime_type } => f
+                .debug_struct("Binary")
+                .field("data", data)
+                .field("mime_type", mime_type)
+                .finish(),
+            Content::Image { data, mime_type } => f
+                .debug_struct("Image")
+                .field("data", data)
+                .field("mime_type", mime_type)
+                .finish(),
+            Content::Audio { data, mime_type } => f
+                .debug_struct("Audio")
+                .field("data", data)
+                .field("mime_type", mime_type)
+                .finish(),
+            Content::Video { data, mime_type } => f
+                .debug_struct("Video")
+                .field("data", data)
+                .field("mime_type", mime_type)
+                .finish(),
+            Content::Stream {
+                mime_type,
+                size_hint,
+                ..
+            } => f
+                .debug_struct("Stream")
+                .field("mime_type", mime_type)
+                .field("size_hint", size_hint)
+                .finish(),
+            Content::Empty => write!(f, "Empty"),
+        }
+    }
+}
+
+impl Clone for Content {
+    fn clone(&self) -> Self {
+        match self {
+            Content::Text(text) => Content::Text(text.clone()),
+            Content::Binary { data, mime_type } => Content::Binary {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Image { data, mime_type } => Content::Image {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Audio { data, mime_type } => Content::Audio {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Video { data, mime_type } => Content::Video {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Stream {
+                reader,
+                mime_type,
+                size_hint,
+            } => Content::Stream {
+                reader: Arc::clone(reader),
+                mime_type: mime_type.clone(),
+                size_hint: *size_hint,
+            },
+            Content::Empty => Content::Empty,
+        }
+    }
+}
+
+impl PartialEq for Content {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Content::Text(a), Content::Text(b)) => a == b,
+            (
+                Content::Binary {
+                    data: a,
+                    mime_type: am,
+                },
+                Content::Binary {
+                    data: b,
+                    mime_type: bm,
+                },
+            ) => a == b && am == bm,
+            (
+                Content::Image {
+                    data: a,
+                    mime_type: am,
+                },
+                Content::Image {
+                    data: b,
+                    mime_type: bm,
+                },
+            ) => a == b && am == bm,
+            (
+                Content::Audio {
+                    data: a,
+                    mime_type: am,
+                },
+                Content::Audio {
+                    data: b,
+                    mime_type: bm,
+                },
+            ) => a == b && am == bm,
+            (
+                Content::Video {
+                    data: a,
+                    mime_type: am,
+                },
+                Content::Video {
+                    data: b,
+                    mime_type: bm,
+                },
+            ) => a == b && am == bm,
+            (
+                Content::Stream {
+                    reader: a,
+                    mime_type: am,
+                    size_hint: ah,
+                },
+                Content::Stream {
+                    reader: b,
+                    mime_type: bm,
+                    size_hint: bh,
+                },
+            ) => Arc::ptr_eq(a, b) && am == bm && ah == bh,
+            (Content::Empty, Content::Empty) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Serde-compatible mirror of [`Content`], excluding [`Content::Stream`]
+///
+/// A boxed reader can't round-trip through serde, so [`Content::Stream`]
+/// has no representation here; serializing one fails with a custom error
+/// instead.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+enum ContentWire {
+    Text(String),
+    Binary { data: Bytes, mime_type: String },
+    Image { data: Bytes, mime_type: String },
+    Audio { data: Bytes, mime_type: String },
+    Video { data: Bytes, mime_type: String },
+    Empty,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Content {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error;
+
+        let wire = match self {
+            Content::Text(text) => ContentWire::Text(text.clone()),
+            Content::Binary { data, mime_type } => ContentWire::Binary {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Image { data, mime_type } => ContentWire::Image {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Audio { data, mime_type } => ContentWire::Audio {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Video { data, mime_type } => ContentWire::Video {
+                data: data.clone(),
+                mime_type: mime_type.clone(),
+            },
+            Content::Stream { .. } => {
+                return Err(S::Error::custom("Content::Stream cannot be serialized"))
+            }
+            Content::Empty => ContentWire::Empty,
+        };
+
+        wire.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Content {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let wire = ContentWire::deserialize(deserializer)?;
+        Ok(match wire {
+            ContentWire::Text(text) => Content::Text(text),
+            ContentWire::Binary { data, mime_type } => Content::Binary { data, mime_type },
+            ContentWire::Image { data, mime_type } => Content::Image { data, mime_type },
+            ContentWire::Audio { data, mime_type } => Content::Audio { data, mime_type },
+            ContentWire::Video { data, mime_type } => Content::Video { data, mime_type },
+            ContentWire::Empty => Content::Empty,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -141,14 +483,77 @@ mod tests {
     #[test]
     fn test_content_size_estimation() {
         let text = Content::text("Hello, world!");
-        assert_eq!(text.estimated_size(), 13);
+        assert_eq!(text.estimated_size(), Some(13));
 
         let binary_data = Bytes::from(vec![0; 100]);
         let binary = Content::binary(binary_data, "application/octet-stream");
-        assert_eq!(binary.estimated_size(), 100);
+        assert_eq!(binary.estimated_size(), Some(100));
 
         let empty = Content::Empty;
-        assert_eq!(empty.estimated_size(), 0);
+        assert_eq!(empty.estimated_size(), Some(0));
+    }
+
+    #[test]
+    fn test_content_multimedia_variants() {
+        let image = Content::image(Bytes::from_static(b"\x89PNG"), "image/png");
+        assert!(image.is_multimedia());
+        assert!(image.has_binary_data());
+        assert_eq!(image.type_name(), "image");
+        assert_eq!(image.format(), Some("image/png"));
+        assert_eq!(image.estimated_size(), Some(4));
+
+        let audio = Content::audio(Bytes::from_static(b"RIFF"), "audio/wav");
+        assert!(audio.is_multimedia());
+        assert_eq!(audio.type_name(), "audio");
+
+        let video = Content::video(Bytes::from_static(b"\x00\x00\x00\x18"), "video/mp4");
+        assert!(video.is_multimedia());
+        assert_eq!(video.type_name(), "video");
+
+        let binary = Content::binary(Bytes::from_static(b"raw"), "application/octet-stream");
+        assert!(!binary.is_multimedia());
+    }
+
+    #[test]
+    fn test_content_stream() {
+        let reader: Box<dyn std::io::Read + Send> = Box::new(std::io::Cursor::new(b"hello".to_vec()));
+        let stream = Content::stream(reader, "video/mp4", Some(5));
+
+        assert_eq!(stream.type_name(), "stream");
+        assert!(stream.is_multimedia());
+        assert!(stream.has_binary_data());
+        assert_eq!(stream.format(), Some("video/mp4"));
+        assert_eq!(stream.estimated_size(), Some(5));
+        assert!(stream.as_bytes().is_none());
+
+        let unsized_reader: Box<dyn std::io::Read + Send> = Box::new(std::io::Cursor::new(b"x".to_vec()));
+        let unsized_stream = Content::stream(unsized_reader, "application/octet-stream", None);
+        assert_eq!(unsized_stream.estimated_size(), None);
+        assert!(!unsized_stream.is_multimedia());
+    }
+
+    #[test]
+    fn test_content_stream_clone_shares_reader_clone_does_not_duplicate_content() {
+        let reader: Box<dyn std::io::Read + Send> = Box::new(std::io::Cursor::new(b"data".to_vec()));
+        let stream = Content::stream(reader, "application/octet-stream", Some(4));
+        let cloned = stream.clone();
+
+        // Cloning a stream shares the same underlying reader rather than
+        // duplicating buffered content.
+        assert_eq!(stream, cloned);
+    }
+
+    #[test]
+    fn test_content_detect_kind() {
+        let zip_bytes = Bytes::from_static(b"PK\x03\x04");
+        let binary = Content::binary(zip_bytes, "application/octet-stream");
+        assert_eq!(
+            binary.detect_kind(),
+            Some(crate::fs::ContentKind::Archive)
+        );
+
+        assert_eq!(Content::text("hello").detect_kind(), None);
+        assert_eq!(Content::Empty.detect_kind(), None);
     }
 
     #[test]
@@ -3,23 +3,286 @@
 //! This module provides the [`ContentData`] struct for storing content data
 //! along with its metadata and source information.
 
+use std::collections::HashMap;
 use std::fmt;
+use std::io::Read;
+use std::mem;
 use std::sync::Mutex;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use crc32fast::Hasher as Crc32Hasher;
 use nvisy_error::{Error, ErrorResource, ErrorType, Result};
+use ripemd::Ripemd160;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use sha1::Sha1;
 use sha2::{Digest, Sha256};
 
 use crate::path::ContentSource;
 
+/// A content digest algorithm, selecting which hasher [`ContentData::digest`]
+/// runs over the content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HashAlgorithm {
+    /// SHA-256
+    Sha256,
+    /// Double SHA-256 (`SHA256(SHA256(x))`), as used for Bitcoin-style
+    /// content addressing
+    Sha256d,
+    /// SHA-1
+    Sha1,
+    /// RIPEMD-160
+    Ripemd160,
+    /// BLAKE3
+    Blake3,
+}
+
+/// A computed content digest, tagged by the algorithm that produced it
+///
+/// Each variant owns its digest as a fixed-size byte array, so the type
+/// system rules out mixing digests from different algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContentHash {
+    /// SHA-256 digest (32 bytes)
+    Sha256([u8; 32]),
+    /// Double SHA-256 digest (32 bytes)
+    Sha256d([u8; 32]),
+    /// SHA-1 digest (20 bytes)
+    Sha1([u8; 20]),
+    /// RIPEMD-160 digest (20 bytes)
+    Ripemd160([u8; 20]),
+    /// BLAKE3 digest (32 bytes)
+    Blake3([u8; 32]),
+}
+
+impl ContentHash {
+    /// The algorithm that produced this digest
+    pub const fn kind(&self) -> HashAlgorithm {
+        match self {
+            Self::Sha256(_) => HashAlgorithm::Sha256,
+            Self::Sha256d(_) => HashAlgorithm::Sha256d,
+            Self::Sha1(_) => HashAlgorithm::Sha1,
+            Self::Ripemd160(_) => HashAlgorithm::Ripemd160,
+            Self::Blake3(_) => HashAlgorithm::Blake3,
+        }
+    }
+
+    /// Borrow the raw digest bytes
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Sha256(bytes) | Self::Sha256d(bytes) | Self::Blake3(bytes) => bytes.as_slice(),
+            Self::Sha1(bytes) | Self::Ripemd160(bytes) => bytes.as_slice(),
+        }
+    }
+
+    /// Build a digest of the given algorithm from raw bytes
+    ///
+    /// Fails if `bytes` doesn't match the algorithm's fixed digest size.
+    pub fn from_slice(kind: HashAlgorithm, bytes: &[u8]) -> Result<Self> {
+        let length_error = || {
+            Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                format!("Invalid digest length {} for {:?}", bytes.len(), kind),
+            )
+        };
+
+        Ok(match kind {
+            HashAlgorithm::Sha256 => Self::Sha256(bytes.try_into().map_err(|_| length_error())?),
+            HashAlgorithm::Sha256d => Self::Sha256d(bytes.try_into().map_err(|_| length_error())?),
+            HashAlgorithm::Sha1 => Self::Sha1(bytes.try_into().map_err(|_| length_error())?),
+            HashAlgorithm::Ripemd160 => {
+                Self::Ripemd160(bytes.try_into().map_err(|_| length_error())?)
+            }
+            HashAlgorithm::Blake3 => Self::Blake3(bytes.try_into().map_err(|_| length_error())?),
+        })
+    }
+}
+
+/// Content-addressable identity derived from a [`ContentHash`], supporting
+/// abbreviated-prefix lookup the way git-style object stores do
+///
+/// Ordering and hashing are derived straight from the wrapped
+/// [`ContentHash`] (which orders first by algorithm, then by digest bytes),
+/// so ids are stable and suitable as map keys or in a sorted store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContentId(ContentHash);
+
+impl ContentId {
+    /// Wrap an already-computed digest as a content id
+    pub fn from_hash(hash: ContentHash) -> Self {
+        Self(hash)
+    }
+
+    /// The underlying digest
+    pub fn hash(&self) -> &ContentHash {
+        &self.0
+    }
+
+    /// Parse a full hex-encoded digest of the given algorithm into a content id
+    pub fn from_hex(kind: HashAlgorithm, hex_str: &str) -> Result<Self> {
+        let bytes = decode_hash_hex(hex_str)?;
+        Ok(Self(ContentHash::from_slice(kind, &bytes)?))
+    }
+
+    /// Hex-encode the full digest
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0.as_slice())
+    }
+
+    /// Does this id's hex digest start with `prefix`?
+    ///
+    /// Compares by hex nibble count rather than whole bytes, so odd-length
+    /// prefixes (e.g. `"abc"`) are handled correctly.
+    pub fn matches_prefix(&self, prefix: &str) -> bool {
+        let full = self.to_hex();
+        prefix.len() <= full.len() && full[..prefix.len()].eq_ignore_ascii_case(prefix)
+    }
+
+    /// Resolve a hex prefix against a set of known ids, the way a git-style
+    /// object store resolves an abbreviated object id
+    ///
+    /// Fails if no id matches the prefix, or if more than one does.
+    pub fn resolve_prefix<'a, I>(prefix: &str, known: I) -> Result<&'a ContentId>
+    where
+        I: IntoIterator<Item = &'a ContentId>,
+    {
+        let mut matches = known.into_iter().filter(|id| id.matches_prefix(prefix));
+
+        let first = matches.next().ok_or_else(|| {
+            Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                format!("No content id matches prefix {prefix:?}"),
+            )
+        })?;
+
+        if matches.next().is_some() {
+            return Err(Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                format!("Prefix {prefix:?} is ambiguous across multiple content ids"),
+            ));
+        }
+
+        Ok(first)
+    }
+}
+
+/// Per-algorithm streaming hash state, the inner state of a [`ContentHasher`]
+enum HasherState {
+    Sha256(Sha256),
+    Sha256d(Sha256),
+    Sha1(Sha1),
+    Ripemd160(Ripemd160),
+    Blake3(blake3::Hasher),
+}
+
+impl HasherState {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Sha256d => Self::Sha256d(Sha256::new()),
+            HashAlgorithm::Sha1 => Self::Sha1(Sha1::new()),
+            HashAlgorithm::Ripemd160 => Self::Ripemd160(Ripemd160::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(blake3::Hasher::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) | Self::Sha256d(hasher) => Digest::update(hasher, data),
+            Self::Sha1(hasher) => Digest::update(hasher, data),
+            Self::Ripemd160(hasher) => Digest::update(hasher, data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finalize(self) -> ContentHash {
+        match self {
+            Self::Sha256(hasher) => ContentHash::Sha256(hasher.finalize().into()),
+            Self::Sha256d(hasher) => {
+                let first = hasher.finalize();
+                ContentHash::Sha256d(Sha256::digest(first).into())
+            }
+            Self::Sha1(hasher) => ContentHash::Sha1(hasher.finalize().into()),
+            Self::Ripemd160(hasher) => ContentHash::Ripemd160(hasher.finalize().into()),
+            Self::Blake3(hasher) => ContentHash::Blake3(*hasher.finalize().as_bytes()),
+        }
+    }
+}
+
+/// Incremental hasher for digesting content in a single streaming pass
+/// instead of buffering it fully before hashing
+///
+/// Mirrors the update/finalize/reset lifecycle of the underlying streaming
+/// hash implementations. Calling [`Self::update`] after [`Self::finalize`]
+/// without an intervening [`Self::reset`] returns an error instead of
+/// silently computing a digest over a truncated stream.
+pub struct ContentHasher {
+    algorithm: HashAlgorithm,
+    state: HasherState,
+    finalized: bool,
+}
+
+impl ContentHasher {
+    /// Create a new hasher for the given algorithm
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Self {
+            algorithm,
+            state: HasherState::new(algorithm),
+            finalized: false,
+        }
+    }
+
+    /// Feed more data into the hasher
+    ///
+    /// Fails if called after [`Self::finalize`] without an intervening
+    /// [`Self::reset`].
+    pub fn update(&mut self, data: &[u8]) -> Result<()> {
+        if self.finalized {
+            return Err(Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                "Cannot update a ContentHasher after finalize without calling reset first",
+            ));
+        }
+
+        self.state.update(data);
+        Ok(())
+    }
+
+    /// Consume the accumulated state and produce the digest
+    ///
+    /// The hasher remains in a finalized state until [`Self::reset`] is
+    /// called; further calls to [`Self::update`] return an error.
+    pub fn finalize(&mut self) -> ContentHash {
+        let state = mem::replace(&mut self.state, HasherState::new(self.algorithm));
+        self.finalized = true;
+        state.finalize()
+    }
+
+    /// Discard the current state and start over with a fresh hasher for the
+    /// same algorithm
+    pub fn reset(&mut self) {
+        self.state = HasherState::new(self.algorithm);
+        self.finalized = false;
+    }
+}
+
 /// Content data with metadata and computed hashes
 ///
 /// This struct is a minimal wrapper around `bytes::Bytes` that stores content data
-/// along with metadata about its source and optional computed SHA256 hash.
+/// along with metadata about its source and any digests computed on demand.
 /// It's designed to be cheap to clone using the `bytes::Bytes` type.
-/// The SHA256 hash is protected by a mutex for thread safety.
+/// Digests are cached in a map keyed by [`HashAlgorithm`], protected by a
+/// mutex for thread safety, so multiple algorithms can coexist without
+/// recomputation.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContentData {
@@ -27,9 +290,13 @@ pub struct ContentData {
     pub content_source: ContentSource,
     /// The actual content data
     pub content_data: Bytes,
-    /// Optional SHA256 hash of the content as bytes, protected by mutex
+    /// Cached digests, keyed by the algorithm that produced them
     #[cfg_attr(feature = "serde", serde(skip))]
-    content_sha256: Mutex<Option<Bytes>>,
+    digests: Mutex<HashMap<HashAlgorithm, ContentHash>>,
+    /// Cached CRC32 (IEEE) checksum, a cheap pre-check ahead of a full
+    /// cryptographic digest comparison
+    #[cfg_attr(feature = "serde", serde(skip))]
+    crc32: Mutex<Option<u32>>,
 }
 
 impl ContentData {
@@ -51,10 +318,52 @@ impl ContentData {
         Self {
             content_source,
             content_data,
-            content_sha256: Mutex::new(None),
+            digests: Mutex::new(HashMap::new()),
+            crc32: Mutex::new(None),
         }
     }
 
+    /// Stream `reader` to completion, computing `algorithm`'s digest in a
+    /// single pass while accumulating the bytes read, so large or streamed
+    /// inputs don't need to be fully buffered before hashing can start
+    ///
+    /// The digest is cached on the returned `ContentData`, so a subsequent
+    /// [`Self::digest`] call for the same algorithm is free.
+    pub fn from_reader<R: Read>(
+        content_source: ContentSource,
+        mut reader: R,
+        algorithm: HashAlgorithm,
+    ) -> Result<Self> {
+        let mut hasher = ContentHasher::new(algorithm);
+        let mut buffer = BytesMut::new();
+        let mut chunk = [0u8; 8192];
+
+        loop {
+            let read = reader.read(&mut chunk).map_err(|e| {
+                Error::from_source(
+                    ErrorType::Runtime,
+                    ErrorResource::Core,
+                    "Failed to read content",
+                    e,
+                )
+            })?;
+            if read == 0 {
+                break;
+            }
+
+            hasher.update(&chunk[..read])?;
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+
+        let hash = hasher.finalize();
+        let content = Self::new(content_source, buffer.freeze());
+        if let Ok(mut guard) = content.digests.lock() {
+            guard.insert(algorithm, hash);
+        }
+
+        Ok(content)
+    }
+
     /// Get the size of the content in bytes
     pub fn size(&self) -> usize {
         self.content_data.len()
@@ -110,40 +419,56 @@ impl ContentData {
         })
     }
 
-    /// Compute and store SHA256 hash of the content, returning the hash as bytes
-    pub fn compute_sha256(&self) -> Bytes {
-        let mut hasher = Sha256::new();
-        hasher.update(&self.content_data);
-        let hash_bytes = Bytes::from(hasher.finalize().to_vec());
+    /// Compute (or return the already-cached) digest for the given algorithm
+    pub fn digest(&self, algorithm: HashAlgorithm) -> ContentHash {
+        if let Ok(guard) = self.digests.lock() {
+            if let Some(hash) = guard.get(&algorithm) {
+                return *hash;
+            }
+        }
 
-        if let Ok(mut guard) = self.content_sha256.lock() {
-            *guard = Some(hash_bytes.clone());
+        let hash = self.compute_digest(algorithm);
+        if let Ok(mut guard) = self.digests.lock() {
+            guard.insert(algorithm, hash);
         }
 
-        hash_bytes
+        hash
     }
 
-    /// Get the SHA256 hash if computed, computing it if not already done
-    pub fn sha256(&self) -> Bytes {
-        if let Ok(guard) = self.content_sha256.lock() {
-            if let Some(ref hash) = *guard {
-                return hash.clone();
+    /// Run the hasher for `algorithm` over the content, ignoring the cache
+    fn compute_digest(&self, algorithm: HashAlgorithm) -> ContentHash {
+        match algorithm {
+            HashAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(&self.content_data);
+                ContentHash::Sha256(hasher.finalize().into())
+            }
+            HashAlgorithm::Sha256d => {
+                let first = Sha256::digest(&self.content_data);
+                ContentHash::Sha256d(Sha256::digest(first).into())
+            }
+            HashAlgorithm::Sha1 => {
+                let mut hasher = Sha1::new();
+                hasher.update(&self.content_data);
+                ContentHash::Sha1(hasher.finalize().into())
             }
+            HashAlgorithm::Ripemd160 => {
+                let mut hasher = Ripemd160::new();
+                hasher.update(&self.content_data);
+                ContentHash::Ripemd160(hasher.finalize().into())
+            }
+            HashAlgorithm::Blake3 => ContentHash::Blake3(*blake3::hash(&self.content_data).as_bytes()),
         }
-        self.compute_sha256()
     }
 
-    /// Get the SHA256 hash as hex string
-    pub fn sha256_hex(&self) -> String {
-        hex::encode(self.sha256())
-    }
-
-    /// Verify the content against a provided SHA256 hash
-    pub fn verify_sha256(&self, expected_hash: impl AsRef<[u8]>) -> Result<()> {
-        let actual_hash = self.sha256();
-        let expected = expected_hash.as_ref();
+    /// Verify the content against a provided digest
+    ///
+    /// The digest is recomputed (or fetched from the cache) using whichever
+    /// algorithm produced `expected`, so callers aren't limited to SHA-256.
+    pub fn verify(&self, expected: &ContentHash) -> Result<()> {
+        let actual = self.digest(expected.kind());
 
-        if actual_hash.as_ref() == expected {
+        if actual == *expected {
             Ok(())
         } else {
             Err(Error::new(
@@ -151,13 +476,67 @@ impl ContentData {
                 ErrorResource::Core,
                 format!(
                     "Hash mismatch: expected {}, got {}",
-                    hex::encode(expected),
-                    hex::encode(&actual_hash)
+                    hex::encode(expected.as_slice()),
+                    hex::encode(actual.as_slice())
                 ),
             ))
         }
     }
 
+    /// Compute and store SHA256 hash of the content, returning the hash as bytes
+    pub fn compute_sha256(&self) -> Bytes {
+        Bytes::copy_from_slice(self.digest(HashAlgorithm::Sha256).as_slice())
+    }
+
+    /// Get the SHA256 hash if computed, computing it if not already done
+    pub fn sha256(&self) -> Bytes {
+        self.compute_sha256()
+    }
+
+    /// Get the SHA256 hash as hex string
+    pub fn sha256_hex(&self) -> String {
+        hex::encode(self.sha256())
+    }
+
+    /// Verify the content against a provided SHA256 hash
+    pub fn verify_sha256(&self, expected_hash: impl AsRef<[u8]>) -> Result<()> {
+        self.verify(&ContentHash::from_slice(HashAlgorithm::Sha256, expected_hash.as_ref())?)
+    }
+
+    /// Compute (or return the cached) double-SHA256 (`SHA256(SHA256(x))`)
+    /// digest of the content, as used for Bitcoin-style content addressing
+    pub fn sha256d(&self) -> Bytes {
+        Bytes::copy_from_slice(self.digest(HashAlgorithm::Sha256d).as_slice())
+    }
+
+    /// Hex-encode the SHA256d digest with its bytes reversed, matching how
+    /// Bitcoin-style tools print content ids in little-endian order
+    pub fn hash_hex_le(&self) -> String {
+        let mut bytes = self.sha256d().to_vec();
+        bytes.reverse();
+        hex::encode(bytes)
+    }
+
+    /// Hex-encode the SHA256d digest in the order it was computed
+    pub fn hash_hex_be(&self) -> String {
+        hex::encode(self.sha256d())
+    }
+
+    /// Parse a little-endian hex-encoded SHA256d digest, as produced by
+    /// [`Self::hash_hex_le`], back into a [`ContentHash`]
+    pub fn from_hash_hex_le(hex_str: &str) -> Result<ContentHash> {
+        let mut bytes = decode_hash_hex(hex_str)?;
+        bytes.reverse();
+        ContentHash::from_slice(HashAlgorithm::Sha256d, &bytes)
+    }
+
+    /// Parse a big-endian hex-encoded SHA256d digest, as produced by
+    /// [`Self::hash_hex_be`], back into a [`ContentHash`]
+    pub fn from_hash_hex_be(hex_str: &str) -> Result<ContentHash> {
+        let bytes = decode_hash_hex(hex_str)?;
+        ContentHash::from_slice(HashAlgorithm::Sha256d, &bytes)
+    }
+
     /// Get a slice of the content data
     pub fn slice(&self, start: usize, end: usize) -> Result<Bytes> {
         if end > self.content_data.len() {
@@ -185,21 +564,220 @@ impl ContentData {
     pub fn is_empty(&self) -> bool {
         self.content_data.is_empty()
     }
+
+    /// Compute (or return the cached) CRC32 checksum of the content, using
+    /// the standard IEEE polynomial so checksums are portable across tools
+    ///
+    /// This is a cheap, non-cryptographic check meant to short-circuit
+    /// expensive digest comparisons, not to replace them.
+    pub fn crc32(&self) -> u32 {
+        if let Ok(guard) = self.crc32.lock() {
+            if let Some(value) = *guard {
+                return value;
+            }
+        }
+
+        let mut hasher = Crc32Hasher::new();
+        hasher.update(&self.content_data);
+        let value = hasher.finalize();
+
+        if let Ok(mut guard) = self.crc32.lock() {
+            *guard = Some(value);
+        }
+
+        value
+    }
+
+    /// Verify the content against a provided CRC32 checksum
+    ///
+    /// Intended for cheap transport-level corruption checks before
+    /// committing to a full cryptographic digest comparison.
+    pub fn verify_crc32(&self, expected: u32) -> Result<()> {
+        let actual = self.crc32();
+
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                format!("CRC32 mismatch: expected {:#010x}, got {:#010x}", expected, actual),
+            ))
+        }
+    }
+
+    /// Compare length and CRC32 first, only falling back to a full byte
+    /// comparison when those cheap checks match
+    ///
+    /// Useful when deduplicating or integrity-checking many `ContentData`
+    /// values, where running a full cryptographic digest on every
+    /// candidate would be wasteful.
+    pub fn fast_eq(&self, other: &Self) -> bool {
+        if self.content_data.len() != other.content_data.len() {
+            return false;
+        }
+        if self.crc32() != other.crc32() {
+            return false;
+        }
+        self.content_data == other.content_data
+    }
+
+    /// The content-addressable id for this content under the given
+    /// algorithm, suitable for prefix lookup in a content store
+    pub fn content_id(&self, algorithm: HashAlgorithm) -> ContentId {
+        ContentId::from_hash(self.digest(algorithm))
+    }
+}
+
+/// Configuration for [`ContentData::encrypt_with`]/[`ContentData::decrypt_with`]
+#[cfg(feature = "encryption")]
+#[derive(Debug, Clone, Copy)]
+pub struct EncryptionOptions {
+    /// PBKDF2-HMAC-SHA256 iteration count used to derive the AES-256-GCM key
+    pub iterations: u32,
+}
+
+#[cfg(feature = "encryption")]
+impl Default for EncryptionOptions {
+    fn default() -> Self {
+        Self {
+            iterations: DEFAULT_PBKDF2_ITERATIONS,
+        }
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl EncryptionOptions {
+    /// Use a non-default PBKDF2 iteration count
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+}
+
+#[cfg(feature = "encryption")]
+const SALT_LEN: usize = 16;
+#[cfg(feature = "encryption")]
+const NONCE_LEN: usize = 12;
+#[cfg(feature = "encryption")]
+const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+
+#[cfg(feature = "encryption")]
+impl ContentData {
+    /// Seal the content into a self-describing encrypted blob:
+    /// `salt || nonce || ciphertext || tag`
+    ///
+    /// The key is derived with PBKDF2-HMAC-SHA256 over a random 16-byte
+    /// salt (using [`EncryptionOptions::default`]'s iteration count), then
+    /// used for AES-256-GCM with a random 12-byte nonce. `content_source`
+    /// metadata is preserved through the round trip.
+    pub fn encrypt(&self, password: &str) -> Result<ContentData> {
+        self.encrypt_with(password, &EncryptionOptions::default())
+    }
+
+    /// Like [`Self::encrypt`], with an explicit iteration count
+    pub fn encrypt_with(&self, password: &str, options: &EncryptionOptions) -> Result<ContentData> {
+        use aes_gcm::aead::rand_core::RngCore;
+        use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+        use aes_gcm::Aes256Gcm;
+
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let key = derive_encryption_key(password, &salt, options.iterations);
+        let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| {
+            Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                format!("Failed to initialize cipher: {e}"),
+            )
+        })?;
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher.encrypt(&nonce, self.content_data.as_ref()).map_err(|e| {
+            Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                format!("Encryption failed: {e}"),
+            )
+        })?;
+
+        let mut blob = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+        blob.extend_from_slice(&salt);
+        blob.extend_from_slice(&nonce);
+        blob.extend_from_slice(&ciphertext);
+
+        Ok(ContentData::new(self.content_source, Bytes::from(blob)))
+    }
+
+    /// Open a blob previously produced by [`Self::encrypt`]
+    ///
+    /// Re-derives the key from the embedded salt and authenticates the GCM
+    /// tag before returning plaintext; a wrong password or corrupted blob
+    /// fails authentication rather than returning garbage.
+    pub fn decrypt(&self, password: &str) -> Result<ContentData> {
+        self.decrypt_with(password, &EncryptionOptions::default())
+    }
+
+    /// Like [`Self::decrypt`], with an explicit iteration count matching
+    /// whatever was passed to [`Self::encrypt_with`]
+    pub fn decrypt_with(&self, password: &str, options: &EncryptionOptions) -> Result<ContentData> {
+        use aes_gcm::aead::{Aead, KeyInit};
+        use aes_gcm::{Aes256Gcm, Nonce};
+
+        if self.content_data.len() < SALT_LEN + NONCE_LEN {
+            return Err(Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                "Encrypted content is too short to contain a salt and nonce",
+            ));
+        }
+
+        let (salt, rest) = self.content_data.split_at(SALT_LEN);
+        let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let key = derive_encryption_key(password, salt, options.iterations);
+        let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| {
+            Error::new(
+                ErrorType::Runtime,
+                ErrorResource::Core,
+                format!("Failed to initialize cipher: {e}"),
+            )
+        })?;
+
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| {
+                Error::new(
+                    ErrorType::Runtime,
+                    ErrorResource::Core,
+                    "Decryption failed: wrong password or corrupted data",
+                )
+            })?;
+
+        Ok(ContentData::new(self.content_source, Bytes::from(plaintext)))
+    }
+}
+
+/// Derive a 32-byte AES-256 key from a password and salt via PBKDF2-HMAC-SHA256
+#[cfg(feature = "encryption")]
+fn derive_encryption_key(password: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key);
+    key
 }
 
 // Manual implementation of Clone since Mutex doesn't implement Clone
 impl Clone for ContentData {
     fn clone(&self) -> Self {
-        let hash = if let Ok(guard) = self.content_sha256.lock() {
-            guard.clone()
-        } else {
-            None
-        };
+        let digests = self.digests.lock().map(|guard| guard.clone()).unwrap_or_default();
+        let crc32 = self.crc32.lock().ok().and_then(|guard| *guard);
 
         Self {
             content_source: self.content_source,
             content_data: self.content_data.clone(),
-            content_sha256: Mutex::new(hash),
+            digests: Mutex::new(digests),
+            crc32: Mutex::new(crc32),
         }
     }
 }
@@ -207,24 +785,15 @@ impl Clone for ContentData {
 // Manual implementation of PartialEq since Mutex doesn't implement PartialEq
 impl PartialEq for ContentData {
     fn eq(&self, other: &Self) -> bool {
-        if self.content_source != other.content_source || self.content_data != other.content_data {
+        if self.content_source != other.content_source || !self.fast_eq(other) {
             return false;
         }
 
-        // Compare hashes if both are computed
-        let self_hash = if let Ok(guard) = self.content_sha256.lock() {
-            guard.clone()
-        } else {
-            None
-        };
-
-        let other_hash = if let Ok(guard) = other.content_sha256.lock() {
-            guard.clone()
-        } else {
-            None
-        };
+        // Compare cached digests, if any have been computed
+        let self_digests = self.digests.lock().map(|guard| guard.clone()).unwrap_or_default();
+        let other_digests = other.digests.lock().map(|guard| guard.clone()).unwrap_or_default();
 
-        self_hash == other_hash
+        self_digests == other_digests
     }
 }
 
@@ -266,6 +835,17 @@ impl From<Bytes> for ContentData {
     }
 }
 
+/// Decode a hex-encoded digest, wrapping `hex`'s error in the crate's error type
+fn decode_hash_hex(hex_str: &str) -> Result<Vec<u8>> {
+    hex::decode(hex_str).map_err(|e| {
+        Error::new(
+            ErrorType::Runtime,
+            ErrorResource::Core,
+            format!("Invalid hex digest: {e}"),
+        )
+    })
+}
+
 impl fmt::Display for ContentData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Ok(text) = self.as_str() {
@@ -288,8 +868,8 @@ mod tests {
 
         assert_eq!(content.content_source, source);
         assert_eq!(content.size(), 13);
-        // Check that hash is not computed yet
-        assert!(content.content_sha256.lock().unwrap().is_none());
+        // Check that no digest is cached yet
+        assert!(content.digests.lock().unwrap().is_empty());
     }
 
     #[test]
@@ -306,7 +886,7 @@ mod tests {
         let content = ContentData::from("Hello, world!");
         let hash = content.compute_sha256();
 
-        assert!(content.content_sha256.lock().unwrap().is_some());
+        assert!(content.digests.lock().unwrap().contains_key(&HashAlgorithm::Sha256));
         assert_eq!(hash.len(), 32); // SHA256 is 32 bytes
 
         // Test getting cached hash
@@ -314,6 +894,204 @@ mod tests {
         assert_eq!(hash, hash2);
     }
 
+    #[test]
+    fn test_digest_multiple_algorithms_coexist() {
+        let content = ContentData::from("Hello, world!");
+
+        let sha256 = content.digest(HashAlgorithm::Sha256);
+        let blake3 = content.digest(HashAlgorithm::Blake3);
+        let ripemd160 = content.digest(HashAlgorithm::Ripemd160);
+
+        assert_eq!(sha256.kind(), HashAlgorithm::Sha256);
+        assert_eq!(sha256.as_slice().len(), 32);
+        assert_eq!(blake3.kind(), HashAlgorithm::Blake3);
+        assert_eq!(blake3.as_slice().len(), 32);
+        assert_eq!(ripemd160.kind(), HashAlgorithm::Ripemd160);
+        assert_eq!(ripemd160.as_slice().len(), 20);
+
+        let guard = content.digests.lock().unwrap();
+        assert_eq!(guard.len(), 3);
+    }
+
+    #[test]
+    fn test_verify_accepts_matching_digest_and_rejects_mismatch() {
+        let content = ContentData::from("Hello, world!");
+        let hash = content.digest(HashAlgorithm::Blake3);
+
+        assert!(content.verify(&hash).is_ok());
+
+        let wrong = ContentHash::Blake3([0u8; 32]);
+        assert!(content.verify(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_content_hash_from_slice_rejects_wrong_length() {
+        let short = vec![0u8; 10];
+        assert!(ContentHash::from_slice(HashAlgorithm::Sha256, &short).is_err());
+
+        let full = vec![0u8; 32];
+        assert!(ContentHash::from_slice(HashAlgorithm::Sha256, &full).is_ok());
+    }
+
+    #[test]
+    fn test_sha256d_differs_from_sha256() {
+        let content = ContentData::from("Hello, world!");
+        assert_ne!(content.sha256d(), content.sha256());
+        assert_eq!(content.sha256d().len(), 32);
+    }
+
+    #[test]
+    fn test_hash_hex_le_is_byte_reversed_hash_hex_be() {
+        let content = ContentData::from("Hello, world!");
+
+        let be = content.hash_hex_be();
+        let le = content.hash_hex_le();
+
+        let mut be_bytes = hex::decode(&be).unwrap();
+        be_bytes.reverse();
+        assert_eq!(hex::encode(be_bytes), le);
+    }
+
+    #[test]
+    fn test_hash_hex_roundtrip() {
+        let content = ContentData::from("Hello, world!");
+        let expected = content.digest(HashAlgorithm::Sha256d);
+
+        let from_be = ContentData::from_hash_hex_be(&content.hash_hex_be()).unwrap();
+        let from_le = ContentData::from_hash_hex_le(&content.hash_hex_le()).unwrap();
+
+        assert_eq!(from_be, expected);
+        assert_eq!(from_le, expected);
+    }
+
+    #[test]
+    fn test_from_hash_hex_rejects_invalid_hex() {
+        assert!(ContentData::from_hash_hex_be("not hex").is_err());
+    }
+
+    #[test]
+    fn test_content_hasher_matches_one_shot_digest() {
+        let mut hasher = ContentHasher::new(HashAlgorithm::Sha256);
+        hasher.update(b"Hello, ").unwrap();
+        hasher.update(b"world!").unwrap();
+        let streamed = hasher.finalize();
+
+        let expected = ContentData::from("Hello, world!").digest(HashAlgorithm::Sha256);
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_content_hasher_rejects_update_after_finalize_without_reset() {
+        let mut hasher = ContentHasher::new(HashAlgorithm::Blake3);
+        hasher.update(b"data").unwrap();
+        hasher.finalize();
+
+        assert!(hasher.update(b"more").is_err());
+
+        hasher.reset();
+        assert!(hasher.update(b"more").is_ok());
+    }
+
+    #[test]
+    fn test_from_reader_streams_and_caches_digest() {
+        let source = ContentSource::new();
+        let reader = std::io::Cursor::new(b"Hello, world!".to_vec());
+        let content = ContentData::from_reader(source, reader, HashAlgorithm::Sha256).unwrap();
+
+        assert_eq!(content.as_str().unwrap(), "Hello, world!");
+        assert!(content.digests.lock().unwrap().contains_key(&HashAlgorithm::Sha256));
+        assert_eq!(
+            content.digest(HashAlgorithm::Sha256),
+            ContentData::from("Hello, world!").digest(HashAlgorithm::Sha256)
+        );
+    }
+
+    #[test]
+    fn test_crc32_is_cached_and_verifiable() {
+        let content = ContentData::from("Hello, world!");
+        let crc = content.crc32();
+
+        assert!(content.crc32.lock().unwrap().is_some());
+        assert_eq!(content.crc32(), crc);
+        assert!(content.verify_crc32(crc).is_ok());
+        assert!(content.verify_crc32(crc ^ 1).is_err());
+    }
+
+    #[test]
+    fn test_fast_eq_matches_partial_eq_semantics() {
+        let a = ContentData::from("Hello, world!");
+        let b = ContentData::from("Hello, world!");
+        let c = ContentData::from("Something else entirely");
+
+        assert!(a.fast_eq(&b));
+        assert!(!a.fast_eq(&c));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let content = ContentData::from("Hello, world!");
+        let options = EncryptionOptions::default().with_iterations(100);
+
+        let encrypted = content.encrypt_with("correct horse", &options).unwrap();
+        assert_ne!(encrypted.as_bytes(), content.as_bytes());
+        assert_eq!(encrypted.content_source, content.content_source);
+
+        let decrypted = encrypted.decrypt_with("correct horse", &options).unwrap();
+        assert_eq!(decrypted.as_str().unwrap(), "Hello, world!");
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_decrypt_rejects_wrong_password() {
+        let content = ContentData::from("Hello, world!");
+        let options = EncryptionOptions::default().with_iterations(100);
+
+        let encrypted = content.encrypt_with("correct horse", &options).unwrap();
+        assert!(encrypted.decrypt_with("wrong password", &options).is_err());
+    }
+
+    #[test]
+    fn test_content_id_prefix_matching_is_nibble_precise() {
+        let content = ContentData::from("Hello, world!");
+        let id = content.content_id(HashAlgorithm::Sha256);
+        let full_hex = id.to_hex();
+
+        assert!(id.matches_prefix(&full_hex[..7]));
+        assert!(id.matches_prefix(&full_hex[..7].to_uppercase()));
+        assert!(!id.matches_prefix("zzzzzzz"));
+        assert!(id.matches_prefix(&full_hex));
+    }
+
+    #[test]
+    fn test_content_id_from_hex_roundtrip() {
+        let content = ContentData::from("Hello, world!");
+        let id = content.content_id(HashAlgorithm::Blake3);
+
+        let parsed = ContentId::from_hex(HashAlgorithm::Blake3, &id.to_hex()).unwrap();
+        assert_eq!(id, parsed);
+    }
+
+    #[test]
+    fn test_resolve_prefix_detects_ambiguity_and_missing() {
+        let a = ContentData::from("aaa").content_id(HashAlgorithm::Sha256);
+        let b = ContentData::from("bbb").content_id(HashAlgorithm::Sha256);
+        let known = vec![a, b];
+
+        // A prefix of the full hex string is unambiguous
+        let full = a.to_hex();
+        let resolved = ContentId::resolve_prefix(&full[..8], &known).unwrap();
+        assert_eq!(*resolved, a);
+
+        // An empty prefix matches everything, so it's ambiguous
+        assert!(ContentId::resolve_prefix("", &known).is_err());
+
+        // A prefix matching nothing is an error too
+        assert!(ContentId::resolve_prefix("ffffffffff", &known).is_err());
+    }
+
     #[test]
     fn test_sha256_verification() {
         let content = ContentData::from("Hello, world!");
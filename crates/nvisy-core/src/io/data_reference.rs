@@ -87,8 +87,10 @@ impl DataReference {
         self.content_type.type_name()
     }
 
-    /// Get the estimated size of the content in bytes
-    pub fn estimated_size(&self) -> usize {
+    /// Get the estimated size of the content in bytes, if known
+    ///
+    /// `None` only for a [`Content::Stream`] with no `size_hint`.
+    pub fn estimated_size(&self) -> Option<usize> {
         self.content_type.estimated_size()
     }
 }
@@ -104,7 +106,7 @@ mod tests {
 
         assert_eq!(data_ref.content_type_name(), "text");
         assert!(data_ref.mapping_id().is_none());
-        assert_eq!(data_ref.estimated_size(), 13);
+        assert_eq!(data_ref.estimated_size(), Some(13));
     }
 
     #[test]
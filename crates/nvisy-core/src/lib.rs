@@ -12,6 +12,8 @@
 //! ## Features
 //!
 //! - `serde`: Enable serialization support with serde
+//! - `encryption`: Enable password-based AES-256-GCM sealing of content via
+//!   [`ContentData::encrypt`](io::ContentData::encrypt)
 //!
 //! ## Core Types
 //!
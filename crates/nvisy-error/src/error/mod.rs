@@ -1,5 +1,8 @@
 //! Structured error handling with source classification and context tracking.
 
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
+
 use hipstr::HipStr;
 
 pub use crate::error::error_source::ErrorResource;
@@ -28,6 +31,11 @@ pub struct Error {
     pub context: Option<HipStr<'static>>,
     /// Primary error message.
     pub message: HipStr<'static>,
+
+    /// Backtrace captured at construction time, behind the `backtrace`
+    /// feature so release builds can opt out of the capture cost.
+    #[cfg(feature = "backtrace")]
+    pub backtrace: Backtrace,
 }
 
 /// Result type alias using the nvisy Error.
@@ -46,6 +54,8 @@ impl Error {
             source: None,
             context: None,
             message: message.into(),
+            #[cfg(feature = "backtrace")]
+            backtrace: Backtrace::capture(),
         }
     }
 
@@ -62,6 +72,8 @@ impl Error {
             source: Some(source.into()),
             context: None,
             message: message.into(),
+            #[cfg(feature = "backtrace")]
+            backtrace: Backtrace::capture(),
         }
     }
 
@@ -100,6 +112,59 @@ impl Error {
 
         parts.join(" ")
     }
+
+    /// Iterates the causal chain, starting with the immediate `source` and
+    /// walking each subsequent error's own `source()` downward.
+    ///
+    /// Yields nothing if this error has no source.
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> + '_ {
+        let mut current: Option<&(dyn std::error::Error + 'static)> =
+            self.source.as_deref().map(|e| e as &(dyn std::error::Error + 'static));
+
+        std::iter::from_fn(move || {
+            let error = current?;
+            current = error.source();
+            Some(error)
+        })
+    }
+
+    /// Returns the deepest error in the causal chain, i.e. the last error
+    /// reachable by repeatedly following `source()`.
+    ///
+    /// Returns `None` if this error has no source.
+    pub fn root_cause(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.chain().last()
+    }
+
+    /// Returns the display message for the error, optionally rendering the
+    /// full causal chain and captured backtrace.
+    ///
+    /// The terse form matches [`Self::display_message`] (and therefore
+    /// `Display`); `verbose` additionally appends each error in
+    /// [`Self::chain`] and, when the `backtrace` feature is enabled, the
+    /// backtrace captured when this error was constructed.
+    pub fn display_verbose(&self, verbose: bool) -> String {
+        let mut message = self.display_message();
+
+        if !verbose {
+            return message;
+        }
+
+        let causes: Vec<String> = self.chain().map(ToString::to_string).collect();
+        if !causes.is_empty() {
+            message.push_str("\n\nCaused by:");
+            for (index, cause) in causes.iter().enumerate() {
+                message.push_str(&format!("\n  {index}: {cause}"));
+            }
+        }
+
+        #[cfg(feature = "backtrace")]
+        {
+            message.push_str(&format!("\n\nBacktrace:\n{}", self.backtrace));
+        }
+
+        message
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -133,4 +198,45 @@ mod tests {
             .with_context("additional context");
         assert_eq!(error.context.as_deref(), Some("additional context"));
     }
+
+    #[test]
+    fn test_chain_empty_without_source() {
+        let error = Error::new(ErrorType::Config, ErrorResource::Core, "no source here");
+        assert_eq!(error.chain().count(), 0);
+        assert!(error.root_cause().is_none());
+    }
+
+    #[test]
+    fn test_chain_walks_nested_sources() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
+        let error = Error::from_source(
+            ErrorType::Runtime,
+            ErrorResource::Core,
+            "failed to read file",
+            io_error,
+        );
+
+        let chain: Vec<String> = error.chain().map(|e| e.to_string()).collect();
+        assert_eq!(chain, vec!["file missing".to_string()]);
+        assert_eq!(error.root_cause().unwrap().to_string(), "file missing");
+    }
+
+    #[test]
+    fn test_display_verbose_includes_chain() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
+        let error = Error::from_source(
+            ErrorType::Runtime,
+            ErrorResource::Core,
+            "failed to read file",
+            io_error,
+        );
+
+        let terse = error.display_verbose(false);
+        assert_eq!(terse, error.display_message());
+        assert!(!terse.contains("Caused by"));
+
+        let verbose = error.display_verbose(true);
+        assert!(verbose.contains("Caused by"));
+        assert!(verbose.contains("file missing"));
+    }
 }
@@ -71,5 +71,10 @@ pub mod prelude {
 
     pub use crate::Component;
     pub use crate::error::{Error, ErrorResource, ErrorType, Result, BoxError};
-    pub use crate::status::{ComponentStatus, HealthStatus, OperationalState, UpdateSeverity};
+    #[cfg(feature = "jiff")]
+    pub use crate::status::HealthTracker;
+    pub use crate::status::{
+        AggregateStatus, ChildStatus, ComponentStatus, HealthStatus, OperationalState,
+        StatusSelector, UpdateSeverity,
+    };
 }
@@ -0,0 +1,255 @@
+//! Hierarchical rollup of many child component statuses into one overall status.
+
+use hipstr::HipStr;
+
+use crate::status::{ComponentStatus, HealthStatus};
+use crate::{ErrorResource, ErrorType, Result};
+
+/// A single child's status within an [`AggregateStatus`], along with how much
+/// its degradation should affect the parent rollup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct ChildStatus {
+    name: HipStr<'static>,
+    status: ComponentStatus,
+    critical: bool,
+    redundant: bool,
+}
+
+impl ChildStatus {
+    /// Creates a new child status. Children are critical and non-redundant by default.
+    pub fn new(name: impl Into<HipStr<'static>>, status: ComponentStatus) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            critical: true,
+            redundant: false,
+        }
+    }
+
+    /// Sets whether this child is critical to overall system health.
+    ///
+    /// A non-critical child's degradation is capped at [`HealthStatus::MinorDegraded`]
+    /// when propagated to the parent, regardless of how badly it has actually failed.
+    pub const fn with_critical(mut self, critical: bool) -> Self {
+        self.critical = critical;
+        self
+    }
+
+    /// Sets whether this child is redundant, i.e. has a failover covering it.
+    ///
+    /// A redundant child's degradation is also capped at [`HealthStatus::MinorDegraded`],
+    /// and it is excluded from the "all critical children offline" escalation that
+    /// would otherwise report the parent itself as [`HealthStatus::Offline`].
+    pub const fn with_redundant(mut self, redundant: bool) -> Self {
+        self.redundant = redundant;
+        self
+    }
+}
+
+/// Composes many [`ChildStatus`] values into one overall [`ComponentStatus`] via
+/// worst-case propagation, the way a composite component rolls up the health of
+/// everything it depends on into a single reported state.
+///
+/// Rules:
+/// - A child that is not degraded has no effect on the rollup.
+/// - A degraded child that is not critical, or that is marked redundant, raises
+///   the overall health to at most [`HealthStatus::MinorDegraded`].
+/// - A critical, non-redundant child that is [`HealthStatus::Offline`] raises the
+///   overall health to at least [`HealthStatus::MajorDegraded`]; any other degraded
+///   critical, non-redundant child raises it to at least [`HealthStatus::MinorDegraded`].
+/// - If every critical, non-redundant child is [`HealthStatus::Offline`], the overall
+///   health becomes [`HealthStatus::Offline`] too.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct AggregateStatus {
+    children: Vec<ChildStatus>,
+}
+
+impl AggregateStatus {
+    /// Creates an empty aggregate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a child status to the aggregate.
+    pub fn with_child(mut self, child: ChildStatus) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Rolls up all child statuses into a single [`ComponentStatus`].
+    ///
+    /// The names and health of offending (degraded) children are recorded in
+    /// the resulting status's `context`, so the worst-case health alone doesn't
+    /// hide which children caused it.
+    pub fn rollup(&self) -> ComponentStatus {
+        let mut overall = HealthStatus::Online;
+        let mut offenders = Vec::new();
+        let mut load_bearing = 0usize;
+        let mut load_bearing_offline = 0usize;
+
+        for child in &self.children {
+            let exempt = child.redundant || !child.critical;
+            if !exempt {
+                load_bearing += 1;
+            }
+
+            if !child.status.health_status.is_degraded() {
+                continue;
+            }
+
+            offenders.push(format!(
+                "{}: {}",
+                child.name, child.status.health_status
+            ));
+
+            let contribution = if exempt {
+                HealthStatus::MinorDegraded
+            } else if child.status.health_status == HealthStatus::Offline {
+                load_bearing_offline += 1;
+                HealthStatus::MajorDegraded
+            } else {
+                HealthStatus::MinorDegraded
+            };
+
+            if contribution.severity_rank() > overall.severity_rank() {
+                overall = contribution;
+            }
+        }
+
+        if load_bearing > 0 && load_bearing_offline == load_bearing {
+            overall = HealthStatus::Offline;
+        }
+
+        let mut status = ComponentStatus::new(overall);
+        if !offenders.is_empty() {
+            status = status
+                .with_message(format!(
+                    "{} of {} components degraded",
+                    offenders.len(),
+                    self.children.len()
+                ))
+                .with_details(offenders.join(", "));
+        }
+        status
+    }
+
+    /// Rolls up all child statuses and converts the result into a [`Result`],
+    /// collapsing an entire subsystem's health to one outcome at a service boundary.
+    pub fn into_result(self, error_type: ErrorType, error_resource: ErrorResource) -> Result<()> {
+        self.rollup().into_result(error_type, error_resource)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_online_rolls_up_to_online() {
+        let aggregate = AggregateStatus::new()
+            .with_child(ChildStatus::new(
+                "cache",
+                ComponentStatus::new(HealthStatus::Online),
+            ))
+            .with_child(ChildStatus::new(
+                "db",
+                ComponentStatus::new(HealthStatus::Online),
+            ));
+
+        assert_eq!(aggregate.rollup().health_status, HealthStatus::Online);
+    }
+
+    #[test]
+    fn test_minor_degraded_child_caps_at_minor() {
+        let aggregate = AggregateStatus::new().with_child(ChildStatus::new(
+            "db",
+            ComponentStatus::new(HealthStatus::MinorDegraded),
+        ));
+
+        assert_eq!(
+            aggregate.rollup().health_status,
+            HealthStatus::MinorDegraded
+        );
+    }
+
+    #[test]
+    fn test_critical_offline_child_raises_to_major_degraded() {
+        let aggregate = AggregateStatus::new()
+            .with_child(ChildStatus::new(
+                "db-primary",
+                ComponentStatus::new(HealthStatus::Offline),
+            ))
+            .with_child(ChildStatus::new(
+                "cache",
+                ComponentStatus::new(HealthStatus::Online),
+            ));
+
+        let status = aggregate.rollup();
+        assert_eq!(status.health_status, HealthStatus::MajorDegraded);
+        assert!(status.context.as_deref().unwrap().contains("db-primary"));
+    }
+
+    #[test]
+    fn test_all_critical_children_offline_rolls_up_to_offline() {
+        let aggregate = AggregateStatus::new()
+            .with_child(ChildStatus::new(
+                "db-primary",
+                ComponentStatus::new(HealthStatus::Offline),
+            ))
+            .with_child(ChildStatus::new(
+                "db-replica",
+                ComponentStatus::new(HealthStatus::Offline),
+            ));
+
+        assert_eq!(aggregate.rollup().health_status, HealthStatus::Offline);
+    }
+
+    #[test]
+    fn test_non_critical_offline_child_does_not_escalate() {
+        let aggregate = AggregateStatus::new()
+            .with_child(ChildStatus::new(
+                "db",
+                ComponentStatus::new(HealthStatus::Online),
+            ))
+            .with_child(
+                ChildStatus::new("metrics-exporter", ComponentStatus::new(HealthStatus::Offline))
+                    .with_critical(false),
+            );
+
+        assert_eq!(
+            aggregate.rollup().health_status,
+            HealthStatus::MinorDegraded
+        );
+    }
+
+    #[test]
+    fn test_redundant_offline_child_does_not_escalate_or_trigger_all_offline() {
+        let aggregate = AggregateStatus::new()
+            .with_child(ChildStatus::new(
+                "db-primary",
+                ComponentStatus::new(HealthStatus::Online),
+            ))
+            .with_child(
+                ChildStatus::new("db-replica", ComponentStatus::new(HealthStatus::Offline))
+                    .with_redundant(true),
+            );
+
+        assert_eq!(
+            aggregate.rollup().health_status,
+            HealthStatus::MinorDegraded
+        );
+    }
+
+    #[test]
+    fn test_into_result_fails_when_degraded() {
+        let aggregate = AggregateStatus::new().with_child(ChildStatus::new(
+            "db",
+            ComponentStatus::new(HealthStatus::Offline),
+        ));
+
+        let result = aggregate.into_result(ErrorType::Runtime, ErrorResource::Engine);
+        assert!(result.is_err());
+    }
+}
@@ -1,9 +1,9 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use strum::{AsRefStr, Display};
+use strum::{AsRefStr, Display, EnumString};
 
 /// Component health status indicating operational wellness and degradation levels.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, AsRefStr, Display)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, AsRefStr, Display, EnumString)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[strum(serialize_all = "snake_case")]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -51,6 +51,18 @@ impl HealthStatus {
             Self::MinorDegraded | Self::MajorDegraded | Self::Offline
         )
     }
+
+    /// Returns the relative severity rank of this health status, for ordering
+    /// and worst-case rollup across multiple statuses. Higher is worse.
+    #[must_use]
+    pub const fn severity_rank(&self) -> u8 {
+        match self {
+            Self::Online => 0,
+            Self::Unknown | Self::MinorDegraded => 1,
+            Self::MajorDegraded => 2,
+            Self::Offline => 3,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -65,4 +77,13 @@ mod tests {
         assert_eq!(HealthStatus::Offline.as_ref(), "offline");
         assert_eq!(HealthStatus::Unknown.as_ref(), "unknown");
     }
+
+    #[test]
+    fn test_severity_rank_ordering() {
+        assert!(HealthStatus::Offline.severity_rank() > HealthStatus::MajorDegraded.severity_rank());
+        assert!(
+            HealthStatus::MajorDegraded.severity_rank() > HealthStatus::MinorDegraded.severity_rank()
+        );
+        assert!(HealthStatus::MinorDegraded.severity_rank() > HealthStatus::Online.severity_rank());
+    }
 }
@@ -0,0 +1,250 @@
+//! Bounded transition history for component health, with flapping and
+//! availability metrics derived from it.
+
+use std::collections::VecDeque;
+
+use jiff::{SignedDuration, Timestamp};
+
+use crate::status::HealthStatus;
+
+/// Default number of transitions retained when none is specified.
+const DEFAULT_CAPACITY: usize = 64;
+
+/// Tracks a bounded history of [`HealthStatus`] transitions over time.
+///
+/// Only actual changes in status are recorded: calling [`HealthTracker::record`]
+/// repeatedly with the same status as the last entry is a no-op. This keeps the
+/// history focused on transitions, which is what [`HealthTracker::transitions_in`],
+/// [`HealthTracker::is_flapping`], and [`HealthTracker::availability`] reason about.
+///
+/// The history is capacity-bounded: once full, recording a new transition drops
+/// the oldest entry.
+#[derive(Debug, Clone)]
+pub struct HealthTracker {
+    capacity: usize,
+    entries: VecDeque<(Timestamp, HealthStatus)>,
+}
+
+impl HealthTracker {
+    /// Creates a new tracker with the default capacity.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Creates a new tracker that retains at most `capacity` transitions.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Records a status observed at the current time.
+    ///
+    /// No-op if `status` matches the most recently recorded status.
+    pub fn record(&mut self, status: HealthStatus) {
+        self.record_at(status, Timestamp::now());
+    }
+
+    /// Records a status observed at `timestamp`.
+    ///
+    /// No-op if `status` matches the most recently recorded status.
+    pub fn record_at(&mut self, status: HealthStatus, timestamp: Timestamp) {
+        if self.entries.back().is_some_and(|(_, last)| *last == status) {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((timestamp, status));
+    }
+
+    /// Returns the most recently recorded status, if any.
+    #[must_use]
+    pub fn current(&self) -> Option<HealthStatus> {
+        self.entries.back().map(|(_, status)| *status)
+    }
+
+    /// Returns the number of transitions recorded so far, up to the tracker's capacity.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no transitions have been recorded yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Counts the number of transitions recorded within the trailing `window`.
+    #[must_use]
+    pub fn transitions_in(&self, window: SignedDuration) -> usize {
+        let cutoff = Timestamp::now().as_nanosecond() - window.as_nanos();
+        self.entries
+            .iter()
+            .filter(|(ts, _)| ts.as_nanosecond() >= cutoff)
+            .count()
+    }
+
+    /// Returns `true` if the number of transitions within `window` exceeds `threshold`.
+    ///
+    /// Useful for suppressing alert noise from a component bouncing between, say,
+    /// `Online` and `MinorDegraded` rather than settling into one state.
+    #[must_use]
+    pub fn is_flapping(&self, window: SignedDuration, threshold: usize) -> bool {
+        self.transitions_in(window) > threshold
+    }
+
+    /// Returns the fraction of wall-clock time within the trailing `window` spent in
+    /// an operational state ([`HealthStatus::is_operational`]), as a value in `0.0..=1.0`.
+    ///
+    /// Time before the tracker's first known status within the window is excluded
+    /// from both the numerator and denominator, since the component's state during
+    /// that span is unknown. Returns `1.0` if no state is known within the window at
+    /// all, since an absence of recorded degradation is assumed to mean healthy.
+    #[must_use]
+    pub fn availability(&self, window: SignedDuration) -> f64 {
+        let now = Timestamp::now().as_nanosecond();
+        let cutoff = now - window.as_nanos();
+
+        let mut last_status = self
+            .entries
+            .iter()
+            .rev()
+            .find(|(ts, _)| ts.as_nanosecond() <= cutoff)
+            .map(|(_, status)| *status);
+        let mut last_nanos = cutoff;
+
+        let mut operational_nanos: i128 = 0;
+        let mut total_nanos: i128 = 0;
+
+        for (ts, status) in self
+            .entries
+            .iter()
+            .filter(|(ts, _)| ts.as_nanosecond() > cutoff)
+        {
+            let ts_nanos = ts.as_nanosecond();
+            if let Some(status) = last_status {
+                let elapsed = ts_nanos - last_nanos;
+                total_nanos += elapsed;
+                if status.is_operational() {
+                    operational_nanos += elapsed;
+                }
+            }
+            last_status = Some(*status);
+            last_nanos = ts_nanos;
+        }
+
+        if let Some(status) = last_status {
+            let elapsed = now - last_nanos;
+            total_nanos += elapsed;
+            if status.is_operational() {
+                operational_nanos += elapsed;
+            }
+        }
+
+        if total_nanos <= 0 {
+            return 1.0;
+        }
+
+        (operational_nanos as f64 / total_nanos as f64).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for HealthTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_ignores_repeated_status() {
+        let mut tracker = HealthTracker::new();
+        tracker.record(HealthStatus::Online);
+        tracker.record(HealthStatus::Online);
+        tracker.record(HealthStatus::Online);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn test_record_tracks_each_transition() {
+        let mut tracker = HealthTracker::new();
+        tracker.record(HealthStatus::Online);
+        tracker.record(HealthStatus::MinorDegraded);
+        tracker.record(HealthStatus::Online);
+        assert_eq!(tracker.len(), 3);
+        assert_eq!(tracker.current(), Some(HealthStatus::Online));
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let mut tracker = HealthTracker::with_capacity(2);
+        tracker.record(HealthStatus::Online);
+        tracker.record(HealthStatus::MinorDegraded);
+        tracker.record(HealthStatus::MajorDegraded);
+        assert_eq!(tracker.len(), 2);
+        assert_eq!(tracker.current(), Some(HealthStatus::MajorDegraded));
+    }
+
+    #[test]
+    fn test_transitions_in_counts_within_window() {
+        let mut tracker = HealthTracker::new();
+        let base = Timestamp::now();
+        tracker.record_at(HealthStatus::Online, base);
+        tracker.record_at(HealthStatus::MinorDegraded, base + SignedDuration::from_secs(10));
+        tracker.record_at(HealthStatus::Online, base + SignedDuration::from_secs(20));
+
+        assert_eq!(tracker.transitions_in(SignedDuration::from_secs(3600)), 3);
+    }
+
+    #[test]
+    fn test_is_flapping_detects_bouncing_status() {
+        let mut tracker = HealthTracker::new();
+        let base = Timestamp::now();
+        for i in 0..6 {
+            let status = if i % 2 == 0 {
+                HealthStatus::Online
+            } else {
+                HealthStatus::MinorDegraded
+            };
+            tracker.record_at(status, base + SignedDuration::from_secs(i64::from(i) * 5));
+        }
+
+        assert!(tracker.is_flapping(SignedDuration::from_secs(3600), 3));
+        assert!(!tracker.is_flapping(SignedDuration::from_secs(3600), 10));
+    }
+
+    #[test]
+    fn test_availability_all_online_is_one() {
+        let mut tracker = HealthTracker::new();
+        let base = Timestamp::now() - SignedDuration::from_secs(60);
+        tracker.record_at(HealthStatus::Online, base);
+
+        assert_eq!(tracker.availability(SignedDuration::from_secs(120)), 1.0);
+    }
+
+    #[test]
+    fn test_availability_with_no_history_defaults_to_one() {
+        let tracker = HealthTracker::new();
+        assert_eq!(tracker.availability(SignedDuration::from_secs(60)), 1.0);
+    }
+
+    #[test]
+    fn test_availability_accounts_for_degraded_span() {
+        let mut tracker = HealthTracker::new();
+        let base = Timestamp::now() - SignedDuration::from_secs(100);
+        tracker.record_at(HealthStatus::Online, base);
+        tracker.record_at(HealthStatus::Offline, base + SignedDuration::from_secs(50));
+
+        let availability = tracker.availability(SignedDuration::from_secs(100));
+        assert!(availability > 0.0 && availability < 1.0);
+    }
+}
@@ -7,14 +7,24 @@ use jiff::Timestamp;
 use jiff::fmt::serde::timestamp::nanosecond::optional as optional_nanosecond;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "jiff")]
+use std::time::{Duration, Instant};
 
+pub use crate::status::aggregate::{AggregateStatus, ChildStatus};
 pub use crate::status::health_status::HealthStatus;
+#[cfg(feature = "jiff")]
+pub use crate::status::health_tracker::HealthTracker;
 pub use crate::status::operational_state::OperationalState;
+pub use crate::status::selector::StatusSelector;
 pub use crate::status::update_severity::UpdateSeverity;
 use crate::{Error, ErrorResource, ErrorType, Result};
 
+mod aggregate;
 mod health_status;
+#[cfg(feature = "jiff")]
+mod health_tracker;
 mod operational_state;
+mod selector;
 mod update_severity;
 
 /// Component status tracking health, operational state, and contextual information.
@@ -41,6 +51,13 @@ pub struct ComponentStatus {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(feature = "serde", serde(with = "optional_nanosecond"))]
     pub timestamp: Option<Timestamp>,
+
+    /// Monotonic anchor paired with `timestamp`, used to measure time-in-state
+    /// immune to wall-clock adjustments. Not serialized: a monotonic instant is
+    /// only meaningful within the process that captured it.
+    #[cfg(feature = "jiff")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub monotonic_anchor: Option<Instant>,
 }
 
 impl ComponentStatus {
@@ -66,6 +83,8 @@ impl ComponentStatus {
             context: None,
             #[cfg(feature = "jiff")]
             timestamp: None,
+            #[cfg(feature = "jiff")]
+            monotonic_anchor: None,
         }
     }
 
@@ -106,10 +125,12 @@ impl ComponentStatus {
         self
     }
 
-    /// Adds the current timestamp to the status.
+    /// Adds the current timestamp to the status, along with a monotonic anchor
+    /// that [`ComponentStatus::time_in_state`] measures elapsed time from.
     #[cfg(feature = "jiff")]
     pub fn with_current_timestamp(mut self) -> Self {
         self.timestamp = Some(Timestamp::now());
+        self.monotonic_anchor = Some(Instant::now());
         self
     }
 }
@@ -145,6 +166,19 @@ impl ComponentStatus {
         self.operational_state.is_stopped()
     }
 
+    /// Returns how long the component has held this status, measured from the
+    /// monotonic anchor set by [`ComponentStatus::with_current_timestamp`].
+    ///
+    /// Unlike comparing `timestamp` against the current wall clock, this is
+    /// immune to NTP adjustments and clock skew. Returns `None` if no anchor
+    /// was recorded, which is the case for statuses built with
+    /// [`ComponentStatus::with_timestamp`] instead.
+    #[cfg(feature = "jiff")]
+    #[must_use]
+    pub fn time_in_state(&self) -> Option<Duration> {
+        self.monotonic_anchor.map(|anchor| anchor.elapsed())
+    }
+
     /// Converts the component status into a Result.
     ///
     /// Returns `Ok(())` if the component is operational, otherwise returns an `Err`
@@ -200,4 +234,21 @@ mod tests {
         assert_eq!(error.message, "Component failed");
         assert_eq!(error.context.as_deref(), Some("Database connection lost"));
     }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_time_in_state_tracks_monotonic_anchor() {
+        let status = ComponentStatus::new(HealthStatus::MajorDegraded).with_current_timestamp();
+
+        let elapsed = status.time_in_state();
+        assert!(elapsed.is_some());
+        assert!(elapsed.unwrap() < Duration::from_secs(1));
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_time_in_state_none_without_anchor() {
+        let status = ComponentStatus::new(HealthStatus::Online);
+        assert!(status.time_in_state().is_none());
+    }
 }
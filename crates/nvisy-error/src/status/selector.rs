@@ -0,0 +1,198 @@
+//! Declarative filters for selecting statuses of interest out of a stream.
+
+use std::str::FromStr;
+
+use glob::Pattern;
+
+use crate::status::{ComponentStatus, HealthStatus, UpdateSeverity};
+use crate::{Error, ErrorResource, ErrorType, Result};
+
+/// A declarative filter over [`ComponentStatus`] values.
+///
+/// Each field is independently optional; a selector with every field unset
+/// matches everything. Built up via the `with_*` builder methods, or parsed
+/// from a compact textual syntax with [`StatusSelector::from_str`]:
+///
+/// ```text
+/// severity>=warning,health=offline|major_degraded
+/// ```
+///
+/// This selects statuses at or above [`UpdateSeverity::Warning`] whose
+/// [`HealthStatus`] is [`HealthStatus::Offline`] or [`HealthStatus::MajorDegraded`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct StatusSelector {
+    min_severity: Option<UpdateSeverity>,
+    health: Option<Vec<HealthStatus>>,
+    name_glob: Option<Pattern>,
+}
+
+impl StatusSelector {
+    /// Creates a selector that matches everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match statuses whose `update_severity` is at or above `min_severity`.
+    pub fn with_min_severity(mut self, min_severity: UpdateSeverity) -> Self {
+        self.min_severity = Some(min_severity);
+        self
+    }
+
+    /// Only match statuses whose `health_status` is one of `health`.
+    pub fn with_health(mut self, health: impl IntoIterator<Item = HealthStatus>) -> Self {
+        self.health = Some(health.into_iter().collect());
+        self
+    }
+
+    /// Only match components whose name matches `name_glob`.
+    ///
+    /// [`ComponentStatus`] itself carries no name, so this only takes effect
+    /// through [`StatusSelector::matches_named`].
+    pub fn with_name_glob(mut self, name_glob: Pattern) -> Self {
+        self.name_glob = Some(name_glob);
+        self
+    }
+
+    /// Checks whether `status` satisfies the severity and health filters.
+    #[must_use]
+    pub fn matches(&self, status: &ComponentStatus) -> bool {
+        if let Some(min_severity) = self.min_severity {
+            if status.update_severity.priority_level() < min_severity.priority_level() {
+                return false;
+            }
+        }
+
+        if let Some(health) = &self.health {
+            if !health.contains(&status.health_status) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Checks whether `status` satisfies this selector, additionally matching
+    /// `name` against the component-name glob, if one is set.
+    #[must_use]
+    pub fn matches_named(&self, name: &str, status: &ComponentStatus) -> bool {
+        if let Some(name_glob) = &self.name_glob {
+            if !name_glob.matches(name) {
+                return false;
+            }
+        }
+
+        self.matches(status)
+    }
+}
+
+impl FromStr for StatusSelector {
+    type Err = Error;
+
+    /// Parses a comma-separated list of `severity>=<level>`, `health=<a|b|c>`,
+    /// and `name=<glob>` clauses into a selector.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut selector = Self::new();
+
+        for clause in s.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            if let Some(value) = clause
+                .strip_prefix("severity>=")
+                .or_else(|| clause.strip_prefix("severity="))
+            {
+                let min_severity = value.trim().parse::<UpdateSeverity>().map_err(|_| {
+                    invalid_clause(clause, "unrecognized severity level")
+                })?;
+                selector = selector.with_min_severity(min_severity);
+            } else if let Some(value) = clause.strip_prefix("health=") {
+                let health = value
+                    .split('|')
+                    .map(|part| {
+                        part.trim()
+                            .parse::<HealthStatus>()
+                            .map_err(|_| invalid_clause(clause, "unrecognized health status"))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                selector = selector.with_health(health);
+            } else if let Some(value) = clause.strip_prefix("name=") {
+                let name_glob = Pattern::new(value.trim())
+                    .map_err(|_| invalid_clause(clause, "invalid glob pattern"))?;
+                selector = selector.with_name_glob(name_glob);
+            } else {
+                return Err(invalid_clause(clause, "unrecognized clause"));
+            }
+        }
+
+        Ok(selector)
+    }
+}
+
+fn invalid_clause(clause: &str, reason: &str) -> Error {
+    Error::new(
+        ErrorType::Config,
+        ErrorResource::Core,
+        format!("Invalid status selector clause '{clause}': {reason}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_severity_threshold() {
+        let selector = StatusSelector::new().with_min_severity(UpdateSeverity::Warning);
+
+        let ok = ComponentStatus::new(HealthStatus::MinorDegraded)
+            .with_update_severity(UpdateSeverity::Error);
+        let low = ComponentStatus::new(HealthStatus::Online).with_update_severity(UpdateSeverity::Info);
+
+        assert!(selector.matches(&ok));
+        assert!(!selector.matches(&low));
+    }
+
+    #[test]
+    fn test_matches_health_set() {
+        let selector =
+            StatusSelector::new().with_health([HealthStatus::Offline, HealthStatus::MajorDegraded]);
+
+        let offline = ComponentStatus::new(HealthStatus::Offline);
+        let online = ComponentStatus::new(HealthStatus::Online);
+
+        assert!(selector.matches(&offline));
+        assert!(!selector.matches(&online));
+    }
+
+    #[test]
+    fn test_matches_named_glob() {
+        let selector = StatusSelector::new().with_name_glob(Pattern::new("db-*").unwrap());
+        let status = ComponentStatus::new(HealthStatus::Online);
+
+        assert!(selector.matches_named("db-primary", &status));
+        assert!(!selector.matches_named("cache-primary", &status));
+    }
+
+    #[test]
+    fn test_from_str_compact_syntax() {
+        let selector: StatusSelector = "severity>=warning,health=offline|major_degraded"
+            .parse()
+            .unwrap();
+
+        assert_eq!(selector.min_severity, Some(UpdateSeverity::Warning));
+        assert_eq!(
+            selector.health,
+            Some(vec![HealthStatus::Offline, HealthStatus::MajorDegraded])
+        );
+    }
+
+    #[test]
+    fn test_from_str_rejects_unrecognized_clause() {
+        assert!("bogus=value".parse::<StatusSelector>().is_err());
+    }
+
+    #[test]
+    fn test_from_str_empty_matches_everything() {
+        let selector: StatusSelector = "".parse().unwrap();
+        let status = ComponentStatus::new(HealthStatus::Offline);
+        assert!(selector.matches(&status));
+    }
+}